@@ -150,6 +150,7 @@ pub fn build_model(
         force_recompilation: false,
         fetch_deps_only: false,
         skip_fetch_latest_git_deps: true,
+        offline: false,
         compiler_config: CompilerConfig {
             bytecode_version,
             compiler_version,
@@ -193,6 +194,7 @@ impl BuiltPackage {
             force_recompilation: false,
             fetch_deps_only: false,
             skip_fetch_latest_git_deps: options.skip_fetch_latest_git_deps,
+            offline: false,
             compiler_config: CompilerConfig {
                 bytecode_version,
                 compiler_version,
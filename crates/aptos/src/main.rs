@@ -17,6 +17,16 @@ fn main() {
     // Register hooks.
     move_tool::register_package_hooks();
 
+    // The binary is started again with the hidden `sandbox-task` subcommand to run the sandboxed tasks
+    // of the mutator and the specification tester.
+    if let Some(task) = move_mutator::sandbox::requested_task() {
+        if let Err(e) = move_spec_test::run_sandbox_task(&task) {
+            eprintln!("Error: {}", e);
+            exit(1);
+        }
+        return;
+    }
+
     // Create a runtime.
     let runtime = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
@@ -5,14 +5,16 @@ use crate::{common::types::load_account_arg, move_tool::CachedPackageRegistry};
 use aptos_framework::UPGRADE_POLICY_CUSTOM_FIELD;
 use futures::executor::block_on;
 use move_package::{
-    compilation::package_layout::CompiledPackageLayout, package_hooks::PackageHooks,
-    source_package::parsed_manifest::CustomDepInfo,
+    compilation::package_layout::CompiledPackageLayout,
+    package_hooks::{CustomDependencyResolver, PackageHooks},
+    source_package::parsed_manifest::{CustomDepInfo, PackageDigest},
 };
 use move_symbol_pool::Symbol;
 use reqwest::Url;
 
 pub fn register_package_hooks() {
-    move_package::package_hooks::register_package_hooks(Box::new(AptosPackageHooks {}))
+    move_package::package_hooks::register_package_hooks(Box::new(AptosPackageHooks {}));
+    move_package::package_hooks::register_custom_dependency_resolver(Box::new(AptosNodeResolver {}))
 }
 
 struct AptosPackageHooks {}
@@ -21,17 +23,26 @@ impl PackageHooks for AptosPackageHooks {
     fn custom_package_info_fields(&self) -> Vec<String> {
         vec![UPGRADE_POLICY_CUSTOM_FIELD.to_string()]
     }
+}
+
+/// Resolves the `{ aptos = <node url>, address = <addr> }` dependencies by downloading the
+/// packages published on chain.
+struct AptosNodeResolver {}
 
-    fn custom_dependency_key(&self) -> Option<String> {
-        Some("aptos".to_string())
+impl CustomDependencyResolver for AptosNodeResolver {
+    fn dependency_key(&self) -> String {
+        "aptos".to_string()
     }
 
-    fn resolve_custom_dependency(
+    fn resolve(
         &self,
         _dep_name: Symbol,
         info: &CustomDepInfo,
-    ) -> anyhow::Result<()> {
-        block_on(maybe_download_package(info))
+    ) -> anyhow::Result<Option<PackageDigest>> {
+        // The digest recorded on chain is the one of the original sources, not of the package
+        // saved to disk, so it cannot be checked here.
+        block_on(maybe_download_package(info))?;
+        Ok(None)
     }
 }
 
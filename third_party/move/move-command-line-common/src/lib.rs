@@ -9,6 +9,7 @@ pub mod character_sets;
 pub mod env;
 pub mod files;
 pub mod movey_constants;
+pub mod output_format;
 pub mod parser;
 pub mod testing;
 pub mod types;
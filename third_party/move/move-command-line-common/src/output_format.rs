@@ -0,0 +1,59 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Format of the command results printed on the standard output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human readable text.
+    #[default]
+    Human,
+    /// Structured JSON (a single document), for scripting. Diagnostics and progress of the tools
+    /// are still written to the standard error.
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(&self) -> bool {
+        *self == OutputFormat::Json
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!(
+                "Unknown output format '{s}', expected human or json"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Human => f.write_str("human"),
+            OutputFormat::Json => f.write_str("json"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_format_is_parsed() {
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("Human".parse(), Ok(OutputFormat::Human));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+        assert_eq!(OutputFormat::Json.to_string(), "json");
+    }
+}
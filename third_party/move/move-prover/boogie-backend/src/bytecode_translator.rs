@@ -102,22 +102,35 @@ impl<'env> BoogieTranslator<'env> {
         } else {
             options.vc_timeout
         };
+        if let Some(timeout) = self.get_function_timeout(fun_target) {
+            return timeout;
+        }
         fun_target
             .func_env
             .get_num_pragma(TIMEOUT_PRAGMA)
             .unwrap_or(default_timeout)
     }
 
+    /// Returns the timeout configured for the function in the options, if any.
+    fn get_function_timeout(&self, fun_target: &FunctionTarget) -> Option<usize> {
+        self.options
+            .function_timeouts
+            .get(&fun_target.func_env.get_full_name_str())
+            .copied()
+    }
+
     pub fn is_not_verified_timeout(&self, fun_target: &FunctionTarget) -> bool {
         let options = self.options;
         let estimate_timeout_opt = fun_target
             .func_env
             .get_num_pragma(VERIFY_DURATION_ESTIMATE_PRAGMA);
         if let Some(estimate_timeout) = estimate_timeout_opt {
-            let timeout = fun_target
-                .func_env
-                .get_num_pragma(TIMEOUT_PRAGMA)
-                .unwrap_or(options.vc_timeout);
+            let timeout = self.get_function_timeout(fun_target).unwrap_or_else(|| {
+                fun_target
+                    .func_env
+                    .get_num_pragma(TIMEOUT_PRAGMA)
+                    .unwrap_or(options.vc_timeout)
+            });
             estimate_timeout > timeout
         } else {
             false
@@ -7,7 +7,7 @@ use itertools::Itertools;
 use move_command_line_common::env::{read_bool_env_var, read_env_var};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::{collections::BTreeMap, process::Command};
 
 /// Default flags passed to boogie. Additional flags will be added to this via the -B option.
 const DEFAULT_BOOGIE_FLAGS: &[&str] = &[
@@ -129,6 +129,10 @@ pub struct BoogieOptions {
     pub vc_timeout: usize,
     /// Whether allow local timeout overwrites the global one
     pub global_timeout_overwrite: bool,
+    /// Timeouts for the solver of particular functions, in seconds, keyed by the function name
+    /// qualified with the module name (e.g. `Module::function`). Overrides both the `vc_timeout`
+    /// and the timeout pragma of the function.
+    pub function_timeouts: BTreeMap<String, usize>,
     /// Whether Boogie output and log should be saved.
     pub keep_artifacts: bool,
     /// Eager threshold for quantifier instantiation.
@@ -180,6 +184,7 @@ impl Default for BoogieOptions {
             proc_cores: 4,
             vc_timeout: 80,
             global_timeout_overwrite: true,
+            function_timeouts: BTreeMap::new(),
             keep_artifacts: false,
             eager_threshold: 100,
             lazy_threshold: 100,
@@ -2,41 +2,112 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::reroot_path;
+use crate::verbosity::Verbosity;
 use clap::*;
-use move_package::{Architecture, BuildConfig};
-use std::path::PathBuf;
+use move_command_line_common::output_format::OutputFormat;
+use move_compiler::compiled_unit::CompiledUnit;
+use move_package::{
+    compilation::{compiled_package::CompiledPackage, package_layout::CompiledPackageLayout},
+    Architecture, BuildConfig,
+};
+use serde_json::json;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 /// Build the package at `path`. If no path is provided defaults to current directory.
-#[derive(Parser)]
+#[derive(Default, Parser)]
 #[clap(name = "build")]
-pub struct Build;
+pub struct Build {
+    /// Format of the build results printed on stdout (`human` or `json`)
+    #[clap(long, default_value = "human")]
+    pub output_format: OutputFormat,
+    /// Verbosity of the command, the progress messages are dropped if quiet.
+    #[clap(skip)]
+    pub verbosity: Verbosity,
+}
 
 impl Build {
     pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
         let rerooted_path = reroot_path(path)?;
-        if config.fetch_deps_only {
-            let mut config = config;
-            if config.test_mode {
-                config.dev_mode = true;
-            }
-            config.download_deps_for_package(&rerooted_path, &mut std::io::stdout())?;
-            return Ok(());
+        if self.verbosity.is_quiet() && !self.output_format.is_json() {
+            return build(&rerooted_path, config, &mut std::io::sink()).map(|_| ());
+        }
+        if !self.output_format.is_json() {
+            return build(&rerooted_path, config, &mut std::io::stdout()).map(|_| ());
         }
-        let architecture = config.architecture.unwrap_or(Architecture::Move);
-
-        match architecture {
-            Architecture::Move | Architecture::AsyncMove => {
-                config.compile_package(&rerooted_path, &mut std::io::stdout())?;
-            },
 
-            Architecture::Ethereum => {
-                #[cfg(feature = "evm-backend")]
-                config.compile_package_evm(&rerooted_path, &mut std::io::stderr())?;
+        // The progress messages are dropped, so stdout contains the JSON results only.
+        let result = build(&rerooted_path, config, &mut Vec::new());
+        let output = match &result {
+            Ok(Some(package)) => build_results(package, &rerooted_path),
+            Ok(None) => json!({ "success": true }),
+            Err(e) => json!({ "success": false, "error": format!("{e:#}") }),
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        result.map(|_| ())
+    }
+}
 
-                #[cfg(not(feature = "evm-backend"))]
-                anyhow::bail!("The Ethereum architecture is not supported because move-cli was not compiled with feature flag `evm-backend`.");
-            },
+/// Builds the package, returning the compiled package unless only the dependencies were fetched
+/// or the package was built for another architecture.
+fn build<W: Write>(
+    path: &Path,
+    config: BuildConfig,
+    writer: &mut W,
+) -> anyhow::Result<Option<CompiledPackage>> {
+    if config.fetch_deps_only {
+        let mut config = config;
+        if config.test_mode {
+            config.dev_mode = true;
         }
-        Ok(())
+        config.download_deps_for_package(path, writer)?;
+        return Ok(None);
     }
+    let architecture = config.architecture.unwrap_or(Architecture::Move);
+
+    match architecture {
+        Architecture::Move | Architecture::AsyncMove => {
+            Ok(Some(config.compile_package(path, writer)?))
+        },
+
+        Architecture::Ethereum => {
+            #[cfg(feature = "evm-backend")]
+            return config
+                .compile_package_evm(path, &mut std::io::stderr())
+                .map(|_| None);
+
+            #[cfg(not(feature = "evm-backend"))]
+            anyhow::bail!("The Ethereum architecture is not supported because move-cli was not compiled with feature flag `evm-backend`.");
+        },
+    }
+}
+
+/// Describes the compiled package: its name, build directory and the compiled root modules and
+/// scripts.
+fn build_results(package: &CompiledPackage, path: &Path) -> serde_json::Value {
+    let info = &package.compiled_package_info;
+    let build_dir = info
+        .build_flags
+        .install_dir
+        .as_deref()
+        .unwrap_or(path)
+        .join(CompiledPackageLayout::Root.path())
+        .join(info.package_name.as_str());
+    let units = |modules: bool| {
+        package
+            .root_compiled_units
+            .iter()
+            .filter(|unit| matches!(unit.unit, CompiledUnit::Module(_)) == modules)
+            .map(|unit| unit.unit.name().to_string())
+            .collect::<Vec<_>>()
+    };
+    json!({
+        "success": true,
+        "package": info.package_name.as_str(),
+        "build_dir": build_dir,
+        "modules": units(true),
+        "scripts": units(false),
+    })
 }
@@ -0,0 +1,33 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MoveCLI;
+use clap::*;
+use clap_complete::Shell;
+
+/// Name of the Move CLI binary the completions are generated for.
+const BIN_NAME: &str = "move";
+
+/// Generate the shell completions of the Move CLI
+///
+/// The completions are printed to the standard output, e.g. for bash:
+/// `move completions bash > /etc/bash_completion.d/move`
+#[derive(Parser)]
+#[clap(name = "completions")]
+pub struct Completions {
+    /// Shell to generate the completions for
+    #[clap(value_enum, ignore_case = true)]
+    pub shell: Shell,
+}
+
+impl Completions {
+    pub fn execute(self) -> anyhow::Result<()> {
+        clap_complete::generate(
+            self.shell,
+            &mut MoveCLI::command(),
+            BIN_NAME,
+            &mut std::io::stdout(),
+        );
+        Ok(())
+    }
+}
@@ -2,15 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::reroot_path;
+use anyhow::Context;
 use clap::*;
+use colored::{ColoredString, Colorize};
 use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
 use move_coverage::{
     coverage_map::CoverageMap, format_csv_summary, format_human_summary,
     source_coverage::SourceCoverageBuilder, summary::summarize_inst_cov,
 };
 use move_disassembler::disassembler::Disassembler;
+use move_mutator::report::{MutantStatus, Position, Report};
 use move_package::BuildConfig;
-use std::path::PathBuf;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
 #[derive(Parser)]
 pub enum CoverageSummaryOptions {
@@ -29,6 +36,10 @@ pub enum CoverageSummaryOptions {
     Source {
         #[clap(long = "module")]
         module_name: String,
+        /// Mutation report (e.g. of `move mutate-test` or `move spec-test`) whose killed and
+        /// survived mutants are marked in the lines they were placed in
+        #[clap(long = "mutation-report", value_parser)]
+        mutation_report: Option<PathBuf>,
     },
     /// Display coverage information about the module against disassembled bytecode
     #[clap(name = "bytecode")]
@@ -41,7 +52,7 @@ pub enum CoverageSummaryOptions {
 /// Inspect test coverage for this package. A previous test run with the `--coverage` flag must
 /// have previously been run.
 #[derive(Parser)]
-#[clap(name = "coverage")]
+#[clap(name = "coverage", infer_subcommands = true)]
 pub struct Coverage {
     #[clap(subcommand)]
     pub options: CoverageSummaryOptions,
@@ -60,7 +71,10 @@ impl Coverage {
             })
             .collect();
         match self.options {
-            CoverageSummaryOptions::Source { module_name } => {
+            CoverageSummaryOptions::Source {
+                module_name,
+                mutation_report,
+            } => {
                 let unit = package.get_module_by_name_from_root(&module_name)?;
                 let source_path = &unit.source_path;
                 let (module, source_map) = match &unit.unit {
@@ -69,10 +83,14 @@ impl Coverage {
                     }) => (module, source_map),
                     _ => panic!("Should all be modules"),
                 };
-                let source_coverage = SourceCoverageBuilder::new(module, &coverage_map, source_map);
+                let source_coverage = SourceCoverageBuilder::new(module, &coverage_map, source_map)
+                    .compute_source_coverage(source_path);
+                let markers = match mutation_report {
+                    Some(report_path) => mutant_markers(&report_path, source_path)?,
+                    None => BTreeMap::new(),
+                };
                 source_coverage
-                    .compute_source_coverage(source_path)
-                    .output_source_coverage(&mut std::io::stdout())
+                    .output_source_coverage_with_annotations(&mut std::io::stdout(), &markers)
                     .unwrap();
             },
             CoverageSummaryOptions::Summary {
@@ -108,3 +126,70 @@ impl Coverage {
         Ok(())
     }
 }
+
+/// Builds the markers describing the mutants from the report placed in the lines of the source
+/// file, keyed by the 0-based line numbers.
+///
+/// # Arguments
+///
+/// * `report_path` - Path to the mutation report.
+/// * `source_path` - Path to the source file the markers are built for.
+///
+/// # Errors
+///
+/// Returns an error if the report or the source file cannot be read.
+///
+/// # Returns
+///
+/// * `anyhow::Result<BTreeMap<usize, ColoredString>>` - The markers of the lines with mutants.
+fn mutant_markers(
+    report_path: &Path,
+    source_path: &Path,
+) -> anyhow::Result<BTreeMap<usize, ColoredString>> {
+    let report = Report::load_from_json_file(report_path).with_context(|| {
+        format!(
+            "Failed to load the mutation report from {}",
+            report_path.display()
+        )
+    })?;
+    let source = fs::read_to_string(source_path)?;
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let source_path = canonical(source_path);
+
+    let mut statuses: BTreeMap<usize, BTreeMap<MutantStatus, usize>> = BTreeMap::new();
+    for entry in report.get_mutants() {
+        if canonical(entry.original_file_path()) != source_path {
+            continue;
+        }
+        let Some(position) = entry.get_mutations().first().and_then(|mutation| {
+            Position::from_offset(&source, mutation.get_changed_place().start())
+        }) else {
+            continue;
+        };
+        *statuses
+            .entry(position.line - 1)
+            .or_default()
+            .entry(entry.status())
+            .or_default() += 1;
+    }
+
+    Ok(statuses
+        .into_iter()
+        .map(|(line, counts)| {
+            let marker = counts
+                .iter()
+                .map(|(status, count)| format!("{count} {status}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let marker = format!("// mutants: {marker}");
+            let marker = if counts.contains_key(&MutantStatus::Survived) {
+                marker.bold().red()
+            } else if counts.contains_key(&MutantStatus::Killed) {
+                marker.green()
+            } else {
+                marker.normal()
+            };
+            (line, marker)
+        })
+        .collect())
+}
@@ -3,11 +3,19 @@
 
 use super::reroot_path;
 use clap::*;
-use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_binary_format::{
+    binary_views::BinaryIndexedView,
+    file_format::{CompiledModule, CompiledScript},
+};
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule, NamedCompiledScript};
 use move_disassembler::disassembler::Disassembler;
+use move_ir_types::location::Spanned;
 use move_model::ast::ModuleName;
 use move_package::{compilation::compiled_package::CompiledUnitWithSource, BuildConfig};
-use std::path::PathBuf;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 /// Disassemble the Move bytecode pointed to
 #[derive(Parser)]
@@ -22,6 +30,10 @@ pub struct Disassemble {
     /// The name of the module or script in the package to disassemble
     #[clap(long = "name")]
     pub module_or_script_name: String,
+    /// Path to another compiled module or script (e.g. built from a mutant of the package) to
+    /// compare with, printing the instruction-level diff of the disassembled bytecode
+    #[clap(long = "diff", value_parser, conflicts_with = "interactive")]
+    pub diff: Option<PathBuf>,
 }
 
 impl Disassemble {
@@ -31,6 +43,7 @@ impl Disassemble {
             interactive,
             package_name,
             module_or_script_name,
+            diff,
         } = self;
         // Make sure the package is built
         let package = config.compile_package(&rerooted_path, &mut Vec::new())?;
@@ -73,6 +86,8 @@ impl Disassemble {
                         ),
                         _ => anyhow::bail!("Interactive disassembler not supported for scripts"),
                     }
+                } else if let Some(other) = diff {
+                    print!("{}", diff_bytecode(&unit.unit, &other)?);
                 } else {
                     println!("{}", Disassembler::from_unit(&unit.unit).disassemble()?);
                 }
@@ -81,3 +96,41 @@ impl Disassemble {
         Ok(())
     }
 }
+
+/// Renders the diff of the disassembled bytecode of the compiled unit and of the compiled module (or
+/// script) from the file. Both are disassembled without the source maps, so only the changes of the
+/// bytecode are shown (and not e.g. the renamed locals).
+fn diff_bytecode(unit: &CompiledUnit, other: &Path) -> anyhow::Result<String> {
+    let original = match unit {
+        CompiledUnit::Module(NamedCompiledModule { module, .. }) => {
+            disassemble(BinaryIndexedView::Module(module))?
+        },
+        CompiledUnit::Script(NamedCompiledScript { script, .. }) => {
+            disassemble(BinaryIndexedView::Script(script))?
+        },
+    };
+
+    let bytes =
+        fs::read(other).map_err(|e| anyhow::anyhow!("Unable to read {}: {e}", other.display()))?;
+    let other = if let Ok(module) = CompiledModule::deserialize(&bytes) {
+        disassemble(BinaryIndexedView::Module(&module))?
+    } else if let Ok(script) = CompiledScript::deserialize(&bytes) {
+        disassemble(BinaryIndexedView::Script(&script))?
+    } else {
+        anyhow::bail!(
+            "{} is neither a compiled module nor a compiled script",
+            other.display()
+        );
+    };
+
+    let patch = diffy::create_patch(&original, &other);
+    if patch.hunks().is_empty() {
+        return Ok("No differences in the bytecode\n".to_owned());
+    }
+    Ok(patch.to_string())
+}
+
+fn disassemble(view: BinaryIndexedView) -> anyhow::Result<String> {
+    let no_loc = Spanned::unsafe_no_loc(()).loc;
+    Ok(Disassembler::from_view(view, no_loc)?.disassemble()? + "\n")
+}
@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod build;
+pub mod completions;
 pub mod coverage;
 pub mod disassemble;
 pub mod docgen;
 pub mod errmap;
 pub mod mutate;
+pub mod mutate_test;
 pub mod new;
 pub mod prove;
 pub mod spec_test;
@@ -1,14 +1,112 @@
 use clap::*;
-use move_package::BuildConfig;
-use std::path::PathBuf;
+use move_command_line_common::output_format::OutputFormat;
+use move_mutator::{
+    cli::{CLIOptions, DEFAULT_OUTPUT_DIR},
+    configuration::Configuration,
+};
+use move_package::{
+    compilation::package_layout::CompiledPackageLayout,
+    resolution::digest::{compute_file_digests, get_changed_files},
+    source_package::layout::SourcePackageLayout,
+    BuildConfig,
+};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+/// Interval of checking the package files for changes in the watch mode.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Mutate the Move files or package
 #[derive(Parser)]
-#[clap(name = "mutate")]
+#[clap(name = "mutate", infer_subcommands = true)]
 pub struct Mutate {
     /// Any options passed to the move-mutator
     #[clap(flatten)]
     pub options: Option<move_mutator::cli::CLIOptions>,
+
+    /// Keep watching the package and regenerate the mutants of the changed files on save
+    #[clap(long)]
+    pub watch: bool,
+
+    /// List the available mutation operators with their descriptions and example transformations
+    #[clap(long, conflicts_with = "watch")]
+    pub list_operators: bool,
+
+    /// Format of the results printed on stdout (`human` or `json`)
+    #[clap(long, default_value = "human", conflicts_with = "watch")]
+    pub output_format: OutputFormat,
+
+    /// Optional action performed on already generated mutants
+    #[clap(subcommand)]
+    pub cmd: Option<MutateCommand>,
+}
+
+/// Actions performed on the mutants listed in the mutator report
+#[derive(Subcommand)]
+pub enum MutateCommand {
+    /// Apply the mutant with the given ID onto the working tree
+    Apply(MutantArgs),
+    /// Revert the mutant with the given ID previously applied onto the working tree
+    Revert(MutantArgs),
+    /// Merge partial mutator reports (e.g. from sharded or resumed runs) into a single report
+    Merge(MergeArgs),
+    /// Export surviving mutants from the report as numbered patch files with an index
+    ExportPatches(ExportPatchesArgs),
+    /// Print the progress of the campaign (generated, verified and remaining mutants)
+    Status(StatusArgs),
+    /// Explain the mutation operator: its replacements, the specification clauses killing its
+    /// mutants and a worked example
+    Explain(ExplainArgs),
+}
+
+#[derive(Args)]
+pub struct MutantArgs {
+    /// ID of the mutant as listed in the mutator report
+    pub mutant_id: String,
+    /// Directory containing the mutator report
+    #[clap(long, short, value_parser, default_value = DEFAULT_OUTPUT_DIR)]
+    pub out_mutant_dir: PathBuf,
+}
+
+#[derive(Args)]
+pub struct MergeArgs {
+    /// Paths to the JSON (or streamed JSONL) reports to merge
+    #[clap(required = true, value_parser)]
+    pub reports: Vec<PathBuf>,
+    /// Path to the merged JSON report
+    #[clap(long, short, value_parser)]
+    pub output: PathBuf,
+}
+
+#[derive(Args)]
+pub struct ExportPatchesArgs {
+    /// Path to the JSON report with the status of each mutant (e.g. from `move spec-test`)
+    pub report: PathBuf,
+    /// Directory where the patch files are created
+    #[clap(long, short, value_parser, default_value = "surviving_mutants")]
+    pub output: PathBuf,
+}
+
+#[derive(Args)]
+pub struct ExplainArgs {
+    /// Name of the operator as listed by `--list-operators`
+    pub operator: String,
+}
+
+#[derive(Args)]
+pub struct StatusArgs {
+    /// Directory containing the mutator report
+    #[clap(long, short, value_parser, default_value = DEFAULT_OUTPUT_DIR)]
+    pub out_mutant_dir: PathBuf,
+    /// JSON (or streamed JSONL) report with the status of the verified mutants (e.g. from
+    /// `move spec-test --stream-output`)
+    #[clap(long, value_parser)]
+    pub results: Option<PathBuf>,
 }
 
 impl Mutate {
@@ -18,10 +116,225 @@ impl Mutate {
     pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
         let path = path.unwrap_or_else(|| PathBuf::from("."));
 
-        let Self { options } = self;
+        let Self {
+            options,
+            watch,
+            list_operators,
+            output_format,
+            cmd,
+        } = self;
 
-        let options = options.unwrap_or_default();
+        match cmd {
+            Some(MutateCommand::Apply(args)) => {
+                let file =
+                    move_mutator::apply::apply_mutant(&args.out_mutant_dir, &args.mutant_id)?;
+                if output_format.is_json() {
+                    print_json(&json!({ "mutant_id": args.mutant_id, "applied_to": file }))
+                } else {
+                    println!("Applied mutant {} to {}", args.mutant_id, file.display());
+                    Ok(())
+                }
+            },
+            Some(MutateCommand::Revert(args)) => {
+                let file =
+                    move_mutator::apply::revert_mutant(&args.out_mutant_dir, &args.mutant_id)?;
+                if output_format.is_json() {
+                    print_json(&json!({ "mutant_id": args.mutant_id, "reverted_in": file }))
+                } else {
+                    println!("Reverted mutant {} in {}", args.mutant_id, file.display());
+                    Ok(())
+                }
+            },
+            Some(MutateCommand::Merge(args)) => {
+                let mut merged = move_mutator::report::Report::new();
+                for path in &args.reports {
+                    let report = if path.extension().is_some_and(|ext| ext == "jsonl") {
+                        move_mutator::report::Report::load_from_jsonl_file(path)?
+                    } else {
+                        move_mutator::report::Report::load_from_json_file(path)?
+                    };
+                    merged.merge(report);
+                }
+                merged.save_to_json_file(&args.output)?;
+                if output_format.is_json() {
+                    return print_json(&json!({
+                        "reports": args.reports,
+                        "mutants": merged.get_mutants().len(),
+                        "output": args.output,
+                    }));
+                }
+                println!(
+                    "Merged {} reports ({} mutants) into {}",
+                    args.reports.len(),
+                    merged.get_mutants().len(),
+                    args.output.display()
+                );
+                Ok(())
+            },
+            Some(MutateCommand::ExportPatches(args)) => {
+                let report = move_mutator::report::Report::load_from_json_file(&args.report)?;
+                let count = move_mutator::bundle::export_patch_bundle(&report, &args.output)?;
+                if output_format.is_json() {
+                    return print_json(&json!({ "exported": count, "output": args.output }));
+                }
+                println!(
+                    "Exported {count} surviving mutants to {}",
+                    args.output.display()
+                );
+                Ok(())
+            },
+            Some(MutateCommand::Status(args)) => {
+                let status = move_mutator::status::CampaignStatus::load(
+                    &args.out_mutant_dir,
+                    args.results.as_deref(),
+                )?;
+                if output_format.is_json() {
+                    return print_json(&serde_json::to_value(status)?);
+                }
+                print!("{}", status.render());
+                Ok(())
+            },
+            Some(MutateCommand::Explain(args)) => {
+                let explanation = move_mutator::explain::OperatorExplanation::find(&args.operator)?;
+                if output_format.is_json() {
+                    return print_json(&serde_json::to_value(explanation)?);
+                }
+                print!("{}", explanation.render());
+                Ok(())
+            },
+            None if list_operators => print_operators(output_format),
+            None if watch => watch_package(options.unwrap_or_default(), &config, &path),
+            None => {
+                let options = options.unwrap_or_default();
+                let output_dir = output_dir(&options)?;
 
-        move_mutator::run_move_mutator(options, &config, &path)
+                move_mutator::run_move_mutator(options, &config, &path)?;
+                if output_format.is_json() {
+                    print_json(&mutation_results(&output_dir)?)?;
+                }
+                Ok(())
+            },
+        }
     }
 }
+
+/// Generates the mutants of the package and regenerates them whenever the package files change,
+/// until interrupted. Changes are detected by comparing the digests of the files. Only the mutants
+/// of the changed files are regenerated, unless the manifest has changed or the previous run has
+/// failed (e.g. the package didn't compile), in which case all the mutants are generated again.
+fn watch_package(options: CLIOptions, config: &BuildConfig, path: &Path) -> anyhow::Result<()> {
+    let package_path = SourcePackageLayout::try_find_root(&path.canonicalize()?)?;
+    let output_dir = output_dir(&options)?;
+    // The mutants are Move files too, so they must not be mistaken for changes of the package.
+    let ignored = [
+        std::env::current_dir()?.join(output_dir),
+        config
+            .install_dir
+            .as_ref()
+            .unwrap_or(&package_path)
+            .join(CompiledPackageLayout::Root.path()),
+    ];
+    let digests = || -> anyhow::Result<_> {
+        let mut digests = compute_file_digests(std::slice::from_ref(&package_path))?;
+        digests.retain(|file, _| !ignored.iter().any(|dir| file.starts_with(dir)));
+        Ok(digests)
+    };
+
+    let mut digest = digests()?;
+    let mut changed: Option<Vec<PathBuf>> = None;
+    loop {
+        let result = match &changed {
+            Some(files) => move_mutator::run_move_mutator_for_files(
+                options.clone(),
+                config,
+                &package_path,
+                files,
+            ),
+            None => move_mutator::run_move_mutator(options.clone(), config, &package_path),
+        };
+        let succeeded = match result {
+            Ok(()) => {
+                println!("Mutants generated");
+                true
+            },
+            Err(e) => {
+                eprintln!("Failed to generate mutants: {e:#}");
+                false
+            },
+        };
+
+        println!(
+            "Watching {} for changes (press Ctrl-C to stop)",
+            package_path.display()
+        );
+        let files = loop {
+            thread::sleep(WATCH_INTERVAL);
+            let current = digests()?;
+            let files = get_changed_files(&digest, &current);
+            digest = current;
+            if !files.is_empty() {
+                break files;
+            }
+        };
+        for file in &files {
+            println!("Changed {}", file.display());
+        }
+
+        let manifest_changed = files
+            .iter()
+            .any(|file| file.ends_with(SourcePackageLayout::Manifest.path()));
+        changed = (succeeded && !manifest_changed).then_some(files);
+    }
+}
+
+/// Returns the directory where the mutants are generated, as set in the configuration file or the
+/// options.
+fn output_dir(options: &CLIOptions) -> anyhow::Result<PathBuf> {
+    Ok(match &options.configuration_file {
+        Some(file) => {
+            let mut configuration = Configuration::from_file(file)?;
+            configuration.apply_env_overrides();
+            configuration.project.out_mutant_dir
+        },
+        None => options.out_mutant_dir.clone(),
+    }
+    .unwrap_or_else(|| PathBuf::from(DEFAULT_OUTPUT_DIR)))
+}
+
+/// Summarizes the generated mutants from the report in the output directory: the number of the
+/// generated and skipped mutants, along with the number of the mutants of each module.
+fn mutation_results(output_dir: &Path) -> anyhow::Result<serde_json::Value> {
+    let report_path = output_dir.join("report.json");
+    let report = move_mutator::report::Report::load_from_json_file(&report_path)?;
+    let mut modules = BTreeMap::<&str, usize>::new();
+    for mutant in report.get_mutants() {
+        *modules.entry(mutant.get_module_name()).or_default() += 1;
+    }
+    Ok(json!({
+        "output_dir": output_dir,
+        "report": report_path,
+        "mutants": report.get_mutants().len(),
+        "skipped": report.get_skipped().len(),
+        "modules": modules,
+    }))
+}
+
+/// Prints the catalog of the mutation operators.
+fn print_operators(output_format: OutputFormat) -> anyhow::Result<()> {
+    let operators = move_mutator::operator_catalog();
+    if output_format.is_json() {
+        return print_json(&json!({ "operators": operators }));
+    }
+    for operator in &operators {
+        let (original, mutated) = operator.example();
+        println!("{}", operator.name());
+        println!("    {}", operator.description());
+        println!("    Example: {original} -> {mutated}");
+    }
+    Ok(())
+}
+
+fn print_json(value: &serde_json::Value) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
@@ -0,0 +1,276 @@
+use crate::{
+    base::test::{run_move_unit_tests, UnitTestResult},
+    NativeFunctionRecord,
+};
+use anyhow::{anyhow, Context};
+use clap::*;
+use move_command_line_common::output_format::OutputFormat;
+use move_core_types::effects::ChangeSet;
+use move_mutator::{
+    cli::DEFAULT_OUTPUT_DIR,
+    report::{MutantStatus, MutationReport, Report},
+};
+use move_package::{source_package::layout::SourcePackageLayout, BuildConfig};
+use move_spec_test::MutantCheck;
+use move_unit_test::UnitTestingConfig;
+use move_vm_test_utils::gas_schedule::CostTable;
+use serde_json::json;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Test the Move package using the Move Mutator and the Move unit tests
+#[derive(Parser)]
+#[clap(name = "mutate-test")]
+pub struct MutateTest {
+    /// Any options passed to the move-mutator
+    #[clap(flatten)]
+    pub options: Option<move_mutator::cli::CLIOptions>,
+    /// Use previously generated mutants from the given directory (containing the mutator report)
+    #[clap(long, value_parser)]
+    pub use_generated_mutants: Option<PathBuf>,
+    /// Save the mutator report updated with the status (killed or survived) of each mutant
+    #[clap(long, value_parser)]
+    pub report_output: Option<PathBuf>,
+    /// Run only the unit tests whose fully qualified name contains the filter
+    #[clap(long)]
+    pub test_filter: Option<String>,
+    /// Bound the amount of gas used by any one test
+    #[clap(long)]
+    pub gas_limit: Option<u64>,
+    /// Number of threads to use for running tests
+    #[clap(long, default_value = "8")]
+    pub threads: usize,
+}
+
+impl MutateTest {
+    /// Executes the mutate-test command which produces mutants from the Move package and runs the
+    /// unit tests of the package against each mutant. Mutants are killed if any of the tests fails
+    /// (or the mutant doesn't compile).
+    /// If no path is provided, the current directory is used.
+    pub fn execute(
+        self,
+        path: Option<PathBuf>,
+        config: BuildConfig,
+        natives: Vec<NativeFunctionRecord>,
+        genesis: ChangeSet,
+        cost_table: Option<CostTable>,
+    ) -> anyhow::Result<()> {
+        let path = path.unwrap_or_else(|| PathBuf::from("."));
+        let package_path = SourcePackageLayout::try_find_root(&path.canonicalize()?)?;
+
+        let unit_test_config = UnitTestingConfig {
+            filter: self.test_filter.clone(),
+            num_threads: self.threads,
+            ..UnitTestingConfig::default_with_bound(self.gas_limit)
+        };
+        let run_tests = unit_test_check(&config, unit_test_config, natives, genesis, cost_table);
+        check_original_package(&run_tests, &package_path)?;
+
+        let mutants_dir = match &self.use_generated_mutants {
+            Some(dir) => dir.clone(),
+            None => {
+                let mut options = self.options.clone().unwrap_or_default();
+                let dir = options
+                    .out_mutant_dir
+                    .get_or_insert_with(|| PathBuf::from(DEFAULT_OUTPUT_DIR))
+                    .clone();
+                move_mutator::run_move_mutator(options, &config, &package_path)?;
+                dir
+            },
+        };
+
+        let mut report = Report::load_from_json_file(&mutants_dir.join("report.json"))?;
+
+        let workdir = tempfile::tempdir()?;
+        let mutant_package = workdir.path().join("mutant");
+        let total = report.get_mutants().len();
+
+        for (index, entry) in report.get_mutants_mut().iter_mut().enumerate() {
+            println!("Testing mutant {} out of {total}", index + 1);
+            test_mutant(&run_tests, &package_path, &mutant_package, entry)?;
+        }
+
+        if let Some(output) = &self.report_output {
+            report.save_to_json_file(output)?;
+        }
+
+        let survivors = report.query().status(MutantStatus::Survived);
+        for entry in survivors.iter() {
+            println!(
+                "Mutant {} survived in {} ({}::{})",
+                entry.mutant_id(),
+                entry.original_file_path().display(),
+                entry.get_module_name(),
+                entry.get_function_name()
+            );
+        }
+
+        print_score(total, total - survivors.len());
+        Ok(())
+    }
+}
+
+/// Runs the unit tests of the package once per mutant from the directory produced by `move mutate`
+/// (containing the mutator report) and reports the result of each mutant: killed if any of the
+/// tests fails (or the mutant doesn't compile), survived otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the unit tests of the original package fail, the mutator report cannot be
+/// loaded or a mutated package cannot be set up.
+pub fn test_mutants_dir(
+    run_tests: &MutantCheck<'_>,
+    package_path: &Path,
+    mutants_dir: &Path,
+    output_format: OutputFormat,
+) -> anyhow::Result<()> {
+    // The mutator report refers to the original files with the absolute paths.
+    let package_path = &package_path.canonicalize()?;
+    check_original_package(run_tests, package_path)?;
+    let mut report = Report::load_from_json_file(&mutants_dir.join("report.json"))?;
+
+    let workdir = tempfile::tempdir()?;
+    let mutant_package = workdir.path().join("mutant");
+    for entry in report.get_mutants_mut() {
+        test_mutant(run_tests, package_path, &mutant_package, entry)?;
+        if !output_format.is_json() {
+            let result = match entry.killed_by() {
+                Some(killed_by) => format!("killed ({killed_by})"),
+                None => "survived".to_owned(),
+            };
+            println!(
+                "Mutant {} in {} ({}::{}): {result}",
+                entry.mutant_id(),
+                entry.original_file_path().display(),
+                entry.get_module_name(),
+                entry.get_function_name()
+            );
+        }
+    }
+
+    let total = report.get_mutants().len();
+    let killed = report.query().status(MutantStatus::Killed).len();
+    if output_format.is_json() {
+        let mutants = report
+            .get_mutants()
+            .iter()
+            .map(|entry| {
+                json!({
+                    "mutant_id": entry.mutant_id(),
+                    "file": entry.original_file_path(),
+                    "module": entry.get_module_name(),
+                    "function": entry.get_function_name(),
+                    "status": entry.status().to_string(),
+                    "killed_by": entry.killed_by(),
+                })
+            })
+            .collect::<Vec<_>>();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "total": total,
+                "killed": killed,
+                "mutants": mutants,
+            }))?
+        );
+    } else {
+        print_score(total, killed);
+    }
+    Ok(())
+}
+
+/// Runs the unit tests against the mutant and records its status. The package is copied into
+/// `mutant_package` along with the mutated file replacing the original one.
+fn test_mutant(
+    run_tests: &MutantCheck<'_>,
+    package_path: &Path,
+    mutant_package: &Path,
+    entry: &mut MutationReport,
+) -> anyhow::Result<()> {
+    let original_file = entry
+        .original_file_path()
+        .strip_prefix(package_path)
+        .unwrap_or(entry.original_file_path())
+        .to_path_buf();
+
+    let _ = fs::remove_dir_all(mutant_package);
+    move_mutator::compiler::copy_dir_all(package_path, mutant_package)?;
+    fs::copy(entry.mutant_path(), mutant_package.join(&original_file))
+        .with_context(|| format!("Can't copy mutant file {}", entry.mutant_path().display()))?;
+    move_mutator::compiler::rewrite_manifest_for_mutant(package_path, mutant_package)?;
+
+    match run_tests(mutant_package)? {
+        Some((killed_by, diagnostics)) => {
+            entry.set_status(MutantStatus::Killed);
+            entry.set_killed_by(killed_by);
+            entry.set_diagnostics(diagnostics);
+        },
+        None => entry.set_status(MutantStatus::Survived),
+    }
+    Ok(())
+}
+
+/// Prints the number of the tested and killed mutants along with the mutation score.
+fn print_score(total: usize, killed: usize) {
+    println!("\nTotal mutants tested: {total}");
+    println!("Total mutants killed: {killed}");
+    if total > 0 {
+        println!(
+            "Mutation score: {:.2}%",
+            killed as f64 / total as f64 * 100.0
+        );
+    }
+}
+
+/// Creates a check running the Move unit tests of the mutated package. The mutant is killed if any of
+/// the tests fails or the mutant doesn't compile, in which case the reason and the output of the
+/// tests are returned.
+pub fn unit_test_check<'a>(
+    config: &'a BuildConfig,
+    unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    genesis: ChangeSet,
+    cost_table: Option<CostTable>,
+) -> impl Fn(&Path) -> anyhow::Result<Option<(String, String)>> + 'a {
+    move |package: &Path| {
+        let mut output = Vec::new();
+        let result = run_move_unit_tests(
+            package,
+            config.clone(),
+            unit_test_config.clone(),
+            natives.clone(),
+            genesis.clone(),
+            cost_table.clone(),
+            false,
+            &mut output,
+        );
+        let output = String::from_utf8_lossy(&output).trim_end().to_owned();
+
+        Ok(match result {
+            Ok(UnitTestResult::Success) => None,
+            Ok(UnitTestResult::Failure) => Some(("unit tests failed".to_owned(), output)),
+            Err(e) => Some(("mutant does not compile".to_owned(), format!("{e:#}"))),
+        })
+    }
+}
+
+/// Checks that the original package passes the unit tests. Otherwise, all the mutants would be
+/// reported as killed.
+///
+/// # Errors
+///
+/// Returns an error if the unit tests of the original package fail.
+pub fn check_original_package(
+    run_tests: &MutantCheck<'_>,
+    package_path: &Path,
+) -> anyhow::Result<()> {
+    match run_tests(package_path)? {
+        Some((reason, output)) => Err(anyhow!(
+            "Unit tests of the original package fail ({reason}). Fix them before running the \
+             mutation test\n{output}"
+        )),
+        None => Ok(()),
+    }
+}
@@ -4,10 +4,11 @@
 use clap::*;
 use move_package::source_package::layout::SourcePackageLayout;
 use std::{
-    fmt::Display,
-    fs::create_dir_all,
+    fmt::{self, Display},
+    fs::{self, create_dir_all},
     io::Write,
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
 pub const MOVE_STDLIB_PACKAGE_NAME: &str = "MoveStdlib";
@@ -18,6 +19,11 @@ pub const MOVE_STDLIB_PACKAGE_PATH: &str = "{ \
 pub const MOVE_STDLIB_ADDR_NAME: &str = "std";
 pub const MOVE_STDLIB_ADDR_VALUE: &str = "0x1";
 
+/// Address of the sample module of the `spec-tested` template.
+const SAMPLE_ADDR_VALUE: &str = "0x42";
+/// Path to the CI workflow of the `spec-tested` template.
+const SPEC_TEST_WORKFLOW_PATH: &str = ".github/workflows/spec-test.yml";
+
 /// Create a new Move package with name `name` at `path`. If `path` is not provided the package
 /// will be created in the directory `name`.
 #[derive(Parser)]
@@ -25,6 +31,44 @@ pub const MOVE_STDLIB_ADDR_VALUE: &str = "0x1";
 pub struct New {
     /// The name of the package to be created.
     pub name: String,
+
+    /// The template of the package (`empty` or `spec-tested`, which adds a sample specified
+    /// module, the spec testing defaults and a CI workflow running the specification test).
+    #[clap(long, default_value = "empty")]
+    pub template: Template,
+}
+
+/// Template of the package created by `move new`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Template {
+    /// Package with the manifest only.
+    #[default]
+    Empty,
+    /// Package set up for the specification testing from day one.
+    SpecTested,
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "empty" => Ok(Template::Empty),
+            "spec-tested" => Ok(Template::SpecTested),
+            _ => Err(format!(
+                "Unknown template '{s}', expected empty or spec-tested"
+            )),
+        }
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Template::Empty => f.write_str("empty"),
+            Template::SpecTested => f.write_str("spec-tested"),
+        }
+    }
 }
 
 impl New {
@@ -47,7 +91,7 @@ impl New {
         custom: &str, // anything else that needs to end up being in Move.toml (or empty string)
     ) -> anyhow::Result<()> {
         // TODO warn on build config flags
-        let Self { name } = self;
+        let Self { name, template } = self;
         let p: PathBuf;
         let path: &Path = match path {
             Some(path) => {
@@ -78,9 +122,122 @@ version = \"{version}\"
         for (addr_name, addr_val) in addrs {
             writeln!(w, "{addr_name} =  \"{addr_val}\"")?;
         }
+        let sample_addr = address_name(&name);
+        if template == Template::SpecTested {
+            writeln!(w, "{sample_addr} =  \"{SAMPLE_ADDR_VALUE}\"")?;
+        }
         if !custom.is_empty() {
             writeln!(w, "{}", custom)?;
         }
+        if template == Template::SpecTested {
+            write_spec_tested(&mut w, path, &sample_addr)?;
+        }
         Ok(())
     }
 }
+
+/// Returns the named address of the sample module, derived from the package name.
+fn address_name(package_name: &str) -> String {
+    let name = package_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("pkg_{name}")
+    }
+}
+
+/// Adds the spec testing defaults to the manifest, along with a sample specified module and a CI
+/// workflow running the specification test.
+fn write_spec_tested(manifest: &mut impl Write, path: &Path, addr: &str) -> anyhow::Result<()> {
+    writeln!(
+        manifest,
+        "
+# Defaults of `move spec-test`, see the move-spec-test README.
+[spec-test]
+# Timeout (in seconds) of proving a single mutant.
+prover-timeout = 300
+# Minimal mutation score (in percent) of the package.
+min-score = [80]
+
+# Options of the CI runs (`move spec-test --profile ci`).
+[profile.ci.spec-test]
+github-annotations = true
+summary-output = \"spec-test-summary.json\"
+junit-output = \"spec-test-junit.xml\"
+
+# Options of generating the mutants locally (`move mutate --profile ci`).
+[profile.ci.mutate]
+verify-mutants = true"
+    )?;
+
+    fs::write(
+        path.join(SourcePackageLayout::Sources.path())
+            .join("math.move"),
+        format!(
+            "module {addr}::math {{
+    /// Returns the sum of `x` and `y`.
+    public fun add(x: u64, y: u64): u64 {{
+        x + y
+    }}
+
+    spec add {{
+        aborts_if x + y > MAX_U64;
+        ensures result == x + y;
+    }}
+
+    /// Returns the greater of `x` and `y`.
+    public fun max(x: u64, y: u64): u64 {{
+        if (x >= y) x else y
+    }}
+
+    spec max {{
+        aborts_if false;
+        ensures result >= x && result >= y;
+        ensures result == x || result == y;
+    }}
+}}
+"
+        ),
+    )?;
+
+    let workflow = path.join(SPEC_TEST_WORKFLOW_PATH);
+    create_dir_all(workflow.parent().expect("workflow path has a parent"))?;
+    fs::write(
+        workflow,
+        "# Runs the specification test of the package, failing if the mutation score drops below
+# the `min-score` from Move.toml. Move the file to the `.github/workflows` directory of the
+# repository root if the package is not placed there.
+name: Specification test
+
+on: [push, pull_request]
+
+jobs:
+  spec-test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      # The Move CLI and the Move Prover dependencies (Boogie and Z3) must be installed first, see
+      # https://github.com/move-language/move/blob/main/language/move-prover/doc/user/install.md
+      - name: Run the specification test
+        run: move spec-test --profile ci
+      - name: Upload the results
+        if: always()
+        uses: actions/upload-artifact@v4
+        with:
+          name: spec-test
+          path: |
+            spec-test-summary.json
+            spec-test-junit.xml
+",
+    )?;
+    Ok(())
+}
@@ -2,13 +2,27 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::reroot_path;
+use crate::verbosity::Verbosity;
 use anyhow::bail;
 use clap::Parser;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
 use colored::Colorize;
-use move_package::{BuildConfig, ModelConfig};
+use log::LevelFilter;
+use move_model::{
+    ast::{PropertyValue, Value},
+    model::GlobalEnv,
+    pragmas::VERIFY_PRAGMA,
+};
+use move_package::{
+    compilation::package_layout::CompiledPackageLayout, resolution::digest::compute_file_digests,
+    BuildConfig, ModelConfig,
+};
 use move_prover::run_move_prover_with_model;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fs,
     io::Write,
     path::{Path, PathBuf},
     time::Instant,
@@ -36,9 +50,16 @@ pub struct Prove {
     /// this string will be part of verification.
     #[clap(short = 't', long = "target")]
     pub target_filter: Option<String>,
+    /// Prove only the modules whose sources (or the sources of the modules they use) changed since
+    /// the last successful run, as recorded in the build directory.
+    #[clap(long)]
+    pub changed_only: bool,
     /// Internal field indicating that this prover run is for a test.
     #[clap(skip)]
     pub for_test: bool,
+    /// Verbosity of the prover, unless given in the prover options.
+    #[clap(skip)]
+    pub verbosity: Verbosity,
     /// Any options passed to the prover.
     #[clap(subcommand)]
     pub options: Option<ProverOptions>,
@@ -49,7 +70,9 @@ impl Prove {
         let rerooted_path = reroot_path(path)?;
         let Self {
             target_filter,
+            changed_only,
             for_test,
+            verbosity,
             options,
         } = self;
         let opts = match options {
@@ -61,14 +84,23 @@ impl Prove {
         if prover_toml.exists() {
             args.push(format!("--config={}", prover_toml.to_string_lossy()));
         }
+        let verbosity_given = opts
+            .iter()
+            .any(|opt| opt == "-v" || opt.starts_with("--verbose"));
         args.extend(opts.iter().cloned());
-        let options = move_prover::cli::Options::create_from_args(&args)?;
+        let mut options = move_prover::cli::Options::create_from_args(&args)?;
+        if !verbosity_given {
+            options.verbosity_level = verbosity.level_filter();
+        }
         if for_test {
             options.setup_logging_for_test();
         } else {
             options.setup_logging();
         }
 
+        if changed_only {
+            return run_move_prover_changed_only(config, &rerooted_path, &target_filter, options);
+        }
         run_move_prover(config, &rerooted_path, &target_filter, for_test, options)
     }
 }
@@ -124,7 +156,9 @@ impl ProverTest {
         let pkg_path = path_in_crate(std::mem::take(&mut self.path));
         let cmd = Prove {
             target_filter: None,
+            changed_only: false,
             for_test: true,
+            verbosity: Verbosity::default(),
             options: Some(ProverOptions::Options(std::mem::take(&mut self.options))),
         };
         let res = cmd.execute(Some(pkg_path), move_package::BuildConfig::default());
@@ -163,26 +197,7 @@ pub fn run_move_prover(
 ) -> anyhow::Result<()> {
     // Always run the prover in dev mode, so addresses get default assignments
     config.dev_mode = true;
-
-    if !options.move_sources.is_empty() {
-        bail!(
-            "move prover options must not specify sources as those are given \
-                     by the package system. Did you meant to prefix `{}` with `-t`?",
-            &options.move_sources[0]
-        );
-    }
-    if !options.move_deps.is_empty() {
-        bail!(
-            "move prover options must not specify dependencies as those are given \
-                     by the package system"
-        );
-    }
-    if !options.move_named_address_values.is_empty() {
-        bail!(
-            "move prover options must not specify named addresses as those are given \
-                     by the package system"
-        );
-    }
+    check_prover_options(&options)?;
 
     let mut message_writer = StandardStream::stdout(ColorChoice::Auto);
     let mut error_writer = StandardStream::stderr(ColorChoice::Auto);
@@ -233,3 +248,249 @@ pub fn run_move_prover(
     }
     res
 }
+
+/// Rejects the prover options which are given by the package system.
+fn check_prover_options(options: &move_prover::cli::Options) -> anyhow::Result<()> {
+    if !options.move_sources.is_empty() {
+        bail!(
+            "move prover options must not specify sources as those are given \
+                     by the package system. Did you meant to prefix `{}` with `-t`?",
+            &options.move_sources[0]
+        );
+    }
+    if !options.move_deps.is_empty() {
+        bail!(
+            "move prover options must not specify dependencies as those are given \
+                     by the package system"
+        );
+    }
+    if !options.move_named_address_values.is_empty() {
+        bail!(
+            "move prover options must not specify named addresses as those are given \
+                     by the package system"
+        );
+    }
+    Ok(())
+}
+
+// =================================================================================================
+// Proving only the changed modules
+
+/// Name of the file in the build directory with the digests from the last successful run.
+const PROVE_STATE_FILE: &str = "prove_state.json";
+
+/// Digests of the modules proved in the last successful run.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProveState {
+    /// Digest of the prover and compiler options.
+    options: String,
+    /// Digests of the modules, covering the sources of the module and of all the modules it uses.
+    modules: BTreeMap<String, String>,
+}
+
+/// Runs the prover on the modules of the package whose digests changed since the last successful
+/// run, so unchanged modules are not proved again. The digest of a module covers its source file
+/// (along with the `<NAME>.spec.move` file next to it, if any) and the digests of all the modules
+/// it uses, directly or transitively, in the code or in the specifications (including the modules
+/// of the dependencies). Changes of the options invalidate all the digests.
+pub fn run_move_prover_changed_only(
+    mut config: BuildConfig,
+    path: &Path,
+    target_filter: &Option<String>,
+    options: move_prover::cli::Options,
+) -> anyhow::Result<()> {
+    // Always run the prover in dev mode, so addresses get default assignments
+    config.dev_mode = true;
+    check_prover_options(&options)?;
+
+    let build_dir = config
+        .install_dir
+        .clone()
+        .unwrap_or_else(|| path.to_path_buf())
+        .join(CompiledPackageLayout::Root.path());
+    let state_file = build_dir.join(PROVE_STATE_FILE);
+    let compiler_config = format!("{:?}", config.compiler_config);
+    let mut error_writer = StandardStream::stderr(ColorChoice::Auto);
+    let now = Instant::now();
+    let compiler_version = config.compiler_config.compiler_version.unwrap_or_default();
+    let language_version = config.compiler_config.language_version.unwrap_or_default();
+    let mut model = config.move_model_for_package(path, ModelConfig {
+        all_files_as_targets: false,
+        target_filter: target_filter.clone(),
+        compiler_version,
+        language_version,
+    })?;
+    if model.has_errors() {
+        // The prover reports the compilation errors.
+        return run_move_prover_with_model(&mut model, &mut error_writer, options, Some(now));
+    }
+
+    // The verbosity doesn't affect the results, so changing it doesn't invalidate the digests.
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(&move_prover::cli::Options {
+        verbosity_level: LevelFilter::Info,
+        ..options.clone()
+    })?);
+    hasher.update(compiler_config);
+    let options_digest = format!("{:x}", hasher.finalize());
+
+    let mut state = fs::read(&state_file)
+        .ok()
+        .and_then(|content| serde_json::from_slice::<ProveState>(&content).ok())
+        .filter(|state| state.options == options_digest)
+        .unwrap_or_default();
+
+    let digests = module_digests(&model)?;
+    let changed = model
+        .get_target_modules()
+        .iter()
+        .map(|module| module.get_full_name_str())
+        .filter(|name| state.modules.get(name) != digests.get(name))
+        .collect::<BTreeSet<_>>();
+    if changed.is_empty() {
+        println!("No modules changed since the last successful run, nothing to prove");
+        return Ok(());
+    }
+    println!(
+        "Proving the modules changed since the last successful run: {}",
+        changed.iter().cloned().collect::<Vec<_>>().join(", ")
+    );
+    verify_only(&model, &changed);
+    run_move_prover_with_model(&mut model, &mut error_writer, options, Some(now))?;
+
+    // Only the proved modules are recorded, so the failed or skipped ones are proved next time.
+    state.options = options_digest;
+    for name in changed {
+        if let Some(digest) = digests.get(&name) {
+            state.modules.insert(name, digest.clone());
+        }
+    }
+    fs::create_dir_all(&build_dir)?;
+    fs::write(&state_file, serde_json::to_vec_pretty(&state)?)?;
+    Ok(())
+}
+
+/// Excludes the functions of the target modules other than the given ones from the verification,
+/// as if they were marked with `pragma verify = false`. Their specifications are still used when
+/// verifying the given modules.
+fn verify_only(model: &GlobalEnv, modules: &BTreeSet<String>) {
+    let pragma = model.symbol_pool().make(VERIFY_PRAGMA);
+    for module in model.get_target_modules() {
+        if modules.contains(&module.get_full_name_str()) {
+            continue;
+        }
+        for fun in module.get_functions() {
+            fun.get_mut_spec()
+                .properties
+                .insert(pragma, PropertyValue::Value(Value::Bool(false)));
+        }
+    }
+}
+
+/// Computes the digests of all the modules in the model, keyed by their full names.
+fn module_digests(model: &GlobalEnv) -> anyhow::Result<BTreeMap<String, String>> {
+    // The sources of a module include the `<NAME>.spec.move` file next to it, if any.
+    let sources = model
+        .get_modules()
+        .map(|module| {
+            let path = PathBuf::from(module.get_source_path());
+            let spec = path.with_extension("spec.move");
+            (module.get_id(), [path, spec])
+        })
+        .collect::<BTreeMap<_, _>>();
+    let files = compute_file_digests(&sources.values().flatten().cloned().collect::<Vec<_>>())?;
+
+    let mut digests = BTreeMap::new();
+    for module in model.get_modules() {
+        // The modules used transitively, as their code is inlined and their specifications are
+        // assumed when the module is verified.
+        let mut used = BTreeSet::from([module.get_id()]);
+        let mut queue = VecDeque::from([module.get_id()]);
+        while let Some(id) = queue.pop_front() {
+            for next in model.get_module(id).get_used_modules(true) {
+                if used.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        let mut hasher = Sha256::new();
+        for id in used {
+            hasher.update(model.get_module(id).get_full_name_str());
+            for file in &sources[&id] {
+                if let Some(digest) = files.get(file) {
+                    hasher.update(digest.to_string());
+                }
+            }
+        }
+        digests.insert(
+            module.get_full_name_str(),
+            format!("{:x}", hasher.finalize()),
+        );
+    }
+    Ok(digests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digests(path: &Path) -> BTreeMap<String, String> {
+        let model = BuildConfig::default()
+            .move_model_for_package(path, ModelConfig {
+                all_files_as_targets: false,
+                target_filter: None,
+                compiler_version: Default::default(),
+                language_version: Default::default(),
+            })
+            .unwrap();
+        module_digests(&model).unwrap()
+    }
+
+    #[test]
+    fn changes_propagate_to_using_modules() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sources")).unwrap();
+        fs::write(
+            dir.path().join("Move.toml"),
+            "[package]\nname = \"Calc\"\nversion = \"0.0.1\"\n\n[addresses]\ncalc = \"0x42\"\n",
+        )
+        .unwrap();
+        let write_math = |body: &str| {
+            fs::write(
+                dir.path().join("sources/Math.move"),
+                format!("module calc::Math {{\n    public fun add(x: u64, y: u64): u64 {{ {body} }}\n}}\n"),
+            )
+            .unwrap()
+        };
+        write_math("x + y");
+        fs::write(
+            dir.path().join("sources/Sum.move"),
+            "module calc::Sum {\n    public fun sum(x: u64): u64 { calc::Math::add(x, 1) }\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sources/Log.move"),
+            "module calc::Log {\n    public fun id(x: u64): u64 { x }\n}\n",
+        )
+        .unwrap();
+
+        let before = digests(dir.path());
+        assert_eq!(before.len(), 3);
+        write_math("y + x");
+        let after = digests(dir.path());
+        let changed = before
+            .keys()
+            .filter(|name| before[*name] != after[*name])
+            .collect::<Vec<_>>();
+        assert_eq!(changed, vec!["0x42::Math", "0x42::Sum"]);
+
+        fs::write(
+            dir.path().join("sources/Log.spec.move"),
+            "spec calc::Log {\n    spec id {\n        ensures result == x;\n    }\n}\n",
+        )
+        .unwrap();
+        let with_spec = digests(dir.path());
+        assert_ne!(with_spec["0x42::Log"], after["0x42::Log"]);
+        assert_eq!(with_spec["0x42::Sum"], after["0x42::Sum"]);
+    }
+}
@@ -1,28 +1,186 @@
+use crate::{
+    base::mutate_test::{check_original_package, unit_test_check},
+    NativeFunctionRecord,
+};
 use clap::*;
-use move_package::BuildConfig;
+use move_core_types::effects::ChangeSet;
+use move_package::{source_package::layout::SourcePackageLayout, BuildConfig};
+use move_spec_test::cli::ReportFormat;
+use move_unit_test::UnitTestingConfig;
+use move_vm_test_utils::gas_schedule::CostTable;
 use std::path::PathBuf;
 
 /// Test the Move specification using the Move Mutator and Move Prover
 #[derive(Parser)]
-#[clap(name = "spec-test")]
+#[clap(name = "spec-test", infer_subcommands = true)]
 pub struct SpecTest {
     /// Any options passed to the move-spec-test
     #[clap(flatten)]
     pub options: Option<move_spec_test::cli::CLIOptions>,
+
+    /// Run the unit tests of the package against each mutant first and prove only the mutants
+    /// which pass them
+    #[clap(long)]
+    pub tests_first: bool,
+
+    /// Optional action performed on already generated reports
+    #[clap(subcommand)]
+    pub cmd: Option<SpecTestCommand>,
+}
+
+/// Actions performed on the spec-test reports
+#[derive(Subcommand)]
+pub enum SpecTestCommand {
+    /// Compare two JSON reports and fail if the new one is a regression
+    Diff(DiffArgs),
+    /// Check the JSON report against a baseline report and fail if new mutants survive
+    Check(CheckArgs),
+    /// Render an SVG badge with the mutation score from the JSON report
+    Badge(BadgeArgs),
+    /// Show the evolution of the mutation scores recorded in the history database
+    Trends(TrendsArgs),
+    /// Merge JSON reports (e.g. from the shards of a single run) into a single report
+    Merge(MergeArgs),
+    /// Render the report of a completed run in another format without re-running anything
+    Report(ReportArgs),
+    /// Step through the surviving mutants and record the triage decisions into the report
+    Review(ReviewArgs),
+}
+
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Path to the JSON report from the previous run
+    pub old_report: PathBuf,
+    /// Path to the JSON report from the current run
+    pub new_report: PathBuf,
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// Path to the JSON report from the current run
+    pub report: PathBuf,
+    /// Path to the baseline JSON report (e.g. committed to the repository)
+    #[clap(long, value_parser)]
+    pub against: PathBuf,
+}
+
+#[derive(Args)]
+pub struct BadgeArgs {
+    /// Path to the JSON report
+    pub report: PathBuf,
+    /// Path to the SVG badge
+    #[clap(long, short, value_parser, default_value = "mutation-score.svg")]
+    pub output: PathBuf,
+}
+
+#[derive(Args)]
+pub struct TrendsArgs {
+    /// Path to the SQLite history database (see the `--history` option)
+    pub history: PathBuf,
+    /// Maximum number of the most recent runs to show
+    #[clap(long, default_value = "20")]
+    pub limit: usize,
+}
+
+#[derive(Args)]
+pub struct MergeArgs {
+    /// Paths to the JSON reports to merge
+    #[clap(required = true, value_parser)]
+    pub reports: Vec<PathBuf>,
+    /// Path to the merged JSON report
+    #[clap(long, short, value_parser)]
+    pub output: PathBuf,
+}
+
+#[derive(Args)]
+pub struct ReportArgs {
+    /// Path to the mutator report with the status of each mutant (see `--mutator-report-output`)
+    /// or to the JSON report with the mutation scores (see `--output`)
+    pub report: PathBuf,
+    /// Format of the rendered report (text, json, html, markdown, sarif, junit, csv or stryker)
+    #[clap(long, short, default_value = "text")]
+    pub format: ReportFormat,
+    /// Path to the rendered report, printed to stdout if not given
+    #[clap(long, short, value_parser)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct ReviewArgs {
+    /// Path to the mutator report with the status of each mutant (see `--mutator-report-output`)
+    pub report: PathBuf,
+    /// Quarantine file where the mutants marked as equivalent are added
+    #[clap(long, value_parser)]
+    pub quarantine: Option<PathBuf>,
+    /// Review also the mutants with a decision from the previous reviews
+    #[clap(long)]
+    pub all: bool,
 }
 
 impl SpecTest {
     /// Executes the spec-test command which produces mutants from the Move files or package using
     /// the provided configuration. Then it passes the mutants to the Move prover to check if the
     /// mutants are killed by the prover.
+    /// With `--tests-first`, the unit tests are run against each mutant first, so only the mutants
+    /// which pass them are proved.
     /// If no path is provided, the current directory is used.
-    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+    pub fn execute(
+        self,
+        path: Option<PathBuf>,
+        config: BuildConfig,
+        natives: Vec<NativeFunctionRecord>,
+        genesis: ChangeSet,
+        cost_table: Option<CostTable>,
+    ) -> anyhow::Result<()> {
         let path = path.unwrap_or_else(|| PathBuf::from("."));
 
-        let Self { options } = self;
+        let Self {
+            options,
+            tests_first,
+            cmd,
+        } = self;
+
+        match cmd {
+            Some(SpecTestCommand::Diff(args)) => {
+                move_spec_test::run_report_diff(&args.old_report, &args.new_report)
+            },
+            Some(SpecTestCommand::Check(args)) => {
+                move_spec_test::run_check(&args.report, &args.against)
+            },
+            Some(SpecTestCommand::Badge(args)) => {
+                move_spec_test::run_badge(&args.report, &args.output)
+            },
+            Some(SpecTestCommand::Trends(args)) => {
+                move_spec_test::run_trends(&args.history, args.limit)
+            },
+            Some(SpecTestCommand::Merge(args)) => {
+                move_spec_test::run_merge(&args.reports, &args.output)
+            },
+            Some(SpecTestCommand::Report(args)) => {
+                move_spec_test::run_report(&args.report, args.format, args.output.as_deref())
+            },
+            Some(SpecTestCommand::Review(args)) => {
+                move_spec_test::run_review(&args.report, args.quarantine.as_deref(), args.all)
+            },
+            None if tests_first => {
+                let options = options.unwrap_or_default();
+                let run_tests = unit_test_check(
+                    &config,
+                    UnitTestingConfig::default_with_bound(None),
+                    natives,
+                    genesis,
+                    cost_table,
+                );
+                let package_path = SourcePackageLayout::try_find_root(&path.canonicalize()?)?;
+                check_original_package(&run_tests, &package_path)?;
 
-        let options = options.unwrap_or_default();
+                move_spec_test::run_spec_test_with_check(&options, &config, &path, Some(&run_tests))
+            },
+            None => {
+                let options = options.unwrap_or_default();
 
-        move_spec_test::run_spec_test(&options, &config, &path)
+                move_spec_test::run_spec_test(&options, &config, &path)
+            },
+        }
     }
 }
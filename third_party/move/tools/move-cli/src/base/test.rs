@@ -2,11 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::reroot_path;
-use crate::{base::test_validation, NativeFunctionRecord};
+use crate::{
+    base::{
+        mutate_test::{test_mutants_dir, unit_test_check},
+        test_validation,
+    },
+    NativeFunctionRecord,
+};
 use anyhow::{bail, Result};
 use clap::*;
 use codespan_reporting::term::{termcolor, termcolor::StandardStream};
-use move_command_line_common::files::{FileHash, MOVE_COVERAGE_MAP_EXTENSION};
+use move_command_line_common::{
+    files::{FileHash, MOVE_COVERAGE_MAP_EXTENSION},
+    output_format::OutputFormat,
+};
 use move_compiler::{
     diagnostics::{self, codes::Severity},
     shared::{NumberFormat, NumericalAddress},
@@ -23,6 +32,7 @@ use move_package::{
 use move_unit_test::UnitTestingConfig;
 use move_vm_runtime::tracing::{LOGGING_FILE_WRITER, TRACING_ENABLED};
 use move_vm_test_utils::gas_schedule::CostTable;
+use serde_json::json;
 // if unix
 #[cfg(target_family = "unix")]
 use std::os::unix::prelude::ExitStatusExt;
@@ -84,6 +94,13 @@ pub struct Test {
     /// Collect coverage information for later use with the various `move coverage` subcommands
     #[clap(long = "coverage")]
     pub compute_coverage: bool,
+    /// Format of the test results printed on stdout (`human` or `json`)
+    #[clap(long, default_value = "human")]
+    pub output_format: OutputFormat,
+    /// Run the tests once per mutant from the directory produced by `move mutate` (containing the
+    /// mutator report) and report which mutants are killed by the tests
+    #[clap(long, value_parser, conflicts_with_all = ["list", "compute_coverage"])]
+    pub mutants_dir: Option<PathBuf>,
 
     /// Use the EVM-based execution backend.
     /// Does not work with --stackless.
@@ -101,6 +118,13 @@ impl Test {
         genesis: ChangeSet,
         cost_table: Option<CostTable>,
     ) -> anyhow::Result<()> {
+        // The mutants directory is relative to the working directory, so it's resolved before
+        // moving into the package root.
+        let mutants_dir = self
+            .mutants_dir
+            .as_deref()
+            .map(Path::canonicalize)
+            .transpose()?;
         let rerooted_path = reroot_path(path)?;
         let Self {
             gas_limit,
@@ -113,6 +137,8 @@ impl Test {
             check_stackless_vm,
             verbose_mode,
             compute_coverage,
+            output_format,
+            mutants_dir: _,
             #[cfg(feature = "evm-backend")]
             evm,
         } = self;
@@ -131,16 +157,43 @@ impl Test {
 
             ..UnitTestingConfig::default_with_bound(None)
         };
-        let result = run_move_unit_tests(
-            &rerooted_path,
-            config,
-            unit_test_config,
-            natives,
-            genesis,
-            cost_table,
-            compute_coverage,
-            &mut std::io::stdout(),
-        )?;
+        if let Some(mutants_dir) = mutants_dir {
+            let run_tests =
+                unit_test_check(&config, unit_test_config, natives, genesis, cost_table);
+            return test_mutants_dir(&run_tests, &rerooted_path, &mutants_dir, output_format);
+        }
+        let result = if output_format.is_json() {
+            // The report is captured without colors, so the results can be picked from it.
+            colored::control::set_override(false);
+            let mut output = Vec::new();
+            let result = run_move_unit_tests(
+                &rerooted_path,
+                config,
+                unit_test_config,
+                natives,
+                genesis,
+                cost_table,
+                compute_coverage,
+                &mut output,
+            );
+            let results = match &result {
+                Ok(result) => test_results(&String::from_utf8_lossy(&output), list, result),
+                Err(e) => json!({ "success": false, "error": format!("{e:#}") }),
+            };
+            println!("{}", serde_json::to_string_pretty(&results)?);
+            result?
+        } else {
+            run_move_unit_tests(
+                &rerooted_path,
+                config,
+                unit_test_config,
+                natives,
+                genesis,
+                cost_table,
+                compute_coverage,
+                &mut std::io::stdout(),
+            )?
+        };
 
         // Return a non-zero exit code if any test failed
         if let UnitTestResult::Failure = result {
@@ -150,6 +203,37 @@ impl Test {
     }
 }
 
+/// Collects the results of the unit tests from their report: the listed tests in the list mode, the
+/// passed, failed and timed out tests otherwise.
+fn test_results(report: &str, list: bool, result: &UnitTestResult) -> serde_json::Value {
+    if list {
+        let tests = report
+            .lines()
+            .filter_map(|line| line.strip_suffix(": test"))
+            .collect::<Vec<_>>();
+        return json!({ "tests": tests });
+    }
+
+    let (mut passed, mut failed, mut timed_out) = (Vec::new(), Vec::new(), Vec::new());
+    for line in report.lines() {
+        if let Some(test) = line.strip_prefix("[ PASS    ] ") {
+            passed.push(test);
+        } else if let Some(test) = line.strip_prefix("[ FAIL    ] ") {
+            failed.push(test);
+        } else if let Some(test) = line.strip_prefix("[ TIMEOUT ] ") {
+            timed_out.push(test);
+        }
+    }
+    json!({
+        "success": *result == UnitTestResult::Success,
+        "total": passed.len() + failed.len() + timed_out.len(),
+        "passed": passed,
+        "failed": failed,
+        "timed_out": timed_out,
+        "output": report,
+    })
+}
+
 /// Encapsulates the possible returned states when running unit tests on a move package.
 #[derive(PartialEq, Eq, Debug)]
 pub enum UnitTestResult {
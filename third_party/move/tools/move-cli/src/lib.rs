@@ -3,13 +3,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use base::{
-    build::Build, coverage::Coverage, disassemble::Disassemble, docgen::Docgen, errmap::Errmap,
-    mutate::Mutate, new::New, prove::Prove, spec_test::SpecTest, test::Test,
+    build::Build, completions::Completions, coverage::Coverage, disassemble::Disassemble,
+    docgen::Docgen, errmap::Errmap, mutate::Mutate, mutate_test::MutateTest, new::New,
+    prove::Prove, spec_test::SpecTest, test::Test,
 };
 use move_package::BuildConfig;
 
 pub mod base;
+pub mod profile;
 pub mod test;
+pub mod verbosity;
 
 /// Default directory where saved Move resources live
 pub const DEFAULT_STORAGE_DIR: &str = "storage";
@@ -18,13 +21,14 @@ pub const DEFAULT_STORAGE_DIR: &str = "storage";
 pub const DEFAULT_BUILD_DIR: &str = ".";
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{ArgAction, Parser};
 use move_core_types::{
     account_address::AccountAddress, effects::ChangeSet, identifier::Identifier,
 };
 use move_vm_runtime::native_functions::NativeFunction;
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::path::PathBuf;
+use verbosity::Verbosity;
 
 type NativeFunctionRecord = (AccountAddress, Identifier, Identifier, NativeFunction);
 
@@ -35,19 +39,49 @@ pub struct Move {
     #[clap(long = "path", short = 'p', global = true, value_parser)]
     pub package_path: Option<PathBuf>,
 
-    /// Print additional diagnostics if available.
-    #[clap(short = 'v', global = true)]
-    pub verbose: bool,
+    /// Print additional diagnostics: informational messages (`-v`), debugging messages (`-vv`) or
+    /// everything (`-vvv`).
+    #[clap(short = 'v', global = true, action = ArgAction::Count)]
+    pub verbose_count: u8,
+
+    /// Print only the errors and the results of the command.
+    #[clap(short = 'q', long, global = true, conflicts_with = "verbose_count")]
+    pub quiet: bool,
+
+    /// Name of the profile (a set of command options defined in the `[profile.<NAME>]` section of
+    /// the manifest) to run the command with.
+    #[clap(long, global = true)]
+    pub profile: Option<String>,
+
+    /// File with the profiles, used instead of the package manifest.
+    #[clap(long, global = true, value_parser, requires = "profile")]
+    pub profile_file: Option<PathBuf>,
 
     /// Package build options
     #[clap(flatten)]
     pub build_config: BuildConfig,
 }
 
+impl Move {
+    /// Checks if additional diagnostics were requested with the `-v` flag (given any number of
+    /// times).
+    pub fn verbose(&self) -> bool {
+        self.verbose_count > 0
+    }
+
+    /// Returns the verbosity chosen with the `-v` and `-q` flags.
+    pub fn verbosity(&self) -> Verbosity {
+        Verbosity::from_flags(self.verbose_count, self.quiet)
+    }
+}
+
 /// MoveCLI is the CLI that will be executed by the `move-cli` command
 /// The `cmd` argument is added here rather than in `Move` to make it
 /// easier for other crates to extend `move-cli`
+///
+/// Commands can be abbreviated to any unambiguous prefix of their names (e.g. `move cov`).
 #[derive(Parser)]
+#[clap(infer_subcommands = true)]
 pub struct MoveCLI {
     #[clap(flatten)]
     pub move_args: Move,
@@ -57,16 +91,23 @@ pub struct MoveCLI {
 }
 
 #[derive(Parser)]
+#[allow(clippy::large_enum_variant)]
 pub enum Command {
+    #[clap(visible_alias = "b")]
     Build(Build),
+    Completions(Completions),
     Coverage(Coverage),
     Disassemble(Disassemble),
     Docgen(Docgen),
     Errmap(Errmap),
     Mutate(Mutate),
+    #[clap(visible_alias = "mt")]
+    MutateTest(MutateTest),
     New(New),
     Prove(Prove),
+    #[clap(visible_alias = "st")]
     SpecTest(SpecTest),
+    #[clap(visible_alias = "t")]
     Test(Test),
 }
 
@@ -80,16 +121,39 @@ pub fn run_cli(
     // TODO: right now, the gas metering story for move-cli (as a library) is a bit of a mess.
     //         1. It's still using the old CostTable.
     //         2. The CostTable only affects sandbox runs, but not unit tests, which use a unit cost table.
+    let verbosity = move_args.verbosity();
+    // The prover sets up its own logger, so the verbosity given in its options is respected.
+    if !matches!(cmd, Command::Prove(_)) {
+        verbosity.setup_logging();
+    }
     match cmd {
-        Command::Build(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Build(c) => {
+            Build { verbosity, ..c }.execute(move_args.package_path, move_args.build_config)
+        },
+        Command::Completions(c) => c.execute(),
         Command::Coverage(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Disassemble(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Docgen(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Errmap(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Mutate(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::MutateTest(c) => c.execute(
+            move_args.package_path,
+            move_args.build_config,
+            natives,
+            genesis,
+            Some(cost_table.clone()),
+        ),
         Command::New(c) => c.execute_with_defaults(move_args.package_path),
-        Command::Prove(c) => c.execute(move_args.package_path, move_args.build_config),
-        Command::SpecTest(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Prove(c) => {
+            Prove { verbosity, ..c }.execute(move_args.package_path, move_args.build_config)
+        },
+        Command::SpecTest(c) => c.execute(
+            move_args.package_path,
+            move_args.build_config,
+            natives,
+            genesis,
+            Some(cost_table.clone()),
+        ),
         Command::Test(c) => c.execute(
             move_args.package_path,
             move_args.build_config,
@@ -105,7 +169,7 @@ pub fn move_cli(
     genesis: ChangeSet,
     cost_table: &CostTable,
 ) -> Result<()> {
-    let args = MoveCLI::parse();
+    let args = MoveCLI::parse_from(profile::apply_profile(std::env::args_os().collect())?);
     run_cli(natives, genesis, cost_table, args.move_args, args.cmd)
 }
 
@@ -114,3 +178,23 @@ fn verify_tool() {
     use clap::CommandFactory;
     MoveCLI::command().debug_assert()
 }
+
+#[test]
+fn commands_can_be_shortened() {
+    let command = |args: &[&str]| {
+        MoveCLI::try_parse_from(std::iter::once("move").chain(args.iter().copied()))
+            .map(|cli| cli.cmd)
+    };
+    assert!(matches!(command(&["st"]), Ok(Command::SpecTest(_))));
+    assert!(matches!(command(&["mt"]), Ok(Command::MutateTest(_))));
+    assert!(matches!(command(&["t"]), Ok(Command::Test(_))));
+    assert!(matches!(command(&["mutate-t"]), Ok(Command::MutateTest(_))));
+    assert!(matches!(command(&["mutate"]), Ok(Command::Mutate(_))));
+    assert!(matches!(command(&["cov", "su"]), Ok(Command::Coverage(_))));
+    assert!(matches!(
+        command(&["mutate", "expl", "x"]),
+        Ok(Command::Mutate(_))
+    ));
+    // Ambiguous prefixes are rejected.
+    assert!(command(&["c"]).is_err());
+}
@@ -2,11 +2,20 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
 use move_core_types::{account_address::AccountAddress, effects::ChangeSet};
 use move_stdlib::natives::{all_natives, nursery_natives, GasParameters, NurseryGasParameters};
 
-fn main() -> Result<()> {
+fn main() {
+    // The binary is started again with the hidden `sandbox-task` subcommand to run the sandboxed tasks
+    // of the mutator and the specification tester.
+    if let Some(task) = move_mutator::sandbox::requested_task() {
+        if let Err(e) = move_spec_test::run_sandbox_task(&task) {
+            eprintln!("Error: {e:?}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let cost_table = &move_vm_test_utils::gas_schedule::INITIAL_COST_SCHEDULE;
     let addr = AccountAddress::from_hex_literal("0x1").unwrap();
     let natives = all_natives(addr, GasParameters::zeros())
@@ -14,5 +23,9 @@ fn main() -> Result<()> {
         .chain(nursery_natives(addr, NurseryGasParameters::zeros()))
         .collect();
 
-    move_cli::move_cli(natives, ChangeSet::new(), cost_table)
+    if let Err(e) = move_cli::move_cli(natives, ChangeSet::new(), cost_table) {
+        eprintln!("Error: {e:?}");
+        // Spec-test failures (e.g. the score below the threshold) have their own exit codes.
+        std::process::exit(move_spec_test::exit_code(&e));
+    }
 }
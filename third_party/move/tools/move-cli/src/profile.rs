@@ -0,0 +1,141 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MoveCLI;
+use anyhow::{anyhow, bail, Context};
+use clap::{parser::ValueSource, CommandFactory};
+use move_package::source_package::{
+    layout::SourcePackageLayout, manifest_parser::parse_move_manifest_string,
+};
+use std::{ffi::OsString, fs, path::PathBuf};
+use toml::Value;
+
+/// Name of the manifest section with the profiles.
+const PROFILE_SECTION: &str = "profile";
+
+/// Inserts the options of the profile selected with `--profile` into the command line arguments.
+///
+/// Profiles are named sets of command line options, defined in the `[profile.<NAME>]` sections of
+/// the package manifest (or of the file given with `--profile-file`), e.g.:
+///
+/// ```toml
+/// [profile.ci]
+/// dev = true
+///
+/// [profile.ci.mutate]
+/// downsampling-ratio-percentage = 50
+///
+/// [profile.ci.spec-test]
+/// prover-timeout = 300
+/// min-score = [80, "Sum=90"]
+/// ```
+///
+/// The keys are the long names of the options. The top level ones are used with every command
+/// (e.g. the build options), while the ones in the tables are used with the named command only.
+/// Flags are set with `true` and options given multiple times with arrays. Options given on the
+/// command line or with the environment variables take precedence over the ones from the profile.
+///
+/// # Arguments
+///
+/// * `args` - the command line arguments, starting with the binary name.
+///
+/// # Errors
+///
+/// Returns an error if the profile is not defined or it contains an unknown or invalid option.
+///
+/// # Returns
+///
+/// * `anyhow::Result<Vec<OsString>>` - the arguments with the options of the profile.
+pub fn apply_profile(args: Vec<OsString>) -> anyhow::Result<Vec<OsString>> {
+    let mut command = MoveCLI::command();
+    command.build();
+    // Invalid arguments are reported when the arguments are parsed.
+    let Ok(matches) = command.clone().try_get_matches_from(&args) else {
+        return Ok(args);
+    };
+    let (Some(name), Some((command_name, command_matches))) =
+        (matches.get_one::<String>("profile"), matches.subcommand())
+    else {
+        return Ok(args);
+    };
+
+    let file = match matches.get_one::<PathBuf>("profile_file") {
+        Some(file) => file.clone(),
+        None => {
+            let path = matches
+                .get_one::<PathBuf>("package_path")
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from("."));
+            SourcePackageLayout::try_find_root(&path.canonicalize()?)?
+                .join(SourcePackageLayout::Manifest.path())
+        },
+    };
+    let profiles = parse_move_manifest_string(
+        fs::read_to_string(&file).with_context(|| format!("Unable to read {}", file.display()))?,
+    )?;
+    let profile = profiles
+        .get(PROFILE_SECTION)
+        .and_then(|profiles| profiles.get(name))
+        .and_then(Value::as_table)
+        .ok_or_else(|| anyhow!("Profile '{name}' is not defined in {}", file.display()))?;
+
+    let command = command
+        .find_subcommand(command_name)
+        .expect("subcommand of the matches must exist");
+    let options = profile.iter().filter(|(_, value)| !value.is_table()).chain(
+        profile
+            .get(command_name)
+            .and_then(Value::as_table)
+            .into_iter()
+            .flatten(),
+    );
+
+    let mut profile_args = Vec::new();
+    for (key, value) in options {
+        let arg = command
+            .get_arguments()
+            .find(|arg| arg.get_long() == Some(key))
+            .ok_or_else(|| {
+                anyhow!("Unknown option '{key}' of the {command_name} command in profile '{name}'")
+            })?;
+        if matches!(
+            command_matches.value_source(arg.get_id().as_str()),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        ) {
+            continue;
+        }
+        let values = match value {
+            Value::Array(values) => values.clone(),
+            value => vec![value.clone()],
+        };
+        for value in values {
+            match value {
+                Value::Boolean(false) => {},
+                Value::Boolean(true) => profile_args.push(format!("--{key}")),
+                Value::String(value) => profile_args.extend([format!("--{key}"), value]),
+                Value::Integer(_) | Value::Float(_) => {
+                    profile_args.extend([format!("--{key}"), value.to_string()])
+                },
+                _ => bail!("Invalid value of option '{key}' in profile '{name}': {value}"),
+            }
+        }
+    }
+
+    // The options are placed right after the command, so they don't get to its subcommands. The
+    // command may be given with its alias or an abbreviation of its name.
+    let position = args
+        .iter()
+        .skip(1)
+        .position(|arg| {
+            let arg = arg.to_string_lossy();
+            command.get_all_aliases().any(|alias| alias == arg)
+                || (!arg.is_empty() && !arg.starts_with('-') && command_name.starts_with(&*arg))
+        })
+        .map_or(args.len(), |position| position + 2);
+    let mut args = args;
+    args.splice(
+        position..position,
+        profile_args.into_iter().map(OsString::from),
+    );
+    Ok(args)
+}
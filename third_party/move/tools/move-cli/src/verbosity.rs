@@ -0,0 +1,77 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use log::LevelFilter;
+
+/// Verbosity of the commands, chosen with the global `-q` and (repeated) `-v` flags and shared by
+/// the mutator, the specification tester, the prover and the package compilation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only the errors are logged and the progress messages are dropped (`-q`).
+    Quiet,
+    /// Errors and warnings are logged.
+    #[default]
+    Normal,
+    /// Informational messages are logged as well (`-v`).
+    Verbose,
+    /// Debugging messages are logged as well (`-vv`).
+    Debug,
+    /// Everything is logged (`-vvv`).
+    Trace,
+}
+
+impl Verbosity {
+    /// Picks the verbosity from the number of the `-v` flags and the `-q` flag.
+    pub fn from_flags(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Self::Quiet,
+            (false, 0) => Self::Normal,
+            (false, 1) => Self::Verbose,
+            (false, 2) => Self::Debug,
+            (false, _) => Self::Trace,
+        }
+    }
+
+    /// Returns the most verbose level of the messages logged with this verbosity.
+    pub fn level_filter(self) -> LevelFilter {
+        match self {
+            Self::Quiet => LevelFilter::Error,
+            Self::Normal => LevelFilter::Warn,
+            Self::Verbose => LevelFilter::Info,
+            Self::Debug => LevelFilter::Debug,
+            Self::Trace => LevelFilter::Trace,
+        }
+    }
+
+    /// Tells if the progress messages (e.g. of the package compilation) should be dropped.
+    pub fn is_quiet(self) -> bool {
+        self == Self::Quiet
+    }
+
+    /// Sets up the logger used by all the tools run by the command. The `RUST_LOG` environment
+    /// variable can still refine the level of the chosen modules.
+    ///
+    /// The logger is set up only once, so the tools trying to set up their own logger later keep
+    /// this one.
+    pub fn setup_logging(self) {
+        let _ = pretty_env_logger::formatted_builder()
+            .filter_level(self.level_filter())
+            .parse_default_env()
+            .try_init();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_are_mapped_to_levels() {
+        let level = |verbose, quiet| Verbosity::from_flags(verbose, quiet).level_filter();
+        assert_eq!(level(0, true), LevelFilter::Error);
+        assert_eq!(level(0, false), LevelFilter::Warn);
+        assert_eq!(level(1, false), LevelFilter::Info);
+        assert_eq!(level(2, false), LevelFilter::Debug);
+        assert_eq!(level(5, false), LevelFilter::Trace);
+    }
+}
@@ -2,7 +2,7 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{env, process::Command};
+use std::{env, fs, path::Path, process::Command};
 
 #[test]
 fn cross_process_locking_git_deps() {
@@ -22,3 +22,404 @@ fn cross_process_locking_git_deps() {
         .expect("Package2 failed");
     handle.join().unwrap();
 }
+
+#[test]
+fn completions_cover_subcommands() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let output = Command::new(cli_exe)
+        .args(["completions", "bash"])
+        .output()
+        .expect("completions failed");
+    assert!(output.status.success());
+    let script = String::from_utf8(output.stdout).unwrap();
+    assert!(script.contains("complete -F _move"));
+    assert!(script.contains("spec-test"));
+    assert!(script.contains("--prover-timeout"));
+}
+
+/// Creates a package with a passing and a failing unit test.
+fn create_test_package(path: &Path) {
+    fs::create_dir_all(path.join("sources")).unwrap();
+    let stdlib = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../move-stdlib");
+    fs::write(
+        path.join("Move.toml"),
+        format!(
+            "[package]\nname = \"Sum\"\nversion = \"0.0.1\"\n\n[addresses]\nsum = \"0x42\"\n\n\
+             [dev-addresses]\nstd = \"0x1\"\n\n[dev-dependencies]\nMoveStdlib = {{ local = {:?} }}\n",
+            stdlib.display().to_string()
+        ),
+    )
+    .unwrap();
+    fs::write(
+        path.join("sources/Sum.move"),
+        "module sum::Sum {\n    public fun sum(x: u64, y: u64): u64 { x + y }\n\n    #[test]\n    \
+         fun sum_works() { assert!(sum(1, 2) == 3, 0) }\n\n    #[test]\n    fun sum_fails() { \
+         assert!(sum(1, 2) == 4, 0) }\n}\n",
+    )
+    .unwrap();
+}
+
+fn run_json(path: &Path, args: &[&str]) -> (bool, serde_json::Value) {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let output = Command::new(cli_exe)
+        .args(args)
+        .args(["--output-format", "json", "--path"])
+        .arg(path)
+        .output()
+        .expect("command failed");
+    let results = serde_json::from_slice(&output.stdout).expect("stdout is not JSON");
+    (output.status.success(), results)
+}
+
+#[test]
+fn build_results_are_printed_as_json() {
+    let dir = tempfile::tempdir().unwrap();
+    create_test_package(dir.path());
+
+    let (success, results) = run_json(dir.path(), &["build"]);
+    assert!(success);
+    assert_eq!(results["success"], true);
+    assert_eq!(results["package"], "Sum");
+    assert_eq!(results["modules"], serde_json::json!(["Sum"]));
+    assert!(results["build_dir"]
+        .as_str()
+        .unwrap()
+        .ends_with("build/Sum"));
+}
+
+#[test]
+fn test_results_are_printed_as_json() {
+    let dir = tempfile::tempdir().unwrap();
+    create_test_package(dir.path());
+
+    let (success, results) = run_json(dir.path(), &["test"]);
+    assert!(!success);
+    assert_eq!(results["success"], false);
+    assert_eq!(results["total"], 2);
+    assert_eq!(
+        results["passed"],
+        serde_json::json!(["0x42::Sum::sum_works"])
+    );
+    assert_eq!(
+        results["failed"],
+        serde_json::json!(["0x42::Sum::sum_fails"])
+    );
+
+    let (success, results) = run_json(dir.path(), &["test", "--list"]);
+    assert!(success);
+    assert_eq!(results["tests"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn tests_are_run_against_mutants() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let dir = tempfile::tempdir().unwrap();
+    create_test_package(dir.path());
+    let mutants_dir = dir.path().join("mutants");
+    assert!(Command::new(cli_exe)
+        .args(["mutate", "--out-mutant-dir"])
+        .arg(&mutants_dir)
+        .arg("--path")
+        .arg(dir.path())
+        .status()
+        .expect("command failed")
+        .success());
+
+    let (success, results) = run_json(dir.path(), &[
+        "test",
+        "sum_works",
+        "--mutants-dir",
+        mutants_dir.to_str().unwrap(),
+    ]);
+    assert!(success);
+    let mutants = results["mutants"].as_array().unwrap();
+    assert!(!mutants.is_empty());
+    assert_eq!(results["total"], mutants.len());
+    let killed = mutants.iter().filter(|m| m["status"] == "killed").count();
+    assert!(killed > 0);
+    assert_eq!(results["killed"], killed);
+    assert!(mutants
+        .iter()
+        .all(|m| m["status"] == "killed" || m["status"] == "survived"));
+
+    // The original package has to pass the tests first.
+    assert!(!Command::new(cli_exe)
+        .args(["test", "--mutants-dir"])
+        .arg(&mutants_dir)
+        .arg("--path")
+        .arg(dir.path())
+        .status()
+        .expect("command failed")
+        .success());
+}
+
+#[test]
+fn mutants_are_verified_in_sandbox() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let dir = tempfile::tempdir().unwrap();
+    create_test_package(dir.path());
+    let mutants_dir = dir.path().join("mutants");
+    assert!(Command::new(cli_exe)
+        .args([
+            "mutate",
+            "--verify-mutants",
+            "--verification-memory-limit",
+            "4096"
+        ])
+        .args(["--verification-timeout", "60", "--out-mutant-dir"])
+        .arg(&mutants_dir)
+        .arg("--path")
+        .arg(dir.path())
+        .status()
+        .expect("command failed")
+        .success());
+
+    let report: serde_json::Value =
+        serde_json::from_slice(&fs::read(mutants_dir.join("report.json")).unwrap()).unwrap();
+    assert!(!report["mutants"].as_array().unwrap().is_empty());
+    let skipped = report["skipped"].as_array().cloned().unwrap_or_default();
+    assert!(skipped.is_empty());
+}
+
+#[test]
+fn mutants_are_marked_in_source_coverage() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let dir = tempfile::tempdir().unwrap();
+    create_test_package(dir.path());
+    let report = dir.path().join("report.json");
+    let run = |args: &[&str]| {
+        Command::new(cli_exe)
+            .args(args)
+            .arg("--path")
+            .arg(dir.path())
+            .env("NO_COLOR", "1")
+            .output()
+            .expect("command failed")
+    };
+    assert!(run(&["test", "sum_works", "--coverage"]).status.success());
+    assert!(run(&[
+        "mutate-test",
+        "--test-filter",
+        "sum_works",
+        "--report-output",
+        report.to_str().unwrap(),
+    ])
+    .status
+    .success());
+
+    let output = run(&[
+        "coverage",
+        "source",
+        "--module",
+        "Sum",
+        "--mutation-report",
+        report.to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let sum_line = stdout.lines().find(|line| line.contains("x + y")).unwrap();
+    assert!(sum_line.contains("// mutants: "));
+    assert!(sum_line.contains(" killed"));
+    let test_line = stdout
+        .lines()
+        .find(|line| line.contains("fun sum_works"))
+        .unwrap();
+    assert!(!test_line.contains("// mutants: "));
+}
+
+#[test]
+fn profile_options_are_applied() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let dir = tempfile::tempdir().unwrap();
+    create_test_package(dir.path());
+    let manifest = dir.path().join("Move.toml");
+    let mut contents = fs::read_to_string(&manifest).unwrap();
+    contents
+        .push_str("\n[profile.ci]\ndev = true\n\n[profile.ci.build]\noutput-format = \"json\"\n");
+    fs::write(&manifest, contents).unwrap();
+    let run = |args: &[&str]| {
+        Command::new(cli_exe)
+            .args(args)
+            .arg("--path")
+            .arg(dir.path())
+            .output()
+            .expect("command failed")
+    };
+
+    let output = run(&["build", "--profile", "ci"]);
+    assert!(output.status.success());
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results["package"], "Sum");
+
+    // The profile applies to the command given with its alias or abbreviation too.
+    for command in ["b", "bui"] {
+        let output = run(&[command, "--profile", "ci"]);
+        assert!(output.status.success());
+        let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(results["package"], "Sum");
+    }
+
+    // Options given on the command line take precedence.
+    let output = run(&["build", "--profile", "ci", "--output-format", "human"]);
+    assert!(output.status.success());
+    assert!(serde_json::from_slice::<serde_json::Value>(&output.stdout).is_err());
+
+    let output = run(&["build", "--profile", "quick"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Profile 'quick' is not defined"));
+
+    let profiles = dir.path().join("profiles.toml");
+    fs::write(&profiles, "[profile.ci.build]\nprover-timeout = 40\n").unwrap();
+    let output = run(&[
+        "build",
+        "--profile",
+        "ci",
+        "--profile-file",
+        profiles.to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr)
+        .contains("Unknown option 'prover-timeout' of the build command in profile 'ci'"));
+}
+
+#[test]
+fn environment_overrides_profile_options() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let dir = tempfile::tempdir().unwrap();
+    create_test_package(dir.path());
+    let manifest = dir.path().join("Move.toml");
+    let mut contents = fs::read_to_string(&manifest).unwrap();
+    contents.push_str("\n[profile.ci.mutate]\nout-mutant-dir = \"profile_output\"\n");
+    fs::write(&manifest, contents).unwrap();
+    let env_output = dir.path().join("env_output");
+    let run = |args: &[&str]| {
+        Command::new(cli_exe)
+            .args(args)
+            .arg("--path")
+            .arg(dir.path())
+            .env("MOVE_OUTPUT_DIR", &env_output)
+            .output()
+            .expect("command failed")
+    };
+
+    assert!(run(&["mutate", "--profile", "ci"]).status.success());
+    assert!(env_output.join("report.json").exists());
+    assert!(!dir.path().join("profile_output").exists());
+
+    let cli_output = dir.path().join("cli_output");
+    assert!(
+        run(&["mutate", "--out-mutant-dir", cli_output.to_str().unwrap()])
+            .status
+            .success()
+    );
+    assert!(cli_output.join("report.json").exists());
+}
+
+#[test]
+fn spec_tested_template_is_scaffolded() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("demo");
+    let output = Command::new(cli_exe)
+        .args(["new", "demo", "--template", "spec-tested", "--path"])
+        .arg(&path)
+        .output()
+        .expect("command failed");
+    assert!(output.status.success());
+    assert!(path.join(".github/workflows/spec-test.yml").exists());
+    let manifest = fs::read_to_string(path.join("Move.toml")).unwrap();
+    assert!(manifest.contains("demo =  \"0x42\""));
+    assert!(manifest.contains("[spec-test]\n"));
+    assert!(manifest.contains("[profile.ci.spec-test]\n"));
+
+    // The sample module builds against the local standard library.
+    let stdlib = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../move-stdlib");
+    let manifest = manifest
+        .lines()
+        .map(|line| {
+            if line.starts_with("MoveStdlib = ") {
+                format!(
+                    "MoveStdlib = {{ local = {:?} }}",
+                    stdlib.display().to_string()
+                )
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(path.join("Move.toml"), manifest).unwrap();
+    let (success, results) = run_json(&path, &["build"]);
+    assert!(success);
+    assert_eq!(results["modules"], serde_json::json!(["math"]));
+}
+
+#[test]
+fn mutation_operators_are_listed() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let output = Command::new(cli_exe)
+        .args(["mutate", "--list-operators", "--output-format", "json"])
+        .output()
+        .expect("command failed");
+    assert!(output.status.success());
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let operators = results["operators"].as_array().unwrap();
+    let binary = operators
+        .iter()
+        .find(|o| o["name"] == "binary_operator_replacement")
+        .expect("binary operator is listed");
+    assert!(!binary["description"].as_str().unwrap().is_empty());
+    assert!(!binary["example_mutated"].as_str().unwrap().is_empty());
+
+    let output = Command::new(cli_exe)
+        .args(["mutate", "--list-operators"])
+        .output()
+        .expect("command failed");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout.lines().filter(|l| !l.starts_with(' ')).count(),
+        operators.len()
+    );
+    assert!(stdout.contains("    Example: "));
+}
+
+#[test]
+fn disassembled_bytecode_is_diffed() {
+    let cli_exe = env!("CARGO_BIN_EXE_move");
+    let (original, mutant) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+    create_test_package(original.path());
+    create_test_package(mutant.path());
+    let source = fs::read_to_string(mutant.path().join("sources/Sum.move")).unwrap();
+    fs::write(
+        mutant.path().join("sources/Sum.move"),
+        source.replacen("x + y", "x - y", 1),
+    )
+    .unwrap();
+    let build = Command::new(cli_exe)
+        .args(["build", "--path"])
+        .arg(mutant.path())
+        .output()
+        .expect("build failed");
+    assert!(build.status.success());
+
+    let compiled_mutant = mutant.path().join("build/Sum/bytecode_modules/Sum.mv");
+    let output = Command::new(cli_exe)
+        .args(["disassemble", "--name", "Sum", "--path"])
+        .arg(original.path())
+        .arg("--diff")
+        .arg(&compiled_mutant)
+        .output()
+        .expect("disassemble failed");
+    assert!(output.status.success());
+    let diff = String::from_utf8(output.stdout).unwrap();
+    assert!(diff.contains("\n-\t2: Add\n+\t2: Sub\n"), "{diff}");
+
+    let output = Command::new(cli_exe)
+        .args(["disassemble", "--name", "Sum", "--path"])
+        .arg(mutant.path())
+        .arg("--diff")
+        .arg(&compiled_mutant)
+        .output()
+        .expect("disassemble failed");
+    assert_eq!(output.stdout, b"No differences in the bytecode\n");
+}
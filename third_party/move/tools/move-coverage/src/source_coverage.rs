@@ -227,13 +227,26 @@ impl<'a> SourceCoverageBuilder<'a> {
 
 impl SourceCoverage {
     pub fn output_source_coverage<W: Write>(&self, output_writer: &mut W) -> io::Result<()> {
-        for line in self.annotated_lines.iter() {
+        self.output_source_coverage_with_annotations(output_writer, &BTreeMap::new())
+    }
+
+    /// Outputs the source coverage along with the annotations appended to the lines (keyed by the
+    /// 0-based line numbers), e.g. describing the mutants placed in the lines.
+    pub fn output_source_coverage_with_annotations<W: Write>(
+        &self,
+        output_writer: &mut W,
+        annotations: &BTreeMap<usize, ColoredString>,
+    ) -> io::Result<()> {
+        for (line_number, line) in self.annotated_lines.iter().enumerate() {
             for string_segment in line.iter() {
                 match string_segment {
                     StringSegment::Covered(s) => write!(output_writer, "{}", s.green())?,
                     StringSegment::Uncovered(s) => write!(output_writer, "{}", s.bold().red())?,
                 }
             }
+            if let Some(annotation) = annotations.get(&line_number) {
+                write!(output_writer, "  {annotation}")?;
+            }
             writeln!(output_writer)?;
         }
         Ok(())
@@ -0,0 +1,200 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::{MutationReport, Report};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Applies the mutant with the given ID onto the original source file.
+///
+/// The mutant is looked up in the report stored in `report_dir` and its diff is applied to the
+/// original file in place. This allows developers to debug surviving mutants directly in the working tree.
+///
+/// # Arguments
+///
+/// * `report_dir` - The directory containing the mutator report (`report.json`).
+/// * `mutant_id` - The ID of the mutant to apply.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be read, the mutant cannot be found or the diff doesn't
+/// apply cleanly (e.g. the original file was modified or the mutant is already applied).
+///
+/// # Returns
+///
+/// * `anyhow::Result<PathBuf>` - The path to the modified file.
+pub fn apply_mutant(report_dir: &Path, mutant_id: &str) -> anyhow::Result<PathBuf> {
+    let entry = find_mutant(report_dir, mutant_id)?;
+    patch_original_file(&entry, entry.get_diff())
+}
+
+/// Reverts the mutant with the given ID previously applied with `apply_mutant`.
+///
+/// # Arguments
+///
+/// * `report_dir` - The directory containing the mutator report (`report.json`).
+/// * `mutant_id` - The ID of the mutant to revert.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be read, the mutant cannot be found or the reversed diff
+/// doesn't apply cleanly (e.g. the mutant is not applied).
+///
+/// # Returns
+///
+/// * `anyhow::Result<PathBuf>` - The path to the restored file.
+pub fn revert_mutant(report_dir: &Path, mutant_id: &str) -> anyhow::Result<PathBuf> {
+    let entry = find_mutant(report_dir, mutant_id)?;
+    patch_original_file(&entry, &reverse_patch(entry.get_diff()))
+}
+
+/// Loads the report from the given directory and finds the mutant with the given ID.
+fn find_mutant(report_dir: &Path, mutant_id: &str) -> anyhow::Result<MutationReport> {
+    let report = Report::load_from_json_file(&report_dir.join("report.json"))?;
+
+    report
+        .get_mutant_by_id(mutant_id)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Mutant {mutant_id} not found in {report_dir:?}"))
+}
+
+/// Applies the given patch onto the original file of the mutant.
+fn patch_original_file(entry: &MutationReport, patch: &str) -> anyhow::Result<PathBuf> {
+    if patch.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Mutant {} has no diff in the report (was it generated with `--omit-diffs`?)",
+            entry.mutant_id()
+        ));
+    }
+
+    let original_file = entry.original_file_path();
+    let source = fs::read_to_string(original_file)?;
+
+    let patch = diffy::Patch::from_str(patch)
+        .map_err(|e| anyhow::anyhow!("Cannot parse diff of mutant {}: {e}", entry.mutant_id()))?;
+    let patched = diffy::apply(&source, &patch).map_err(|e| {
+        anyhow::anyhow!(
+            "Cannot apply diff of mutant {} to {original_file:?} (was the file modified?): {e}",
+            entry.mutant_id()
+        )
+    })?;
+
+    fs::write(original_file, patched)?;
+
+    info!("Mutant {} patched {:?}", entry.mutant_id(), original_file);
+
+    Ok(original_file.clone())
+}
+
+/// Creates a unified diff which undoes the given unified diff.
+/// Headers are swapped, hunk ranges are exchanged and added lines become removed ones (and vice versa).
+fn reverse_patch(diff: &str) -> String {
+    let lines = diff.split_inclusive('\n').collect::<Vec<_>>();
+    let mut result = String::with_capacity(diff.len());
+    let mut index = 0;
+
+    // Swap the `---` and `+++` headers.
+    if lines.len() >= 2 && lines[0].starts_with("--- ") && lines[1].starts_with("+++ ") {
+        result.push_str(&format!("--- {}", &lines[1][4..]));
+        result.push_str(&format!("+++ {}", &lines[0][4..]));
+        index = 2;
+    }
+
+    for line in &lines[index..] {
+        if line.starts_with("@@ ") {
+            let mut parts = line.splitn(4, ' ').collect::<Vec<_>>();
+            if parts.len() >= 3 {
+                let old = format!("-{}", &parts[2][1..]);
+                let new = format!("+{}", &parts[1][1..]);
+                parts[1] = &old;
+                parts[2] = &new;
+                result.push_str(&parts.join(" "));
+                continue;
+            }
+        }
+
+        if let Some(rest) = line.strip_prefix('-') {
+            result.push('+');
+            result.push_str(rest);
+        } else if let Some(rest) = line.strip_prefix('+') {
+            result.push('-');
+            result.push_str(rest);
+        } else {
+            result.push_str(line);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Mutation, Range};
+    use tempfile::tempdir;
+
+    const ORIGINAL: &str =
+        "module 0x42::Sum {\n    fun sum(x: u64, y: u64): u64 {\n        x + y\n    }\n}\n";
+    const MUTATED: &str =
+        "module 0x42::Sum {\n    fun sum(x: u64, y: u64): u64 {\n        x - y\n    }\n}\n";
+
+    fn prepare_report(dir: &Path) -> (PathBuf, String) {
+        let original_file = dir.join("Sum.move");
+        fs::write(&original_file, ORIGINAL).unwrap();
+
+        let mut entry = MutationReport::new(
+            &dir.join("Sum_mut0.move"),
+            &original_file,
+            "Sum",
+            "sum",
+            MUTATED,
+            ORIGINAL,
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(64, 65),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "-".to_string(),
+        ));
+        let mutant_id = entry.mutant_id().to_owned();
+
+        let mut report = Report::new();
+        report.add_entry(entry);
+        report.save_to_json_file(&dir.join("report.json")).unwrap();
+
+        (original_file, mutant_id)
+    }
+
+    #[test]
+    fn reverse_patch_swaps_changes() {
+        let patch = diffy::create_patch(ORIGINAL, MUTATED).to_string();
+        let reversed = reverse_patch(&patch);
+        let reversed = diffy::Patch::from_str(&reversed).unwrap();
+        assert_eq!(diffy::apply(MUTATED, &reversed).unwrap(), ORIGINAL);
+    }
+
+    #[test]
+    fn apply_and_revert_mutant_restores_original_file() {
+        let dir = tempdir().unwrap();
+        let (original_file, mutant_id) = prepare_report(dir.path());
+
+        apply_mutant(dir.path(), &mutant_id).unwrap();
+        assert_eq!(fs::read_to_string(&original_file).unwrap(), MUTATED);
+
+        // The mutant is already applied, so applying it again must fail.
+        assert!(apply_mutant(dir.path(), &mutant_id).is_err());
+
+        revert_mutant(dir.path(), &mutant_id).unwrap();
+        assert_eq!(fs::read_to_string(&original_file).unwrap(), ORIGINAL);
+    }
+
+    #[test]
+    fn apply_mutant_fails_for_unknown_id() {
+        let dir = tempdir().unwrap();
+        prepare_report(dir.path());
+        assert!(apply_mutant(dir.path(), "unknown").is_err());
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::{MutantStatus, MutationReport, Report};
+use std::{fmt::Write, fs, path::Path};
+
+/// Name of the index file of the patch bundle.
+pub const INDEX_FILE: &str = "index.md";
+
+/// Exports all surviving mutants from the report as a bundle of numbered patch files.
+///
+/// Each patch (`0001-<mutant_id>.patch`, ...) starts with a short description of the mutant followed
+/// by the diff with the original file path in its headers, so it can be replayed with `patch -p0`
+/// (or `git apply`) from the directory the report paths are relative to. The `index.md` file lists
+/// all the patches. Mutants without diffs in the report (e.g. saved with `--omit-diffs`) are skipped.
+///
+/// # Arguments
+///
+/// * `report` - the report with the status of each mutant (e.g. from `move spec-test`).
+/// * `output_dir` - the directory where the bundle is created.
+///
+/// # Errors
+///
+/// Returns an error if the bundle files cannot be written.
+///
+/// # Returns
+///
+/// * `anyhow::Result<usize>` - the number of exported patches.
+pub fn export_patch_bundle(report: &Report, output_dir: &Path) -> anyhow::Result<usize> {
+    fs::create_dir_all(output_dir)?;
+
+    let mut index = String::from(
+        "# Surviving mutants\n\n| # | Patch | File | Function | Mutations |\n|---|---|---|---|---|\n",
+    );
+    let mut count = 0;
+
+    for entry in report.query().status(MutantStatus::Survived) {
+        if entry.get_diff().is_empty() {
+            warn!(
+                "Mutant {} has no diff in the report and is not exported",
+                entry.mutant_id()
+            );
+            continue;
+        }
+
+        count += 1;
+        let patch_name = format!("{count:04}-{}.patch", entry.mutant_id());
+        fs::write(output_dir.join(&patch_name), patch(entry))?;
+
+        let _ = writeln!(
+            index,
+            "| {count} | [{patch_name}]({patch_name}) | {} | {}::{} | {} |",
+            entry.original_file_path().display(),
+            entry.get_module_name(),
+            entry.get_function_name(),
+            describe_mutations(entry).replace('|', "\\|")
+        );
+    }
+
+    fs::write(output_dir.join(INDEX_FILE), index)?;
+    info!(
+        "Exported {count} surviving mutants to {}",
+        output_dir.display()
+    );
+
+    Ok(count)
+}
+
+/// Creates the patch file content for the mutant.
+fn patch(entry: &MutationReport) -> String {
+    let file = entry.original_file_path().to_string_lossy();
+    let mut patch = format!(
+        "Mutant: {}\nFunction: {}::{}\nMutations: {}\n\n--- {file}\n+++ {file}\n",
+        entry.mutant_id(),
+        entry.get_module_name(),
+        entry.get_function_name(),
+        describe_mutations(entry)
+    );
+
+    // Replace the generic `original` / `modified` headers with the file path.
+    let body = entry
+        .get_diff()
+        .split_inclusive('\n')
+        .skip_while(|line| line.starts_with("--- ") || line.starts_with("+++ "))
+        .collect::<String>();
+    patch.push_str(&body);
+    patch
+}
+
+/// Describes all the mutations of the mutant in a single line.
+fn describe_mutations(entry: &MutationReport) -> String {
+    entry
+        .get_mutations()
+        .iter()
+        .map(|m| {
+            format!(
+                "{}: `{}` -> `{}`",
+                m.get_operator_name(),
+                m.get_old_value(),
+                m.get_new_value()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Mutation, Range};
+    use tempfile::tempdir;
+
+    const ORIGINAL: &str =
+        "module 0x42::Sum {\n    fun sum(x: u64, y: u64): u64 {\n        x + y\n    }\n}\n";
+    const MUTATED: &str =
+        "module 0x42::Sum {\n    fun sum(x: u64, y: u64): u64 {\n        x - y\n    }\n}\n";
+
+    fn entry(original_file: &Path, status: MutantStatus) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("Sum_mut0.move"),
+            original_file,
+            "Sum",
+            "sum",
+            MUTATED,
+            ORIGINAL,
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(64, 65),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "-".to_string(),
+        ));
+        entry.set_status(status);
+        entry
+    }
+
+    #[test]
+    fn surviving_mutants_are_exported_as_replayable_patches() {
+        let dir = tempdir().unwrap();
+        let original_file = dir.path().join("Sum.move");
+        fs::write(&original_file, ORIGINAL).unwrap();
+
+        let survivor = entry(&original_file, MutantStatus::Survived);
+        let mut report = Report::new();
+        report.add_entry(entry(&original_file, MutantStatus::Killed));
+        report.add_entry(survivor.clone());
+
+        let bundle = dir.path().join("bundle");
+        assert_eq!(export_patch_bundle(&report, &bundle).unwrap(), 1);
+
+        let patch_name = format!("0001-{}.patch", survivor.mutant_id());
+        let index = fs::read_to_string(bundle.join(INDEX_FILE)).unwrap();
+        assert!(index.contains(&format!("| 1 | [{patch_name}]({patch_name}) |")));
+        assert!(index.contains("binary_operator_replacement: `+` -> `-`"));
+
+        let content = fs::read_to_string(bundle.join(&patch_name)).unwrap();
+        assert!(content.starts_with(&format!("Mutant: {}\n", survivor.mutant_id())));
+        assert!(content.contains(&format!("--- {}\n", original_file.display())));
+
+        let diff = &content[content.find("--- ").unwrap()..];
+        let patch = diffy::Patch::from_str(diff).unwrap();
+        assert_eq!(diffy::apply(ORIGINAL, &patch).unwrap(), MUTATED);
+    }
+}
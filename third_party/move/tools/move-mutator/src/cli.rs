@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::{path::PathBuf, str::FromStr};
 
 pub const DEFAULT_OUTPUT_DIR: &str = "mutants_output";
+/// Environment variable with the output directory, used when `--out-mutant-dir` is not given.
+pub const OUT_MUTANT_DIR_ENV: &str = "MOVE_OUTPUT_DIR";
 
 /// Command line options for mutator
 #[derive(Parser, Debug, Clone, Deserialize, Serialize)]
@@ -18,8 +20,11 @@ pub struct CLIOptions {
     /// Module names to be mutated.
     #[clap(long, value_parser, default_value = "all")]
     pub mutate_modules: ModuleFilter,
+    /// Function names to be mutated. Use `<FUNCTION>` or `<MODULE>::<FUNCTION>`, separated by commas.
+    #[clap(long, value_parser, default_value = "all")]
+    pub mutate_functions: FunctionFilter,
     /// The path where to put the output files.
-    #[clap(long, short, value_parser)]
+    #[clap(long, short, value_parser, env = OUT_MUTANT_DIR_ENV)]
     pub out_mutant_dir: Option<PathBuf>,
     /// Indicates if mutants should be verified and made sure mutants can compile.
     #[clap(long, default_value = "false")]
@@ -33,9 +38,28 @@ pub struct CLIOptions {
     /// Remove averagely given percentage of mutants. See the doc for more details.
     #[clap(long)]
     pub downsampling_ratio_percentage: Option<usize>,
+    /// Seed of the random choices (e.g. downsampling), so runs on the same sources generate identical
+    /// mutants. A random seed is used (and logged) if not given.
+    #[clap(long)]
+    pub seed: Option<u64>,
     /// Optional configuration file. If provided, it will override the default configuration.
     #[clap(long, short, value_parser)]
     pub configuration_file: Option<PathBuf>,
+    /// Resident memory limit (in MiB) of a single mutant verification. If set, verification is run in a separate process.
+    #[clap(long)]
+    pub verification_memory_limit: Option<u64>,
+    /// Timeout (in seconds) of a single mutant verification. If set, verification is run in a separate process.
+    #[clap(long)]
+    pub verification_timeout: Option<u64>,
+    /// Don't store the diffs of the mutants in the report (mutant files are still generated).
+    #[clap(long, default_value = "false")]
+    pub omit_diffs: bool,
+    /// Truncate the diffs of the mutants in the report to the given number of lines.
+    #[clap(long)]
+    pub max_diff_lines: Option<usize>,
+    /// Strip the source code (diffs and replaced values) from the report, keeping only the structural data.
+    #[clap(long, default_value = "false")]
+    pub anonymize: bool,
 }
 
 impl Default for CLIOptions {
@@ -46,12 +70,19 @@ impl Default for CLIOptions {
         Self {
             move_sources: vec![],
             mutate_modules: ModuleFilter::All,
+            mutate_functions: FunctionFilter::All,
             out_mutant_dir: Some(PathBuf::from(DEFAULT_OUTPUT_DIR)),
             verify_mutants: false,
             no_overwrite: false,
             downsample_filter: None,
             downsampling_ratio_percentage: None,
+            seed: None,
             configuration_file: None,
+            verification_memory_limit: None,
+            verification_timeout: None,
+            omit_diffs: false,
+            max_diff_lines: None,
+            anonymize: false,
         }
     }
 }
@@ -64,6 +95,17 @@ pub enum ModuleFilter {
     Selected(Vec<String>),
 }
 
+impl ModuleFilter {
+    /// Checks if the module is selected by the filter.
+    #[must_use]
+    pub fn matches(&self, module: &str) -> bool {
+        match self {
+            ModuleFilter::All => true,
+            ModuleFilter::Selected(modules) => modules.iter().any(|m| m == module),
+        }
+    }
+}
+
 impl FromStr for ModuleFilter {
     type Err = String;
 
@@ -76,3 +118,64 @@ impl FromStr for ModuleFilter {
         }
     }
 }
+
+/// Filter allowing to select functions to be mutated. Functions are given either by name (matching
+/// functions of that name in all modules) or qualified with the module name (`<MODULE>::<FUNCTION>`).
+#[derive(Default, Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub enum FunctionFilter {
+    #[default]
+    All,
+    Selected(Vec<String>),
+}
+
+impl FunctionFilter {
+    /// Checks if the function of the given module is selected by the filter.
+    #[must_use]
+    pub fn matches(&self, module: &str, function: &str) -> bool {
+        match self {
+            FunctionFilter::All => true,
+            FunctionFilter::Selected(functions) => {
+                functions.iter().any(|f| match f.split_once("::") {
+                    Some((m, f)) => m == module && f == function,
+                    None => f == function,
+                })
+            },
+        }
+    }
+}
+
+impl FromStr for FunctionFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(FunctionFilter::All),
+            _ => Ok(FunctionFilter::Selected(
+                s.split(',').map(String::from).collect(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_filter_matches_plain_and_qualified_names() {
+        let filter = FunctionFilter::from_str("sum,Math::mul").unwrap();
+        assert!(filter.matches("Sum", "sum"));
+        assert!(filter.matches("Other", "sum"));
+        assert!(filter.matches("Math", "mul"));
+        assert!(!filter.matches("Other", "mul"));
+        assert!(FunctionFilter::All.matches("Other", "mul"));
+    }
+
+    #[test]
+    fn module_filter_matches_selected_modules() {
+        let filter = ModuleFilter::from_str("Sum,Math").unwrap();
+        assert!(filter.matches("Math"));
+        assert!(!filter.matches("Other"));
+        assert!(ModuleFilter::All.matches("Other"));
+    }
+}
@@ -2,7 +2,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::configuration::Configuration;
+use crate::{configuration::Configuration, sandbox::SandboxTask};
 use either::Either;
 use itertools::Itertools;
 use move_command_line_common::{address::NumericalAddress, parser::NumberFormat};
@@ -16,7 +16,12 @@ use move_package::{
     BuildConfig,
 };
 use move_symbol_pool::Symbol;
-use std::{collections::BTreeMap, fs, io, path::Path};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 /// Generate the AST from the Move sources.
 ///
@@ -68,7 +73,7 @@ pub fn generate_ast(
     let options = if is_package {
         prepare_compiler_for_package(config, package_path)?
     } else {
-        prepare_compiler_for_files(config, source_files.as_slice())
+        prepare_compiler_for_files(config, source_files.as_slice())?
     };
 
     let env = run_checker(options.clone())?;
@@ -209,6 +214,9 @@ fn prepare_compiler_for_package(
 
 /// Prepare the compiler for the given source files.
 ///
+/// Named addresses are resolved from the manifests of the packages the source files belong to (if any).
+/// Addresses passed explicitly in the build configuration take precedence over the manifest ones.
+///
 /// # Arguments
 ///
 /// * `config` - the build configuration.
@@ -224,24 +232,34 @@ fn prepare_compiler_for_package(
 fn prepare_compiler_for_files(
     config: &BuildConfig,
     source_files: &[&str],
-) -> move_compiler_v2::Options {
+) -> Result<move_compiler_v2::Options, anyhow::Error> {
     debug!("Source files and folders: {source_files:?}");
 
-    let named_addr_map = config
-        .additional_named_addresses
-        .clone()
-        .into_iter()
-        .map(|(name, addr)| {
+    let mut named_addr_map = BTreeMap::new();
+    for source_file in source_files {
+        let Ok(root) = Path::new(source_file)
+            .canonicalize()
+            .map_err(anyhow::Error::from)
+            .and_then(|path| SourcePackageLayout::try_find_root(&path))
+        else {
+            continue;
+        };
+
+        named_addr_map.extend(named_addresses_from_manifest(&root, config.dev_mode)?);
+    }
+
+    named_addr_map.extend(config.additional_named_addresses.clone().into_iter().map(
+        |(name, addr)| {
             (
                 name,
                 NumericalAddress::new(addr.into_bytes(), NumberFormat::Decimal),
             )
-        })
-        .collect::<BTreeMap<_, _>>();
+        },
+    ));
 
     let known_attributes = config.compiler_config.known_attributes.clone();
 
-    move_compiler_v2::Options {
+    Ok(move_compiler_v2::Options {
         sources: source_files
             .iter()
             .map(std::string::ToString::to_string)
@@ -254,7 +272,53 @@ fn prepare_compiler_for_files(
         skip_attribute_checks: config.compiler_config.skip_attribute_checks,
         known_attributes: known_attributes.clone(),
         ..Default::default()
-    }
+    })
+}
+
+/// Reads the named addresses declared in the manifest of the package placed at `root`.
+///
+/// Unassigned addresses are skipped. In dev mode, the `[dev-addresses]` section is taken into account
+/// as well and overrides the regular assignments.
+///
+/// # Arguments
+///
+/// * `root` - the path to the package root.
+/// * `dev_mode` - whether the dev addresses should be used.
+///
+/// # Errors
+///
+/// * If the manifest cannot be read or parsed, the appropriate error is returned using anyhow.
+///
+/// # Returns
+///
+/// * `Result<BTreeMap<String, NumericalAddress>, anyhow::Error>` - the named addresses if successful, or an error.
+fn named_addresses_from_manifest(
+    root: &Path,
+    dev_mode: bool,
+) -> Result<BTreeMap<String, NumericalAddress>, anyhow::Error> {
+    let manifest_string = fs::read_to_string(root.join(SourcePackageLayout::Manifest.path()))?;
+    let manifest = manifest_parser::parse_move_manifest_string(manifest_string)?;
+    let manifest = manifest_parser::parse_source_manifest(manifest)?;
+
+    let addresses = manifest
+        .addresses
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(name, addr)| addr.map(|addr| (name, addr)));
+    let dev_addresses = manifest
+        .dev_address_assignments
+        .filter(|_| dev_mode)
+        .unwrap_or_default();
+
+    Ok(addresses
+        .chain(dev_addresses)
+        .map(|(name, addr)| {
+            (
+                name.as_str().to_owned(),
+                NumericalAddress::new(addr.into_bytes(), NumberFormat::Hex),
+            )
+        })
+        .collect())
 }
 
 /// Verify the mutant.
@@ -336,6 +400,27 @@ pub fn verify_mutant(
     Ok(())
 }
 
+/// Verification of the mutant run in the sandbox process.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyMutantTask {
+    /// The build configuration.
+    pub config: BuildConfig,
+    /// The mutated source code.
+    pub mutated_source: String,
+    /// The path to the original file.
+    pub original_file: PathBuf,
+}
+
+impl SandboxTask for VerifyMutantTask {
+    type Output = ();
+
+    const NAME: &'static str = "verify-mutant";
+
+    fn run(self) -> anyhow::Result<()> {
+        verify_mutant(&self.config, &self.mutated_source, &self.original_file)
+    }
+}
+
 /// Rewrite the manifest file to use absolute paths.
 ///
 /// # Arguments
@@ -433,6 +518,34 @@ mod tests {
         assert!(dst_dir.join("file.txt").exists());
     }
 
+    #[test]
+    fn named_addresses_from_manifest_resolves_addresses() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("Move.toml"),
+            r#"[package]
+name = "test"
+version = "0.0.0"
+
+[addresses]
+A = "0x1"
+B = "_"
+
+[dev-addresses]
+B = "0x2"
+"#,
+        )
+        .unwrap();
+
+        let addresses = named_addresses_from_manifest(temp_dir.path(), false).unwrap();
+        assert_eq!(addresses.len(), 1);
+        assert_eq!(addresses["A"].to_string(), "0x1");
+
+        let addresses = named_addresses_from_manifest(temp_dir.path(), true).unwrap();
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses["B"].to_string(), "0x2");
+    }
+
     #[test]
     fn copy_dir_all_errors_if_source_does_not_exist() {
         let temp_dir = tempdir().unwrap();
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli::CLIOptions;
+use move_model::ast::Operation;
 use serde::{Deserialize, Serialize};
 use std::{
     path::{Path, PathBuf},
@@ -41,6 +42,14 @@ impl Configuration {
         }
     }
 
+    /// Applies the environment variable overrides to the options read from the configuration file,
+    /// as the environment takes precedence over the file.
+    pub fn apply_env_overrides(&mut self) {
+        if let Some(dir) = std::env::var_os(crate::cli::OUT_MUTANT_DIR_ENV) {
+            self.project.out_mutant_dir = Some(PathBuf::from(dir));
+        }
+    }
+
     /// Recognizes the file type based on the file extension.
     /// Currently supported file types are JSON and TOML.
     ///
@@ -122,6 +131,111 @@ pub struct MutationConfig {
     pub operators: Vec<String>,
     /// Names of the mutation categories to be used.
     pub categories: Vec<String>,
+    /// Settings of the binary operator replacement.
+    #[serde(default)]
+    pub binary_operator_replacement: Option<BinaryOperatorConfig>,
+    /// Settings of the literal replacement.
+    #[serde(default)]
+    pub literal_replacement: Option<LiteralConfig>,
+    /// Maximum number of mutants generated for a single mutation site.
+    #[serde(default)]
+    pub max_mutants_per_site: Option<usize>,
+}
+
+impl MutationConfig {
+    /// Checks if the binary operator replacement should be applied to the given operation.
+    #[must_use]
+    pub fn is_binary_operation_enabled(&self, operation: &Operation) -> bool {
+        let Some(conf) = &self.binary_operator_replacement else {
+            return true;
+        };
+
+        conf.groups.is_empty()
+            || BinaryOperatorGroup::of(operation).is_some_and(|g| conf.groups.contains(&g))
+    }
+
+    /// Returns the kinds of literal replacements to be used.
+    #[must_use]
+    pub fn literal_replacements(&self) -> Vec<LiteralReplacement> {
+        match &self.literal_replacement {
+            Some(conf) if !conf.replacements.is_empty() => conf.replacements.clone(),
+            _ => LiteralReplacement::all(),
+        }
+    }
+}
+
+/// Settings of the binary operator replacement.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BinaryOperatorConfig {
+    /// Groups of the operators to be mutated. If empty, all groups are mutated.
+    #[serde(default)]
+    pub groups: Vec<BinaryOperatorGroup>,
+}
+
+/// Group of the binary operators which are exchangeable with each other.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BinaryOperatorGroup {
+    /// `+`, `-`, `*`, `/`, `%`
+    Arithmetic,
+    /// `|`, `&`, `^`
+    Bitwise,
+    /// `<<`, `>>`
+    Shift,
+    /// `||`, `&&`
+    Logical,
+    /// `==`, `!=`, `<`, `>`, `<=`, `>=`
+    Comparison,
+}
+
+impl BinaryOperatorGroup {
+    /// Returns the group of the given operation or `None` if it's not a binary operation.
+    #[must_use]
+    pub fn of(operation: &Operation) -> Option<Self> {
+        match operation {
+            Operation::Add | Operation::Sub | Operation::Mul | Operation::Div | Operation::Mod => {
+                Some(Self::Arithmetic)
+            },
+            Operation::BitOr | Operation::BitAnd | Operation::Xor => Some(Self::Bitwise),
+            Operation::Shl | Operation::Shr => Some(Self::Shift),
+            Operation::Or | Operation::And => Some(Self::Logical),
+            Operation::Eq
+            | Operation::Neq
+            | Operation::Lt
+            | Operation::Gt
+            | Operation::Le
+            | Operation::Ge => Some(Self::Comparison),
+            _ => None,
+        }
+    }
+}
+
+/// Settings of the literal replacement.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LiteralConfig {
+    /// Kinds of the replacements to be used. If empty, all kinds are used.
+    #[serde(default)]
+    pub replacements: Vec<LiteralReplacement>,
+}
+
+/// Kind of the value used to replace a literal.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum LiteralReplacement {
+    /// Minimum value of the type (`0`, `false`, `0x0`).
+    Min,
+    /// Maximum value of the type.
+    Max,
+    /// Literal value increased by one.
+    Increment,
+    /// Literal value decreased by one.
+    Decrement,
+}
+
+impl LiteralReplacement {
+    /// Returns all kinds of the literal replacements.
+    #[must_use]
+    pub fn all() -> Vec<Self> {
+        vec![Self::Min, Self::Max, Self::Increment, Self::Decrement]
+    }
 }
 
 /// Configuration for the individual file.
@@ -180,18 +294,17 @@ mod tests {
         fs::write("test.toml", toml_content).unwrap();
         let config = Configuration::from_toml_file(Path::new("test.toml")).unwrap();
         fs::remove_file("test.toml").unwrap();
-        assert_eq!(
-            config.project.move_sources,
-            vec![Path::new("/path/to/move/source")]
-        );
+        assert_eq!(config.project.move_sources, vec![Path::new(
+            "/path/to/move/source"
+        )]);
         assert_eq!(
             config.project.mutate_modules,
             ModuleFilter::Selected(vec!["module1".to_owned(), "module2".to_owned()])
         );
-        assert_eq!(
-            config.mutation.unwrap().operators,
-            vec!["operator1", "operator2"]
-        );
+        assert_eq!(config.mutation.unwrap().operators, vec![
+            "operator1",
+            "operator2"
+        ]);
         assert_eq!(config.individual.len(), 1);
         assert_eq!(config.individual[0].file, PathBuf::from("/path/to/file"));
         assert!(config.individual[0].verify_mutants);
@@ -201,6 +314,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn configuration_with_operator_settings_loads_correctly() {
+        let toml_content = r#"
+            [project]
+            move_sources = []
+            [mutation]
+            operators = []
+            categories = []
+            max_mutants_per_site = 2
+            [mutation.binary_operator_replacement]
+            groups = ["Arithmetic"]
+            [mutation.literal_replacement]
+            replacements = ["Min", "Max"]
+            [[individual]]
+            file = "/path/to/file"
+            verify_mutants = true
+            include_functions = "All"
+        "#;
+        fs::write("test_operator_settings.toml", toml_content).unwrap();
+        let config =
+            Configuration::from_toml_file(Path::new("test_operator_settings.toml")).unwrap();
+        fs::remove_file("test_operator_settings.toml").unwrap();
+
+        let mutation = config.mutation.unwrap();
+        assert_eq!(mutation.max_mutants_per_site, Some(2));
+        assert!(mutation.is_binary_operation_enabled(&Operation::Add));
+        assert!(!mutation.is_binary_operation_enabled(&Operation::Eq));
+        assert_eq!(mutation.literal_replacements(), vec![
+            LiteralReplacement::Min,
+            LiteralReplacement::Max
+        ]);
+    }
+
+    #[test]
+    fn operator_settings_default_to_all() {
+        let mutation = MutationConfig {
+            operators: vec![],
+            categories: vec![],
+            binary_operator_replacement: None,
+            literal_replacement: None,
+            max_mutants_per_site: None,
+        };
+        assert!(mutation.is_binary_operation_enabled(&Operation::Shl));
+        assert_eq!(mutation.literal_replacements(), LiteralReplacement::all());
+    }
+
     #[test]
     fn configuration_from_non_existent_toml_file_fails() {
         let result = Configuration::from_toml_file(Path::new("non_existent.toml"));
@@ -253,10 +412,9 @@ mod tests {
         fs::write("test.json", json_content).unwrap();
         let config = Configuration::from_json_file(Path::new("test.json")).unwrap();
         fs::remove_file("test.json").unwrap();
-        assert_eq!(
-            config.project.move_sources,
-            vec![Path::new("/path/to/move/source")]
-        );
+        assert_eq!(config.project.move_sources, vec![Path::new(
+            "/path/to/move/source"
+        )]);
         assert_eq!(config.project.mutate_modules, ModuleFilter::All);
         assert_eq!(
             config.project.out_mutant_dir,
@@ -270,10 +428,10 @@ mod tests {
             Path::new("/path/to/configuration")
         );
         assert_eq!(config.project_path.unwrap(), Path::new("/path/to/project"));
-        assert_eq!(
-            config.mutation.unwrap().operators,
-            vec!["operator1", "operator2"]
-        );
+        assert_eq!(config.mutation.unwrap().operators, vec![
+            "operator1",
+            "operator2"
+        ]);
     }
 
     #[test]
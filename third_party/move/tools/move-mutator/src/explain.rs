@@ -0,0 +1,123 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{operator::OperatorInfo, operators::OPERATORS};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Detailed description of a mutation operator, meant to help with the triage of the surviving
+/// mutants.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OperatorExplanation {
+    /// The name of the operator, as used in the report.
+    pub name: String,
+    /// Human readable description of the operator.
+    pub description: String,
+    /// The exact replacements performed by the operator.
+    pub replacements: Vec<String>,
+    /// Typical specification clauses killing the mutants of the operator.
+    pub killing_specs: Vec<String>,
+    /// Function along with its specification killing the mutants of the operator.
+    pub worked_example: String,
+    /// Explanation of the worked example.
+    pub worked_example_explanation: String,
+}
+
+impl OperatorExplanation {
+    /// Finds the explanation of the operator with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the operator, as used in the report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing the available operators if there is no operator with the name.
+    ///
+    /// # Returns
+    ///
+    /// * `anyhow::Result<OperatorExplanation>` - The explanation of the operator.
+    pub fn find(name: &str) -> anyhow::Result<Self> {
+        OPERATORS
+            .iter()
+            .find(|info| info.name == name)
+            .map(Self::from)
+            .ok_or_else(|| {
+                let names = OPERATORS.iter().map(|info| info.name).collect::<Vec<_>>();
+                anyhow::anyhow!(
+                    "Unknown mutation operator '{name}', available operators: {}",
+                    names.join(", ")
+                )
+            })
+    }
+
+    /// Renders the explanation in a human readable way.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut output = format!("{}\n    {}\n", self.name, self.description);
+        for (title, items) in [
+            ("Replacements", &self.replacements),
+            (
+                "Specification clauses killing the mutants",
+                &self.killing_specs,
+            ),
+        ] {
+            let _ = writeln!(output, "\n{title}:");
+            for item in items {
+                let _ = writeln!(output, "    - {item}");
+            }
+        }
+        let _ = writeln!(output, "\nExample:");
+        for line in self.worked_example.lines() {
+            let _ = writeln!(output, "    {line}");
+        }
+        let _ = writeln!(output, "\n{}", self.worked_example_explanation);
+        output
+    }
+}
+
+impl From<&OperatorInfo> for OperatorExplanation {
+    fn from(info: &OperatorInfo) -> Self {
+        let strings = |items: &[&str]| items.iter().map(|item| (*item).to_owned()).collect();
+        Self {
+            name: info.name.to_owned(),
+            description: info.description.to_owned(),
+            replacements: strings(info.replacements),
+            killing_specs: strings(info.killing_specs),
+            worked_example: info.worked_example.to_owned(),
+            worked_example_explanation: info.worked_example_explanation.to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_operators_are_explained() {
+        for info in OPERATORS {
+            let explanation = OperatorExplanation::find(info.name).unwrap();
+            assert!(!explanation.replacements.is_empty());
+            assert!(!explanation.killing_specs.is_empty());
+            assert!(explanation.worked_example.contains("spec "));
+        }
+    }
+
+    #[test]
+    fn explanation_is_rendered() {
+        let rendered = OperatorExplanation::find("unary_operator_replacement")
+            .unwrap()
+            .render();
+        assert!(
+            rendered.starts_with("unary_operator_replacement\n    Removes the unary operator.\n")
+        );
+        assert!(rendered.contains("\nReplacements:\n    - `!e` with `e`\n"));
+        assert!(rendered.contains("\nExample:\n    fun is_not_zero(x: u64): bool { !(x == 0) }\n"));
+
+        let error = OperatorExplanation::find("nope").unwrap_err().to_string();
+        assert!(error.starts_with("Unknown mutation operator 'nope', available operators: "));
+        assert!(error.contains("literal_replacement"));
+    }
+}
@@ -6,26 +6,36 @@ extern crate pretty_env_logger;
 #[macro_use]
 extern crate log;
 
+pub mod apply;
+pub mod bundle;
 pub mod cli;
 pub mod compiler;
 
 mod mutate;
 
 pub mod configuration;
+pub mod explain;
 mod mutant;
 mod operator;
 mod operators;
 mod output;
 pub mod report;
+pub mod sandbox;
+pub mod status;
 
 use crate::{
-    compiler::{generate_ast, verify_mutant},
+    compiler::{generate_ast, verify_mutant, VerifyMutantTask},
     configuration::Configuration,
-    report::Report,
+    report::{Report, ReportDetail, ReportStreamWriter, SkipReason},
+    sandbox::{SandboxLimits, SandboxResult, SandboxTask},
 };
 use move_package::BuildConfig;
-use rand::{seq::SliceRandom, thread_rng};
-use std::{fs, path::Path};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 /// Runs the Move mutator tool.
 /// Entry point for the Move mutator tool both for the CLI and the Rust API.
@@ -50,6 +60,65 @@ pub fn run_move_mutator(
     options: cli::CLIOptions,
     config: &BuildConfig,
     package_path: &Path,
+) -> anyhow::Result<()> {
+    run_mutator(options, config, package_path, None)
+}
+
+/// Regenerates the mutants of the given Move files of the package only.
+///
+/// The mutants of the other files are kept in the output directory and in the report from the
+/// previous run, so it's much faster than a full run when only a few files have changed (e.g. in
+/// the watch mode). Files which were removed from the package just have their mutants removed.
+/// If there is no report from the previous run, all the mutants are generated.
+///
+/// # Arguments
+///
+/// * `options` - Command line options passed to the Move mutator tool.
+/// * `config` - The build configuration for the Move package.
+/// * `package_path` - The path to the Move package.
+/// * `files` - The paths to the changed Move files.
+///
+/// # Errors
+/// Any error that occurs during the mutation process will be returned as an `anyhow::Error` with a description of the error.
+///
+/// # Returns
+///
+/// * `anyhow::Result<()>` - Returns `Ok(())` if the mutation process completes successfully, or an error if any error occurs.
+pub fn run_move_mutator_for_files(
+    options: cli::CLIOptions,
+    config: &BuildConfig,
+    package_path: &Path,
+    files: &[PathBuf],
+) -> anyhow::Result<()> {
+    run_mutator(options, config, package_path, Some(files))
+}
+
+/// Returns the descriptions of all the available mutation operators along with their example
+/// transformations, as embedded in the report.
+#[must_use]
+pub fn operator_catalog() -> Vec<report::OperatorCatalogEntry> {
+    operators::catalog()
+}
+
+/// Runs the mutator task the current process has been started to run in the sandbox (see
+/// `sandbox::requested_task`).
+///
+/// # Errors
+///
+/// Returns an error if the task is unknown or cannot be served.
+pub fn run_sandbox_task(name: &str) -> anyhow::Result<()> {
+    match name {
+        VerifyMutantTask::NAME => sandbox::serve::<VerifyMutantTask>(),
+        _ => Err(anyhow::anyhow!("Unknown sandbox task '{name}'")),
+    }
+}
+
+/// Runs the mutator, generating only the mutants of the given files (if any).
+fn run_mutator(
+    options: cli::CLIOptions,
+    config: &BuildConfig,
+    package_path: &Path,
+    only_files: Option<&[PathBuf]>,
 ) -> anyhow::Result<()> {
     // We need to initialize logger using try_init() as it might be already initialized in some other tool
     // (e.g. spec-test). If we use init() instead, we will get an abort.
@@ -61,7 +130,11 @@ pub fn run_move_mutator(
 
     // Load configuration from file or create a new one.
     let mutator_configuration = match options.configuration_file {
-        Some(path) => Configuration::from_file(path.as_path())?,
+        Some(path) => {
+            let mut configuration = Configuration::from_file(path.as_path())?;
+            configuration.apply_env_overrides();
+            configuration
+        },
         None => Configuration::new(options, Some(package_path.to_owned())),
     };
 
@@ -79,9 +152,62 @@ pub fn run_move_mutator(
 
     trace!("Generated AST.");
 
-    let mutants = mutate::mutate(&env, &mutator_configuration)?;
-    let output_dir = output::setup_output_dir(&mutator_configuration)?;
-    let mut report: Report = Report::new();
+    let sandbox_limits = SandboxLimits {
+        memory_limit_mb: mutator_configuration.project.verification_memory_limit,
+        group_memory_limit_mb: None,
+        timeout: mutator_configuration
+            .project
+            .verification_timeout
+            .map(Duration::from_secs),
+    };
+
+    let report_detail = ReportDetail {
+        omit_diffs: mutator_configuration.project.omit_diffs,
+        max_diff_lines: mutator_configuration.project.max_diff_lines,
+        survivors_only: false,
+        anonymize: mutator_configuration.project.anonymize,
+    };
+
+    let mut mutants = mutate::mutate(&env, &mutator_configuration)?;
+    let previous = match only_files {
+        Some(files) => output::reuse_output_dir(&mutator_configuration, files)?,
+        None => None,
+    };
+    let (output_dir, mut report) = match (only_files, previous) {
+        (Some(files), Some(previous)) => {
+            let files = files
+                .iter()
+                .filter_map(|file| file.canonicalize().ok())
+                .collect::<Vec<_>>();
+            mutants.retain(|m| {
+                Path::new(env.get_file(m.get_file_id()))
+                    .canonicalize()
+                    .is_ok_and(|file| files.contains(&file))
+            });
+            previous
+        },
+        _ => (
+            output::setup_output_dir(&mutator_configuration)?,
+            Report::new(),
+        ),
+    };
+    report.set_operator_catalog(operators::catalog());
+    report.set_package_root(
+        mutator_configuration
+            .project_path
+            .as_deref()
+            .unwrap_or(package_path),
+    );
+    // Entries are streamed as they are generated, so an interrupted run still leaves a partial report.
+    let mut stream = ReportStreamWriter::create(&output_dir.join(Path::new("report.jsonl")))?;
+
+    // All the random choices are made with a single seeded generator, so the run can be reproduced.
+    let seed = mutator_configuration
+        .project
+        .seed
+        .unwrap_or_else(rand::random);
+    info!("Using seed {seed}");
+    let mut rng = StdRng::seed_from_u64(seed);
 
     for mutant in &mutants {
         let file_id = &mutant.get_file_id();
@@ -93,6 +219,15 @@ pub fn run_move_mutator(
 
         let mut mutated_sources = mutant.apply(source);
 
+        // Limit the number of mutants generated for a single site if requested.
+        if let Some(max) = mutator_configuration
+            .mutation
+            .as_ref()
+            .and_then(|m| m.max_mutants_per_site)
+        {
+            mutated_sources.truncate(max);
+        }
+
         // If the downsample ratio is set, we need to downsample the mutants.
         //TODO: currently we are downsampling the mutants after they are generated. This is not
         // ideal as we are generating all mutants and then removing some of them.
@@ -106,7 +241,6 @@ pub fn run_move_mutator(
             );
 
             // Delete randomly elements from the vector.
-            let mut rng = thread_rng();
             let chosen_elements: Vec<_> = mutated_sources
                 .choose_multiple(&mut rng, no_of_mutants_to_keep)
                 .cloned()
@@ -115,7 +249,16 @@ pub fn run_move_mutator(
             mutated_sources = chosen_elements;
         }
 
-        for mutated in mutated_sources {
+        let mod_name = if let Some(name) = mutant.get_module_name() {
+            name
+        } else {
+            "script".to_owned() // if there is no module name, it is a script
+        };
+        let function_name = mutant
+            .get_function_name()
+            .map_or_else(String::new, |f| f.to_string());
+
+        for mut mutated in mutated_sources {
             if let Some(mutation_conf) = &mutator_configuration.mutation {
                 if !mutation_conf.operators.is_empty()
                     && !mutation_conf
@@ -126,13 +269,57 @@ pub fn run_move_mutator(
                 }
             }
 
+            // Store line and column numbers, so report consumers don't need to read the sources.
+            mutated.mutation.locate(source);
+
+            // Mutants placed in the unreachable code would always survive, so just report them.
+            if mutant.is_unreachable() {
+                info!("{mutant} is placed in the unreachable code and will not be generated");
+                let mut skipped = report::SkippedMutation::new(
+                    path,
+                    mod_name.as_str(),
+                    function_name.as_str(),
+                    mutated.mutation,
+                    SkipReason::Unreachable,
+                );
+                skipped.reduce_detail(&report_detail);
+                stream.write_skipped(&skipped)?;
+                report.add_skipped_entry(skipped);
+                continue;
+            }
+
             if mutator_configuration.project.verify_mutants {
-                let res = verify_mutant(config, &mutated.mutated_source, path);
+                let res = if sandbox_limits.is_enabled() {
+                    sandbox::run_sandboxed(&sandbox_limits, &VerifyMutantTask {
+                        config: config.clone(),
+                        mutated_source: mutated.mutated_source.clone(),
+                        original_file: path.to_owned(),
+                    })
+                } else {
+                    SandboxResult::Finished(verify_mutant(config, &mutated.mutated_source, path))
+                };
 
-                // In case the mutant is not a valid Move file, skip the mutant (do not save it).
-                if res.is_err() {
-                    warn!("Mutant {mutant} is not valid and will not be generated. Error: {res:?}");
-                    continue;
+                match res {
+                    SandboxResult::Finished(Ok(())) => {},
+                    // In case the mutant is not a valid Move file, skip the mutant (do not save it).
+                    SandboxResult::Finished(res) => {
+                        warn!("Mutant {mutant} is not valid and will not be generated. Error: {res:?}");
+                        continue;
+                    },
+                    SandboxResult::Failed(failure) => {
+                        warn!("Verification of mutant {mutant} failed in the sandbox: {failure}");
+                        let mut skipped = report::SkippedMutation::new(
+                            path,
+                            mod_name.as_str(),
+                            function_name.as_str(),
+                            mutated.mutation,
+                            SkipReason::Sandbox(failure),
+                        );
+                        skipped.reduce_detail(&report_detail);
+                        stream.write_skipped(&skipped)?;
+                        report.add_skipped_entry(skipped);
+                        continue;
+                    },
                 }
             }
 
@@ -142,29 +329,27 @@ pub fn run_move_mutator(
                 continue;
             };
 
-            fs::write(&mutant_path, &mutated.mutated_source)?;
-
-            info!("{} written to {}", mutant, mutant_path.display());
-
-            let mod_name = if let Some(name) = mutant.get_module_name() {
-                name
-            } else {
-                "script".to_owned() // if there is no module name, it is a script
-            };
-
             let mut entry = report::MutationReport::new(
                 mutant_path.as_path(),
                 path,
                 mod_name.as_str(),
-                mutant
-                    .get_function_name()
-                    .map_or_else(String::new, |f| f.to_string())
-                    .as_str(),
+                function_name.as_str(),
                 &mutated.mutated_source,
                 source,
             );
 
             entry.add_modification(mutated.mutation);
+
+            // Prepend the header, so the mutant file describes itself.
+            fs::write(
+                &mutant_path,
+                entry.header(source) + mutated.mutated_source.as_str(),
+            )?;
+
+            info!("{} written to {}", mutant, mutant_path.display());
+
+            entry.reduce_detail(&report_detail);
+            stream.write_entry(&entry)?;
             report.add_entry(entry);
         }
     }
@@ -5,8 +5,7 @@
 #![forbid(unsafe_code)]
 
 use clap::Parser;
-use move_mutator::cli::CLIOptions;
-use move_mutator::run_move_mutator;
+use move_mutator::{cli::CLIOptions, run_move_mutator, sandbox};
 use move_package::BuildConfig;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -26,6 +25,11 @@ pub struct Opts {
 }
 
 fn main() -> anyhow::Result<()> {
+    // The binary is started again with the hidden subcommand to run the sandboxed tasks.
+    if let Some(task) = sandbox::requested_task() {
+        return move_mutator::run_sandbox_task(&task);
+    }
+
     let opts = Opts::parse();
     let package_path = opts.package_path.unwrap_or(PathBuf::from("."));
 
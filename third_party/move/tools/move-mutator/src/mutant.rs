@@ -13,6 +13,7 @@ pub struct Mutant {
     operator: MutationOp,
     module_name: Option<String>,
     function_name: Option<String>,
+    unreachable: bool,
 }
 
 impl Mutant {
@@ -24,6 +25,7 @@ impl Mutant {
             operator,
             module_name: None,
             function_name: None,
+            unreachable: false,
         }
     }
 
@@ -58,6 +60,16 @@ impl Mutant {
     pub fn set_function_name(&mut self, function_name: String) {
         self.function_name = Some(function_name);
     }
+
+    /// Returns true if the mutant is placed in the unreachable code.
+    pub fn is_unreachable(&self) -> bool {
+        self.unreachable
+    }
+
+    /// Marks the mutant as placed in the unreachable code.
+    pub fn set_unreachable(&mut self) {
+        self.unreachable = true;
+    }
 }
 
 impl fmt::Display for Mutant {
@@ -4,7 +4,7 @@
 
 use crate::{
     cli,
-    configuration::{Configuration, IncludeFunctions},
+    configuration::{Configuration, IncludeFunctions, LiteralReplacement, MutationConfig},
     mutant::Mutant,
     operator::MutationOp,
     operators::{
@@ -13,8 +13,8 @@ use crate::{
     },
 };
 use move_model::{
-    ast::{Exp, ExpData, Operation},
-    model::{FunctionEnv, GlobalEnv, ModuleEnv},
+    ast::{Exp, ExpData, Operation, Value},
+    model::{FunctionEnv, GlobalEnv, Loc, ModuleEnv},
 };
 use move_package::source_package::layout::SourcePackageLayout;
 use std::path::Path;
@@ -86,6 +86,7 @@ fn traverse_module_with_check(
 
 /// Traverses a single module and returns a list of mutants.
 /// Checks all the functions and constants defined in the module.
+/// Scripts are represented as modules too, so they are traversed the same way.
 #[allow(clippy::unnecessary_to_owned)]
 fn traverse_module(module: &ModuleEnv<'_>, conf: &Configuration) -> anyhow::Result<Vec<Mutant>> {
     let module_name = module.get_name().display(module.env);
@@ -97,10 +98,13 @@ fn traverse_module(module: &ModuleEnv<'_>, conf: &Configuration) -> anyhow::Resu
         .collect::<Result<Vec<_>, _>>()?
         .concat();
 
-    // Set the module name for all the mutants.
-    mutants
-        .iter_mut()
-        .for_each(|m| m.set_module_name(module_name.to_string()));
+    // Set the module name for all the mutants. Script pseudo-modules have no real name, so mutants
+    // found in scripts are left without module name.
+    if !module.is_script_module() {
+        mutants
+            .iter_mut()
+            .for_each(|m| m.set_module_name(module_name.to_string()));
+    }
 
     trace!(
         "Found {} possible mutations in module {}",
@@ -136,6 +140,18 @@ fn traverse_function(
     let function_name = function.get_name_str();
     let filename = function.module_env.get_source_path();
 
+    // Check if function is included in the project configuration.
+    let module = &function.module_env;
+    let module_name = module.symbol_pool().string(module.get_name().name());
+    if !conf
+        .project
+        .mutate_functions
+        .matches(&module_name, &function_name)
+    {
+        trace!("Skipping function {module_name}::{function_name}");
+        return Ok(vec![]);
+    }
+
     // Check if function is included in individual configuration.
     if let Some(ind) = conf.get_file_configuration(Path::new(filename)) {
         if let IncludeFunctions::Selected(funcs) = &ind.include_functions {
@@ -149,6 +165,8 @@ fn traverse_function(
     trace!("Traversing function {}", &function_name);
     let mut result = Vec::<Mutant>::new();
     if let Some(exp) = function.get_def() {
+        let unreachable_code = find_unreachable_code(function, exp);
+
         exp.visit_pre_post(&mut |asc, exp_data| {
             // Collect the spec blocks locations.
             if let ExpData::SpecBlock(_, _) = exp_data {
@@ -158,7 +176,16 @@ fn traverse_function(
 
             // Parse only during the descend phase and when we are not inside the spec block.
             if !asc && !is_inside_spec {
-                result.extend(parse_expression_and_find_mutants(function, exp_data));
+                let mut mutants = parse_expression_and_find_mutants(function, exp_data, conf);
+
+                // Mutants placed in the unreachable code cannot be killed, so mark them to be skipped.
+                let loc = function.module_env.env.get_node_loc(exp_data.node_id());
+                if unreachable_code.iter().any(|l| l.is_enclosing(&loc)) {
+                    trace!("Expression at {loc:?} is unreachable");
+                    mutants.iter_mut().for_each(Mutant::set_unreachable);
+                }
+
+                result.extend(mutants);
             }
 
             true
@@ -172,11 +199,65 @@ fn traverse_function(
     Ok(result)
 }
 
+/// Finds the locations of the obviously unreachable code inside the function body.
+///
+/// This is a simple reachability pass which detects:
+/// - expressions placed in a sequence after `abort`, `return`, `break` or `continue`,
+/// - branches of the `if` expression which cannot be taken due to the constant condition (e.g. `if (false)`).
+fn find_unreachable_code(function: &FunctionEnv<'_>, exp: &ExpData) -> Vec<Loc> {
+    let env = function.module_env.env;
+    let is_diverging = |exp: &ExpData| {
+        matches!(
+            exp,
+            ExpData::Call(_, Operation::Abort, _) | ExpData::Return(_, _) | ExpData::LoopCont(_, _)
+        )
+    };
+
+    let mut unreachable = vec![];
+    exp.visit_pre_post(&mut |asc, exp_data| {
+        if asc {
+            return true;
+        }
+
+        match exp_data {
+            ExpData::IfElse(_, cond, if_exp, else_exp) => {
+                if let ExpData::Value(_, Value::Bool(value)) = cond.as_ref() {
+                    let dead_branch = if *value { else_exp } else { if_exp };
+                    let dead_loc = env.get_node_loc(dead_branch.node_id());
+                    // Missing `else` branch is synthesized by the compiler and may cover the whole `if`.
+                    if !dead_loc.is_enclosing(&env.get_node_loc(cond.node_id())) {
+                        unreachable.push(dead_loc);
+                    }
+                }
+            },
+            ExpData::Sequence(_, exps) => {
+                if let Some(pos) = exps.iter().position(|e| is_diverging(e)) {
+                    unreachable.extend(
+                        exps[pos + 1..]
+                            .iter()
+                            .map(|e| env.get_node_loc(e.node_id())),
+                    );
+                }
+            },
+            _ => {},
+        }
+
+        true
+    });
+
+    unreachable
+}
+
 /// This function does the actual parsing of the expression and checks if any of the mutation operators
 /// can be applied to it.
 /// When Move language is extended with new expressions, this function needs to be updated to support them.
+/// Operator-specific settings from the configuration are taken into account here.
 #[allow(clippy::too_many_lines)]
-fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData) -> Vec<Mutant> {
+fn parse_expression_and_find_mutants(
+    function: &FunctionEnv<'_>,
+    exp: &ExpData,
+    conf: &Configuration,
+) -> Vec<Mutant> {
     let convert_exps_to_explocs = |exps: &[Exp]| -> Vec<ExpLoc> {
         exps.iter()
             .map(|e| ExpLoc {
@@ -214,17 +295,24 @@ fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData)
             | Operation::Shr
             | Operation::Xor => {
                 let exps_loc = convert_exps_to_explocs(exps);
-                let mut result = vec![Mutant::new(MutationOp::new(Box::new(Binary::new(
-                    op.clone(),
-                    function.module_env.env.get_node_loc(*node_id),
-                    exps_loc.clone(),
-                ))))];
+                let mut result = vec![];
 
-                result.push(Mutant::new(MutationOp::new(Box::new(BinarySwap::new(
-                    op.clone(),
-                    function.module_env.env.get_node_loc(*node_id),
-                    exps_loc,
-                )))));
+                if conf
+                    .mutation
+                    .as_ref()
+                    .map_or(true, |m| m.is_binary_operation_enabled(op))
+                {
+                    result.push(Mutant::new(MutationOp::new(Box::new(Binary::new(
+                        op.clone(),
+                        function.module_env.env.get_node_loc(*node_id),
+                        exps_loc.clone(),
+                    )))));
+                    result.push(Mutant::new(MutationOp::new(Box::new(BinarySwap::new(
+                        op.clone(),
+                        function.module_env.env.get_node_loc(*node_id),
+                        exps_loc,
+                    )))));
+                }
 
                 result
             },
@@ -258,12 +346,18 @@ fn parse_expression_and_find_mutants(function: &FunctionEnv<'_>, exp: &ExpData)
             ))))]
         },
         ExpData::Value(node_id, value) => {
-            let mutants = vec![Mutant::new(MutationOp::new(Box::new(Literal::new(
-                value.clone(),
-                function.module_env.env.get_node_type(*node_id),
-                function.module_env.env.get_node_loc(*node_id),
-            ))))];
-            mutants
+            let replacements = conf.mutation.as_ref().map_or_else(
+                LiteralReplacement::all,
+                MutationConfig::literal_replacements,
+            );
+            vec![Mutant::new(MutationOp::new(Box::new(
+                Literal::new(
+                    value.clone(),
+                    function.module_env.env.get_node_type(*node_id),
+                    function.module_env.env.get_node_loc(*node_id),
+                )
+                .with_replacements(replacements),
+            )))]
         },
         ExpData::LoopCont(node_id, _) => vec![Mutant::new(MutationOp::new(Box::new(
             BreakContinue::new(function.module_env.env.get_node_loc(*node_id)),
@@ -2,7 +2,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::report::Mutation;
+use crate::report::{Mutation, OperatorCatalogEntry};
 use codespan::FileId;
 use std::{
     fmt,
@@ -28,6 +28,38 @@ impl MutantInfo {
     }
 }
 
+/// Static description of the mutation operator used to build the operator catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OperatorInfo {
+    /// The name of the operator, as used in the report.
+    pub name: &'static str,
+    /// Human readable description of the operator.
+    pub description: &'static str,
+    /// Example of the code before the mutation.
+    pub example_original: &'static str,
+    /// Example of the code after the mutation.
+    pub example_mutated: &'static str,
+    /// The exact replacements performed by the operator.
+    pub replacements: &'static [&'static str],
+    /// Typical specification clauses killing the mutants of the operator.
+    pub killing_specs: &'static [&'static str],
+    /// Function along with its specification killing the mutants of the operator.
+    pub worked_example: &'static str,
+    /// Explanation of the worked example: which mutant is killed by which clause.
+    pub worked_example_explanation: &'static str,
+}
+
+impl From<&OperatorInfo> for OperatorCatalogEntry {
+    fn from(info: &OperatorInfo) -> Self {
+        OperatorCatalogEntry::new(
+            info.name,
+            info.description,
+            info.example_original,
+            info.example_mutated,
+        )
+    }
+}
+
 /// Trait for mutation operators.
 /// Mutation operators are used to apply mutations to the source code. To keep adding new mutation operators simple,
 /// we use a trait that all mutation operators implement.
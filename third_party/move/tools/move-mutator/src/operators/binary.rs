@@ -3,8 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
-    operators::ExpLoc,
+    operator::{MutantInfo, MutationOperator, OperatorInfo},
+    operators::{skip_leading_whitespaces, skip_trailing_whitespaces, source_slice, ExpLoc},
     report::{Mutation, Range},
 };
 use codespan::FileId;
@@ -13,6 +13,35 @@ use std::fmt;
 
 pub const OPERATOR_NAME: &str = "binary_operator_replacement";
 
+/// Description of the operator used in the operator catalog.
+pub const OPERATOR_INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    description: "Replaces a binary operator with other operators from the same group (arithmetic, bitwise, shift, logical or comparison).",
+    example_original: "a + b",
+    example_mutated: "a - b",
+    replacements: &[
+        "`+`, `-`, `*`, `/` and `%` with each other",
+        "`|`, `&` and `^` with each other",
+        "`<<` with `>>` and vice versa",
+        "`||` with `&&` and vice versa",
+        "`==`, `!=`, `<`, `>`, `<=` and `>=` with each other",
+    ],
+    killing_specs: &[
+        "`ensures result == a + b;` stating the exact result",
+        "`aborts_if a + b > MAX_U64;` stating when the arithmetic aborts",
+        "`ensures` clauses comparing the result with the arguments (e.g. `ensures result >= a;`)",
+    ],
+    worked_example: concat!(
+        "fun add(a: u64, b: u64): u64 { a + b }\n",
+        "spec add {\n",
+        "    aborts_if a + b > MAX_U64;\n",
+        "    ensures result == a + b;\n",
+        "}",
+    ),
+    worked_example_explanation: "The mutant `a - b` is killed twice: it aborts if `a < b`, which \
+        `aborts_if` doesn't allow, and its result breaks `ensures`.",
+};
+
 /// The binary mutation operator.
 #[derive(Debug, Clone)]
 pub struct Binary {
@@ -47,15 +76,13 @@ impl MutationOperator for Binary {
         let right = &self.exps[1].loc;
         let start = left.span().end().to_usize();
         // Adjust start to omit whitespaces before the operator
-        let start = source[start..]
-            .find(|c: char| !c.is_whitespace())
-            .map_or(start, |i| start + i);
+        let start = skip_leading_whitespaces(source, start);
         let end = right.span().start().to_usize();
         // Adjust end to omit whitespaces after the operator
-        let end = source[..end]
-            .rfind(|c: char| !c.is_whitespace())
-            .map_or(end, |i| i + 1);
-        let cur_op = &source[start..end];
+        let end = skip_trailing_whitespaces(source, end);
+        let Some(cur_op) = source_slice(source, start, end) else {
+            return vec![];
+        };
 
         // Group of exchangeable binary operators - we only want to replace the operator with a different one
         // within the same group.
@@ -156,6 +183,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_apply_binary_operator_with_multibyte_characters() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let source = "vector[b\"ż\"] != vector[b\"ł\"]";
+        let left_end = source.find(" !=").unwrap();
+        let right_start = source.rfind("vector").unwrap();
+        let loc = Loc::new(fid, codespan::Span::new(0, source.len() as u32));
+        let loc2 = Loc::new(fid, codespan::Span::new(0, left_end as u32));
+        let loc3 = Loc::new(
+            fid,
+            codespan::Span::new(right_start as u32, source.len() as u32),
+        );
+        let e1 = ExpData::Value(NodeId::new(1), Value::Bool(true));
+        let e2 = ExpData::Value(NodeId::new(2), Value::Bool(false));
+        let exp1 = ExpLoc::new(e1.into_exp(), loc2);
+        let exp2 = ExpLoc::new(e2.into_exp(), loc3);
+
+        let operator = Binary::new(Operation::Neq, loc, vec![exp1, exp2]);
+        let result = operator.apply(source);
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[0].mutated_source, "vector[b\"ż\"] == vector[b\"ł\"]");
+    }
+
+    #[test]
+    fn test_apply_binary_operator_skips_invalid_range() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 5));
+        let loc2 = Loc::new(fid, codespan::Span::new(0, 2));
+        let loc3 = Loc::new(fid, codespan::Span::new(4, 5));
+        let e1 = ExpData::Value(NodeId::new(1), Value::Bool(true));
+        let e2 = ExpData::Value(NodeId::new(2), Value::Bool(false));
+        let exp1 = ExpLoc::new(e1.into_exp(), loc2);
+        let exp2 = ExpLoc::new(e2.into_exp(), loc3);
+
+        // The operator range splits the multi-byte character.
+        let operator = Binary::new(Operation::Add, loc, vec![exp1, exp2]);
+        assert!(operator.apply("5ż+2").is_empty());
+    }
+
     #[test]
     fn test_get_file_id() {
         let mut files = Files::new();
@@ -3,8 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
-    operators::ExpLoc,
+    operator::{MutantInfo, MutationOperator, OperatorInfo},
+    operators::{skip_leading_whitespaces, skip_trailing_whitespaces, source_slice, ExpLoc},
     report::{Mutation, Range},
 };
 use codespan::FileId;
@@ -13,6 +13,31 @@ use std::fmt;
 
 pub const OPERATOR_NAME: &str = "binary_operator_swap";
 
+/// Description of the operator used in the operator catalog.
+pub const OPERATOR_INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    description: "Swaps the operands of a non-commutative binary operator.",
+    example_original: "a - b",
+    example_mutated: "b - a",
+    replacements: &[
+        "`a op b` with `b op a` for all the binary operators except `+`, `*`, `==` and `!=`",
+    ],
+    killing_specs: &[
+        "`ensures result == a - b;` stating the exact result",
+        "`aborts_if a < b;` stating when the operation aborts, as the swapped operands abort in other cases",
+    ],
+    worked_example: concat!(
+        "fun sub(a: u64, b: u64): u64 { a - b }\n",
+        "spec sub {\n",
+        "    aborts_if a < b;\n",
+        "    ensures result == a - b;\n",
+        "}",
+    ),
+    worked_example_explanation: "The mutant `b - a` aborts if `b < a` instead of `a < b`, so the \
+        `aborts_if` clause kills it. Swapping the operands of `&&` and `||` usually gives an \
+        equivalent mutant, unless the left operand guards an aborting right one.",
+};
+
 /// The binary swap mutation operator.
 #[derive(Debug, Clone)]
 pub struct BinarySwap {
@@ -57,22 +82,31 @@ impl MutationOperator for BinarySwap {
         let right = &self.exps[1].loc;
         let start = left.span().end().to_usize();
         // Adjust start to omit whitespaces before the operator
-        let start = source[start..]
-            .find(|c: char| !c.is_whitespace())
-            .map_or(start, |i| start + i);
+        let start = skip_leading_whitespaces(source, start);
         let end = right.span().start().to_usize();
         // Adjust end to omit whitespaces after the operator
-        let end = source[..end]
-            .rfind(|c: char| !c.is_whitespace())
-            .map_or(end, |i| i + 1);
-        let binop_str = &source[start..end];
+        let end = skip_trailing_whitespaces(source, end);
+        let Some(binop_str) = source_slice(source, start, end) else {
+            return vec![];
+        };
 
         let start = left.span().start().to_usize();
         let end = right.span().end().to_usize();
-        let cur_op = &source[start..end];
-
-        let left_str = &source[left.span().start().to_usize()..left.span().end().to_usize()];
-        let right_str = &source[right.span().start().to_usize()..right.span().end().to_usize()];
+        let (Some(cur_op), Some(left_str), Some(right_str)) = (
+            source_slice(source, start, end),
+            source_slice(
+                source,
+                left.span().start().to_usize(),
+                left.span().end().to_usize(),
+            ),
+            source_slice(
+                source,
+                right.span().start().to_usize(),
+                right.span().end().to_usize(),
+            ),
+        ) else {
+            return vec![];
+        };
 
         let mut mutated_source = source.to_string();
         let mut op = right_str.to_owned();
@@ -3,8 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
-    operators::{MOVE_BREAK, MOVE_CONTINUE, MOVE_EMPTY_STMT},
+    operator::{MutantInfo, MutationOperator, OperatorInfo},
+    operators::{source_slice, MOVE_BREAK, MOVE_CONTINUE, MOVE_EMPTY_STMT},
     report::{Mutation, Range},
 };
 use codespan::FileId;
@@ -13,6 +13,39 @@ use std::{fmt, fmt::Debug};
 
 pub const OPERATOR_NAME: &str = "break_continue_replacement";
 
+/// Description of the operator used in the operator catalog.
+pub const OPERATOR_INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    description: "Replaces `break` with `continue` (and vice versa) or removes the statement.",
+    example_original: "break",
+    example_mutated: "continue",
+    replacements: &[
+        "`break` with `continue` or `{}`",
+        "`continue` with `break` or `{}`",
+    ],
+    killing_specs: &[
+        "loop invariants (`spec { invariant ... };` in the loop condition) bounding the loop variables",
+        "`ensures` clauses stating the result computed by the loop",
+    ],
+    worked_example: concat!(
+        "fun count_to(n: u64): u64 {\n",
+        "    let i = 0;\n",
+        "    while ({ spec { invariant i <= n; }; true }) {\n",
+        "        if (i == n) break;\n",
+        "        i = i + 1;\n",
+        "    };\n",
+        "    i\n",
+        "}\n",
+        "spec count_to {\n",
+        "    ensures result == n;\n",
+        "}",
+    ),
+    worked_example_explanation: "Removing the `break` lets `i` grow past `n`, so the loop invariant \
+        is not preserved and the mutant is killed. Replacing it with `continue` makes the loop \
+        endless, which the prover (checking only the partial correctness) can't tell apart from the \
+        original - such mutants can be marked as equivalent.",
+};
+
 /// Break and continue mutation operator.
 /// Replaces break and continue statements with each other or deletes them.
 #[derive(Debug, Clone)]
@@ -32,7 +65,9 @@ impl MutationOperator for BreakContinue {
     fn apply(&self, source: &str) -> Vec<MutantInfo> {
         let start = self.loc.span().start().to_usize();
         let end = self.loc.span().end().to_usize();
-        let cur_op = &source[start..end];
+        let Some(cur_op) = source_slice(source, start, end) else {
+            return vec![];
+        };
 
         // Group of exchangeable break/continue statements.
         let ops: Vec<&str> = match cur_op {
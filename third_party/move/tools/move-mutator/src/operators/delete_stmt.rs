@@ -3,8 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
-    operators::MOVE_EMPTY_STMT,
+    operator::{MutantInfo, MutationOperator, OperatorInfo},
+    operators::{source_slice, MOVE_EMPTY_STMT},
     report::{Mutation, Range},
 };
 use codespan::FileId;
@@ -13,6 +13,34 @@ use std::{fmt, fmt::Debug};
 
 pub const OPERATOR_NAME: &str = "delete_statement";
 
+/// Description of the operator used in the operator catalog.
+pub const OPERATOR_INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    description: "Replaces a statement with an empty block.",
+    example_original: "foo(x)",
+    example_mutated: "{}",
+    replacements: &[
+        "`abort code` with `{}`",
+        "`move_to(signer, resource)` with `{}`",
+    ],
+    killing_specs: &[
+        "`aborts_if` clauses listing all the abort conditions, so the function has to abort when they hold",
+        "`aborts_if ... with CODE;` stating the abort codes",
+        "`ensures exists<T>(addr);` for the published resources",
+    ],
+    worked_example: concat!(
+        "fun check_owner(owner: address, caller: address) {\n",
+        "    if (owner != caller) abort 1;\n",
+        "}\n",
+        "spec check_owner {\n",
+        "    aborts_if owner != caller with 1;\n",
+        "}",
+    ),
+    worked_example_explanation: "The mutant with `abort 1` replaced by `{}` never aborts, although \
+        `aborts_if` requires it to abort if `owner != caller`, so it's killed. Without any \
+        `aborts_if` clause, the prover doesn't check the abort conditions and the mutant survives.",
+};
+
 /// Statement delete operator.
 /// Deletes statements which can be potentially deleted, still allowing the code to compile
 /// properly.
@@ -36,7 +64,9 @@ impl MutationOperator for DeleteStmt {
             self.loc.span().start().to_usize(),
             self.loc.span().end().to_usize(),
         );
-        let cur_op = &source[start..end];
+        let Some(cur_op) = source_slice(source, start, end) else {
+            return vec![];
+        };
 
         let ops: Vec<&str> = vec![MOVE_EMPTY_STMT];
 
@@ -3,8 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
-    operators::ExpLoc,
+    operator::{MutantInfo, MutationOperator, OperatorInfo},
+    operators::{source_slice, ExpLoc},
     report::{Mutation, Range},
 };
 use codespan::FileId;
@@ -12,6 +12,33 @@ use std::{fmt, fmt::Debug};
 
 pub const OPERATOR_NAME: &str = "if_else_replacement";
 
+/// Description of the operator used in the operator catalog.
+pub const OPERATOR_INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    description: "Replaces the condition of an `if` expression with `true`, `false` or its negation.",
+    example_original: "if (a < b)",
+    example_mutated: "if (!(a < b))",
+    replacements: &[
+        "the condition `c` with `true`",
+        "the condition `c` with `false`",
+        "the condition `c` with `!(c)`",
+    ],
+    killing_specs: &[
+        "`ensures` clauses covering both branches (e.g. `ensures a < b ==> result == b;`)",
+        "`aborts_if` clauses stating the conditions of the aborting branch",
+    ],
+    worked_example: concat!(
+        "fun max(a: u64, b: u64): u64 { if (a < b) b else a }\n",
+        "spec max {\n",
+        "    ensures result >= a && result >= b;\n",
+        "    ensures result == a || result == b;\n",
+        "}",
+    ),
+    worked_example_explanation: "The mutant `if (true)` always returns `b`, breaking `result >= a` \
+        if `a > b`, and the other mutants return the smaller argument in some cases, so all of them \
+        are killed. With only the second clause, all the mutants would survive.",
+};
+
 /// `IfElse` mutation operator.
 /// Replaces conditional expressions in if/else statements with literals.
 /// Currently only condition field is used.
@@ -39,7 +66,9 @@ impl MutationOperator for IfElse {
     fn apply(&self, source: &str) -> Vec<MutantInfo> {
         let start = self.cond.loc.span().start().to_usize();
         let end = self.cond.loc.span().end().to_usize();
-        let cur_op = &source[start..end];
+        let Some(cur_op) = source_slice(source, start, end) else {
+            return vec![];
+        };
 
         // Change if/else expression to true/false.
         let ops: Vec<String> = vec![
@@ -3,10 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
+    configuration::LiteralReplacement,
+    operator::{MutantInfo, MutationOperator, OperatorInfo},
     operators::{
-        MOVE_ADDR_MAX, MOVE_ADDR_ZERO, MOVE_FALSE, MOVE_MAX_INFERRED_NUM, MOVE_MAX_U256, MOVE_TRUE,
-        MOVE_ZERO_U256,
+        source_slice, MOVE_ADDR_MAX, MOVE_ADDR_ZERO, MOVE_FALSE, MOVE_MAX_INFERRED_NUM,
+        MOVE_MAX_U256, MOVE_TRUE, MOVE_ZERO_U256,
     },
     report::{Mutation, Range},
 };
@@ -21,6 +22,35 @@ use std::{fmt, fmt::Debug};
 
 pub const OPERATOR_NAME: &str = "literal_replacement";
 
+/// Description of the operator used in the operator catalog.
+pub const OPERATOR_INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    description:
+        "Replaces a literal with boundary values (zero, maximum) or increments and decrements it.",
+    example_original: "10",
+    example_mutated: "11",
+    replacements: &[
+        "an integer with `0`, the maximum of its type, the value + 1 and the value - 1",
+        "a boolean with `true` and `false`",
+        "an address with `0x0` and `0xFFFF...FFFF`",
+    ],
+    killing_specs: &[
+        "`ensures` clauses stating the exact values (e.g. `ensures result == amount / 100;`)",
+        "`aborts_if ... with CODE;` stating the abort codes",
+    ],
+    worked_example: concat!(
+        "fun fee(amount: u64): u64 { amount / 100 }\n",
+        "spec fee {\n",
+        "    aborts_if false;\n",
+        "    ensures result == amount / 100;\n",
+        "}",
+    ),
+    worked_example_explanation:
+        "The mutants `amount / 101` and `amount / 99` return other results, \
+        so `ensures` kills them. The `amount / 0` mutant always aborts, so it's killed only by \
+        `aborts_if false;`, as `ensures` holds just for the calls which don't abort.",
+};
+
 /// Literal replacement mutation operator.
 /// Replaces literal statements with other ones but withing the same type.
 #[derive(Debug, Clone)]
@@ -28,25 +58,37 @@ pub struct Literal {
     operation: Value,
     optype: Type,
     loc: Loc,
+    replacements: Vec<LiteralReplacement>,
 }
 
 impl Literal {
     /// Creates a new instance of the literal mutation operator.
+    /// All kinds of replacements are used by default.
     #[must_use]
     pub fn new(operation: Value, optype: Type, loc: Loc) -> Self {
         Self {
             operation,
             optype,
             loc,
+            replacements: LiteralReplacement::all(),
         }
     }
+
+    /// Restricts the kinds of replacements generated by the operator.
+    #[must_use]
+    pub fn with_replacements(mut self, replacements: Vec<LiteralReplacement>) -> Self {
+        self.replacements = replacements;
+        self
+    }
 }
 
 impl MutationOperator for Literal {
     fn apply(&self, source: &str) -> Vec<MutantInfo> {
         let start = self.loc.span().start().to_usize();
         let end = self.loc.span().end().to_usize();
-        let cur_op = &source[start..end];
+        let Some(cur_op) = source_slice(source, start, end) else {
+            return vec![];
+        };
 
         // Group of literal statements for possible Value types.
         // For each group use minimum and maximum values and some additional values:
@@ -56,22 +98,35 @@ impl MutationOperator for Literal {
         // More values can be added.
         // Please be aware that adding here even one additional case may increase
         // the number of mutants significantly.
-        let ops: Vec<String> = match &self.optype {
+        // Each value is tagged with its replacement kind, so it can be disabled in the configuration.
+        let ops: Vec<(LiteralReplacement, String)> = match &self.optype {
             Type::Primitive(PrimitiveType::Address) => {
-                vec![MOVE_ADDR_ZERO.to_owned(), MOVE_ADDR_MAX.to_owned()]
+                vec![
+                    (LiteralReplacement::Min, MOVE_ADDR_ZERO.to_owned()),
+                    (LiteralReplacement::Max, MOVE_ADDR_MAX.to_owned()),
+                ]
             },
             Type::Primitive(PrimitiveType::Bool) => {
-                vec![MOVE_TRUE.to_owned(), MOVE_FALSE.to_owned()]
+                vec![
+                    (LiteralReplacement::Max, MOVE_TRUE.to_owned()),
+                    (LiteralReplacement::Min, MOVE_FALSE.to_owned()),
+                ]
             },
             Type::Primitive(PrimitiveType::U8) => {
                 if let Value::Number(bigint) = &self.operation {
                     let u8_val = bigint.to_u8().expect("Invalid u8 value");
 
                     vec![
-                        u8::MIN.to_string(),
-                        u8::MAX.to_string(),
-                        u8_val.saturating_add(1).to_string(),
-                        u8_val.saturating_sub(1).to_string(),
+                        (LiteralReplacement::Min, u8::MIN.to_string()),
+                        (LiteralReplacement::Max, u8::MAX.to_string()),
+                        (
+                            LiteralReplacement::Increment,
+                            u8_val.saturating_add(1).to_string(),
+                        ),
+                        (
+                            LiteralReplacement::Decrement,
+                            u8_val.saturating_sub(1).to_string(),
+                        ),
                     ]
                 } else {
                     vec![]
@@ -82,10 +137,16 @@ impl MutationOperator for Literal {
                     let u16_val = bigint.to_u16().expect("Invalid u16 value");
 
                     vec![
-                        u16::MIN.to_string(),
-                        u16::MAX.to_string(),
-                        u16_val.saturating_add(1).to_string(),
-                        u16_val.saturating_sub(1).to_string(),
+                        (LiteralReplacement::Min, u16::MIN.to_string()),
+                        (LiteralReplacement::Max, u16::MAX.to_string()),
+                        (
+                            LiteralReplacement::Increment,
+                            u16_val.saturating_add(1).to_string(),
+                        ),
+                        (
+                            LiteralReplacement::Decrement,
+                            u16_val.saturating_sub(1).to_string(),
+                        ),
                     ]
                 } else {
                     vec![]
@@ -96,10 +157,16 @@ impl MutationOperator for Literal {
                     let u32_val = bigint.to_u32().expect("Invalid u32 value");
 
                     vec![
-                        u32::MIN.to_string(),
-                        u32::MAX.to_string(),
-                        u32_val.saturating_add(1).to_string(),
-                        u32_val.saturating_sub(1).to_string(),
+                        (LiteralReplacement::Min, u32::MIN.to_string()),
+                        (LiteralReplacement::Max, u32::MAX.to_string()),
+                        (
+                            LiteralReplacement::Increment,
+                            u32_val.saturating_add(1).to_string(),
+                        ),
+                        (
+                            LiteralReplacement::Decrement,
+                            u32_val.saturating_sub(1).to_string(),
+                        ),
                     ]
                 } else {
                     vec![]
@@ -108,11 +175,18 @@ impl MutationOperator for Literal {
             Type::Primitive(PrimitiveType::U64) => {
                 if let Value::Number(bigint) = &self.operation {
                     let u64_val = bigint.to_u64().expect("Invalid u64 value");
+
                     vec![
-                        u64::MIN.to_string(),
-                        u64::MAX.to_string(),
-                        u64_val.saturating_add(1).to_string(),
-                        u64_val.saturating_sub(1).to_string(),
+                        (LiteralReplacement::Min, u64::MIN.to_string()),
+                        (LiteralReplacement::Max, u64::MAX.to_string()),
+                        (
+                            LiteralReplacement::Increment,
+                            u64_val.saturating_add(1).to_string(),
+                        ),
+                        (
+                            LiteralReplacement::Decrement,
+                            u64_val.saturating_sub(1).to_string(),
+                        ),
                     ]
                 } else {
                     vec![]
@@ -121,28 +195,41 @@ impl MutationOperator for Literal {
             Type::Primitive(PrimitiveType::U128) => {
                 if let Value::Number(bigint) = &self.operation {
                     let u128_val = bigint.to_u128().expect("Invalid u128 value");
+
                     vec![
-                        u128::MIN.to_string(),
-                        u128::MAX.to_string(),
-                        u128_val.saturating_add(1).to_string(),
-                        u128_val.saturating_sub(1).to_string(),
+                        (LiteralReplacement::Min, u128::MIN.to_string()),
+                        (LiteralReplacement::Max, u128::MAX.to_string()),
+                        (
+                            LiteralReplacement::Increment,
+                            u128_val.saturating_add(1).to_string(),
+                        ),
+                        (
+                            LiteralReplacement::Decrement,
+                            u128_val.saturating_sub(1).to_string(),
+                        ),
                     ]
                 } else {
                     vec![]
                 }
             },
             Type::Primitive(PrimitiveType::U256) => {
-                vec![MOVE_ZERO_U256.to_owned(), MOVE_MAX_U256.to_owned()]
+                vec![
+                    (LiteralReplacement::Min, MOVE_ZERO_U256.to_owned()),
+                    (LiteralReplacement::Max, MOVE_MAX_U256.to_owned()),
+                ]
             },
             Type::Primitive(PrimitiveType::Num) => {
-                vec!["0".to_owned(), MOVE_MAX_INFERRED_NUM.to_owned()]
+                vec![
+                    (LiteralReplacement::Min, "0".to_owned()),
+                    (LiteralReplacement::Max, MOVE_MAX_INFERRED_NUM.to_owned()),
+                ]
             },
             _ => vec![],
         };
 
         ops.into_iter()
-            .filter(|v| cur_op != *v)
-            .map(|op| {
+            .filter(|(kind, v)| cur_op != *v && self.replacements.contains(kind))
+            .map(|(_, op)| {
                 let mut mutated_source = source.to_string();
                 mutated_source.replace_range(start..end, op.as_str());
                 MutantInfo::new(
@@ -210,6 +297,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_apply_with_restricted_replacements() {
+        let mut files = Files::new();
+        let fid = files.add("test", "test");
+        let loc = Loc::new(fid, codespan::Span::new(0, 2));
+
+        let operator = Literal::new(
+            Value::Number(51.into()),
+            Type::Primitive(PrimitiveType::U64),
+            loc,
+        )
+        .with_replacements(vec![
+            LiteralReplacement::Increment,
+            LiteralReplacement::Decrement,
+        ]);
+        let source = "51";
+        let expected = ["52", "50"];
+        let result = operator.apply(source);
+        assert_eq!(result.len(), expected.len());
+        for (i, r) in result.iter().enumerate() {
+            assert_eq!(r.mutated_source, expected[i]);
+        }
+    }
+
     #[test]
     fn test_apply_u16() {
         let mut files = Files::new();
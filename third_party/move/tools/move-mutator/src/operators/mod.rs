@@ -2,6 +2,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::{operator::OperatorInfo, report::OperatorCatalogEntry};
 use move_model::{ast::Exp, model::Loc};
 
 pub(crate) mod binary;
@@ -28,6 +29,22 @@ pub(crate) const MOVE_ADDR_ZERO: &str = "0x0";
 pub(crate) const MOVE_ADDR_MAX: &str =
     "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF";
 
+/// Descriptions of all the available mutation operators.
+pub(crate) const OPERATORS: &[OperatorInfo] = &[
+    binary::OPERATOR_INFO,
+    binary_swap::OPERATOR_INFO,
+    break_continue::OPERATOR_INFO,
+    delete_stmt::OPERATOR_INFO,
+    ifelse::OPERATOR_INFO,
+    literal::OPERATOR_INFO,
+    unary::OPERATOR_INFO,
+];
+
+/// Returns the catalog of all the available mutation operators, which is embedded in the report.
+pub(crate) fn catalog() -> Vec<OperatorCatalogEntry> {
+    OPERATORS.iter().map(OperatorCatalogEntry::from).collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct ExpLoc {
     pub exp: Exp,
@@ -41,3 +58,69 @@ impl ExpLoc {
         Self { exp, loc }
     }
 }
+
+/// Returns the part of the source placed in the given byte range.
+/// Returns `None` if the range is out of the source or splits a multi-byte UTF-8 character, as mutating
+/// such place would produce a broken mutant.
+pub(crate) fn source_slice(source: &str, start: usize, end: usize) -> Option<&str> {
+    let slice = source.get(start..end);
+    if slice.is_none() {
+        warn!("Range {start}..{end} does not lie on character boundaries of the source, skipping mutation");
+    }
+    slice
+}
+
+/// Moves the `start` byte offset forward past any whitespaces.
+pub(crate) fn skip_leading_whitespaces(source: &str, start: usize) -> usize {
+    source
+        .get(start..)
+        .and_then(|s| s.find(|c: char| !c.is_whitespace()))
+        .map_or(start, |i| start + i)
+}
+
+/// Moves the `end` byte offset backward past any whitespaces.
+/// The returned offset points right after the last non-whitespace character, which may be longer than one byte.
+pub(crate) fn skip_trailing_whitespaces(source: &str, end: usize) -> usize {
+    source
+        .get(..end)
+        .and_then(|s| s.char_indices().rev().find(|(_, c)| !c.is_whitespace()))
+        .map_or(end, |(i, c)| i + c.len_utf8())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_contains_unique_operators() {
+        let catalog = catalog();
+        let mut names = catalog
+            .iter()
+            .map(|o| o.name().to_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), OPERATORS.len());
+        assert!(catalog.iter().all(|o| !o.description().is_empty()));
+    }
+
+    #[test]
+    fn source_slice_rejects_ranges_splitting_characters() {
+        let source = "ż+ł";
+        assert_eq!(source_slice(source, 0, 2), Some("ż"));
+        assert_eq!(source_slice(source, 0, 1), None);
+        assert_eq!(source_slice(source, 3, 10), None);
+    }
+
+    #[test]
+    fn skip_whitespaces_respects_multibyte_characters() {
+        let source = "ż  +  ł";
+        assert_eq!(skip_leading_whitespaces(source, 2), 4);
+        assert_eq!(skip_trailing_whitespaces(source, 4), 2);
+        assert_eq!(skip_trailing_whitespaces(source, 7), 5);
+        assert_eq!(
+            &source[skip_leading_whitespaces(source, 2)..skip_trailing_whitespaces(source, 7)],
+            "+"
+        );
+    }
+}
@@ -3,8 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    operator::{MutantInfo, MutationOperator},
-    operators::ExpLoc,
+    operator::{MutantInfo, MutationOperator, OperatorInfo},
+    operators::{skip_leading_whitespaces, source_slice, ExpLoc},
     report::{Mutation, Range},
 };
 use codespan::FileId;
@@ -13,6 +13,27 @@ use std::fmt;
 
 pub const OPERATOR_NAME: &str = "unary_operator_replacement";
 
+/// Description of the operator used in the operator catalog.
+pub const OPERATOR_INFO: OperatorInfo = OperatorInfo {
+    name: OPERATOR_NAME,
+    description: "Removes the unary operator.",
+    example_original: "!a",
+    example_mutated: "a",
+    replacements: &["`!e` with `e`"],
+    killing_specs: &[
+        "`ensures` clauses stating the boolean results (e.g. `ensures result == (x != 0);`)",
+        "`aborts_if` clauses stating the conditions of the aborts guarded by the negation",
+    ],
+    worked_example: concat!(
+        "fun is_not_zero(x: u64): bool { !(x == 0) }\n",
+        "spec is_not_zero {\n",
+        "    ensures result == (x != 0);\n",
+        "}",
+    ),
+    worked_example_explanation: "The mutant `(x == 0)` returns the opposite result, so `ensures` \
+        kills it.",
+};
+
 /// Represents a unary operator mutation.
 #[derive(Debug, Clone)]
 pub struct Unary {
@@ -43,11 +64,11 @@ impl MutationOperator for Unary {
 
         let start = self.loc.span().start().to_usize();
         // Adjust start to omit whitespaces before the operator
-        let start = source[start..]
-            .find(|c: char| !c.is_whitespace())
-            .map_or(start, |i| start + i);
+        let start = skip_leading_whitespaces(source, start);
         let end = self.loc.span().end().to_usize();
-        let cur_op = &source[start..end];
+        let Some(cur_op) = source_slice(source, start, end) else {
+            return vec![];
+        };
 
         // For unary operator mutations, we only need to replace the operator with a space (to ensure the same file length).
         vec![" "]
@@ -59,7 +80,7 @@ impl MutationOperator for Unary {
                     mutated_source,
                     Mutation::new(
                         Range::new(start, end),
-                        "unary_operator_replacement".to_string(),
+                        OPERATOR_NAME.to_string(),
                         cur_op.to_string(),
                         op.to_string(),
                     ),
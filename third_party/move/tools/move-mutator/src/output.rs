@@ -2,7 +2,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{cli, configuration::Configuration};
+use crate::{cli, configuration::Configuration, report::Report};
 use move_package::source_package::layout::SourcePackageLayout;
 use std::{
     ffi::OsString,
@@ -21,6 +21,8 @@ use std::{
 /// This function constructs the following output path for file.move:
 /// "`output_dir/X/Y/file_index.move`"
 /// It finds the package root for the file, which is "/a/b/c", then it append the relative path to the output directory.
+/// Files placed outside the sources directory (e.g. scripts in "/a/b/c/scripts/file.move") keep
+/// the directory name, so the output path is "`output_dir/scripts/file_index.move`".
 ///
 /// If the file is not inside any package, it creates the directory structure in the output directory like:
 /// The file to be mutated is located in "/a/b/c/file.move" (`file_path`).
@@ -46,13 +48,18 @@ pub(crate) fn setup_mutant_path(output_dir: &Path, file_path: &Path) -> anyhow::
 
     // Try to find package root for the file. If the file is not inside any package, assume that it is a single file.
     let root = SourcePackageLayout::try_find_root(&file_path_canonicalized);
-    let root_path = if root.is_err() {
+    let root_path = if let Ok(root) = root {
+        // In case of file is inside the package it must follow the Move structure. Sources directory
+        // can be omitted, but other directories (like scripts) are kept to avoid name clashes.
+        let sources = root.join(SourcePackageLayout::Sources.path());
+        if file_path_canonicalized.starts_with(&sources) {
+            sources
+        } else {
+            root
+        }
+    } else {
         debug!("No package root for {file_path_canonicalized:?}. Assuming mutating a single file.");
         file_path_canonicalized.clone()
-    } else {
-        // In case of file is inside the package it must follow the Move structure. So we can assume that
-        // there will be a sources directory inside the package root. We can omit it.
-        root?.join("sources")
     };
 
     // Stripping whole prefix before file to get it relative path inside the package.
@@ -126,6 +133,44 @@ pub(crate) fn setup_output_dir(mutator_configuration: &Configuration) -> anyhow:
     Ok(output_dir)
 }
 
+/// Prepares the output directory for regenerating the mutants of the given files only.
+///
+/// The mutants of the files are removed from the output directory and from the report of the
+/// previous run, so the mutants of the other files are kept.
+///
+/// # Arguments
+///
+/// * `mutator_configuration` - The configuration for the mutator.
+/// * `files` - The original files whose mutants are regenerated.
+///
+/// # Returns
+///
+/// * `anyhow::Result<Option<(PathBuf, Report)>>` - Returns the path to the output directory along with
+///   the report of the previous run without the mutants of the files, or `None` if there is no
+///   previous report.
+pub(crate) fn reuse_output_dir(
+    mutator_configuration: &Configuration,
+    files: &[PathBuf],
+) -> anyhow::Result<Option<(PathBuf, Report)>> {
+    let output_dir = mutator_configuration
+        .project
+        .out_mutant_dir
+        .clone()
+        .unwrap_or(PathBuf::from(cli::DEFAULT_OUTPUT_DIR));
+    let report_path = output_dir.join("report.json");
+    if !report_path.exists() {
+        return Ok(None);
+    }
+
+    let mut report = Report::load_from_json_file(&report_path)?;
+    for entry in report.remove_files(files) {
+        trace!("Removing outdated mutant {}", entry.mutant_path().display());
+        let _ = fs::remove_file(entry.mutant_path());
+    }
+
+    Ok(Some((output_dir, report)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
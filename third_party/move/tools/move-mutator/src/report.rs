@@ -2,19 +2,57 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod github;
+mod html;
+mod query;
+mod sarif;
+mod stream;
+mod stryker;
+
+use crate::sandbox::SandboxFailure;
+pub use query::MutantView;
 use serde::{Deserialize, Serialize};
 use serde_json;
+use sha2::{Digest, Sha256};
 use std::{
+    borrow::Cow,
+    collections::HashSet,
+    fmt,
     io::{Error, ErrorKind, Result, Write},
     path::{Path, PathBuf},
 };
+pub use stream::ReportStreamWriter;
+
+/// Version of the report structure. It must be incremented on every change of the report format
+/// and a migration from the previous version has to be added to `Report::migrate`.
+pub const REPORT_SCHEMA_VERSION: u32 = 9;
+
+/// Version of the reports created before the version was stored in the report.
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+fn legacy_schema_version() -> u32 {
+    LEGACY_SCHEMA_VERSION
+}
 
 /// The `Report` struct represents a report of mutations.
 /// It contains a vector of `MutationReport` instances.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Report {
+    /// The version of the report structure.
+    #[serde(default = "legacy_schema_version")]
+    schema_version: u32,
+    /// The root directory of the package. Paths of the files placed under the root are stored
+    /// relative to it, so the report is portable across machines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    package_root: Option<PathBuf>,
+    /// Descriptions of the mutation operators, so the report can be interpreted on its own.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    operators: Vec<OperatorCatalogEntry>,
     /// The vector of `ReportEntry` instances.
     mutants: Vec<MutationReport>,
+    /// Mutations which were not generated, along with the reason.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    skipped: Vec<SkippedMutation>,
 }
 
 impl Report {
@@ -22,7 +60,11 @@ impl Report {
     #[must_use]
     pub fn new() -> Self {
         Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            package_root: None,
+            operators: Vec::new(),
             mutants: Vec::new(),
+            skipped: Vec::new(),
         }
     }
 
@@ -32,6 +74,84 @@ impl Report {
         self.mutants.push(entry);
     }
 
+    /// Adds a mutation which was not generated to the report.
+    pub fn add_skipped_entry(&mut self, entry: SkippedMutation) {
+        trace!("Adding a skipped mutation to the report: {entry:?}");
+        self.skipped.push(entry);
+    }
+
+    /// Reduces the level of detail of the report, so it stays small for big packages.
+    ///
+    /// Depending on the given `detail`, only surviving mutants are kept (skipped mutations are dropped
+    /// as well), diffs of the mutants are omitted or truncated and the source code is stripped.
+    pub fn reduce_detail(&mut self, detail: &ReportDetail) {
+        if detail.survivors_only {
+            self.mutants.retain(|m| m.status == MutantStatus::Survived);
+            self.skipped.clear();
+        }
+
+        for entry in &mut self.skipped {
+            entry.reduce_detail(detail);
+        }
+
+        for entry in &mut self.mutants {
+            entry.reduce_detail(detail);
+        }
+    }
+
+    /// Merges another `Report` (e.g. a partial report from a sharded or resumed run) into this one.
+    ///
+    /// Mutants are de-duplicated by their stable ID - if the mutant is already present in this report,
+    /// the entry from the other report is dropped. Skipped mutations are de-duplicated as well.
+    pub fn merge(&mut self, other: Report) {
+        let mut known = self
+            .mutants
+            .iter()
+            .map(|m| m.mutant_id.clone())
+            .collect::<HashSet<_>>();
+
+        for entry in other.mutants {
+            if known.insert(entry.mutant_id.clone()) {
+                self.add_entry(entry);
+            } else {
+                trace!("Skipping duplicated mutant {}", entry.mutant_id);
+            }
+        }
+
+        for entry in other.skipped {
+            if !self.skipped.contains(&entry) {
+                self.add_skipped_entry(entry);
+            }
+        }
+
+        if self.package_root.is_none() {
+            self.package_root = other.package_root;
+        }
+
+        for operator in other.operators {
+            if !self.operators.iter().any(|o| o.name == operator.name) {
+                self.operators.push(operator);
+            }
+        }
+    }
+
+    /// Removes the mutants and skipped mutations of the given original files from the report (e.g.
+    /// before regenerating them) and returns the removed mutants.
+    pub fn remove_files(&mut self, files: &[PathBuf]) -> Vec<MutationReport> {
+        let files = files
+            .iter()
+            .map(|file| absolute_path(file))
+            .collect::<HashSet<_>>();
+        let is_removed = |file: &Path| files.contains(&absolute_path(file));
+
+        let (removed, kept) = std::mem::take(&mut self.mutants)
+            .into_iter()
+            .partition(|m| is_removed(&m.original_file));
+        self.mutants = kept;
+        self.skipped.retain(|s| !is_removed(&s.original_file));
+        removed
+    }
+
     /// Saves the `Report` as a JSON file.
     ///
     /// # Errors
@@ -42,20 +162,132 @@ impl Report {
 
         info!("Saving report to {}", path.display());
 
-        serde_json::to_writer_pretty(file, &self).map_err(|e| Error::new(ErrorKind::Other, e))
+        serde_json::to_writer_pretty(file, &self.portable())
+            .map_err(|e| Error::new(ErrorKind::Other, e))
     }
 
     /// Loads the `Report` from a JSON file.
+    /// Reports saved by older versions of the tool are upgraded to the current structure.
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be opened or read from.
+    /// Returns an error if the file cannot be opened or read from, or if the report was created by
+    /// a newer version of the tool.
     pub fn load_from_json_file(path: &Path) -> Result<Self> {
         info!("Reading report from {}", path.display());
 
         let file = std::fs::File::open(path)?;
 
-        serde_json::from_reader(file).map_err(|e| Error::new(ErrorKind::Other, e))
+        let mut report: Self =
+            serde_json::from_reader(file).map_err(|e| Error::new(ErrorKind::Other, e))?;
+        report.migrate()?;
+
+        // Relative paths are restored against the recorded package root.
+        if let Some(root) = report.package_root.clone() {
+            report.map_paths(|path| root.join(path));
+        }
+        Ok(report)
+    }
+
+    /// Records the root directory of the package.
+    ///
+    /// Paths of the files placed under the root are saved relative to it (with the root stored once
+    /// in the report), so the report can be moved across machines and CI runs. Paths outside the
+    /// root (e.g. mutants in a temporary directory) are saved as absolute paths.
+    pub fn set_package_root(&mut self, root: &Path) {
+        self.package_root = Some(absolute_path(root));
+    }
+
+    /// Returns the root directory of the package, if recorded.
+    #[must_use]
+    pub fn package_root(&self) -> Option<&Path> {
+        self.package_root.as_deref()
+    }
+
+    /// Moves all paths placed under the current package root to the new root.
+    ///
+    /// This is useful when the report was created on a different machine or in a different directory
+    /// (e.g. by a CI job) than the one it's used in.
+    pub fn reroot(&mut self, new_root: &Path) {
+        let new_root = absolute_path(new_root);
+        if let Some(old_root) = self.package_root.take() {
+            self.map_paths(|path| {
+                let path = absolute_path(path);
+                path.strip_prefix(&old_root)
+                    .map_or_else(|_| path.clone(), |relative| new_root.join(relative))
+            });
+        }
+        self.package_root = Some(new_root);
+    }
+
+    /// Returns the report with paths relative to the package root (if recorded), as stored in files.
+    /// Tools embedding the report in their own files should serialize this version.
+    #[must_use]
+    pub fn portable(&self) -> Cow<'_, Self> {
+        let Some(root) = &self.package_root else {
+            return Cow::Borrowed(self);
+        };
+
+        let mut report = self.clone();
+        report.map_paths(|path| {
+            let path = absolute_path(path);
+            path.strip_prefix(root)
+                .map_or_else(|_| path.clone(), Path::to_path_buf)
+        });
+        Cow::Owned(report)
+    }
+
+    /// Applies the given function to all file paths stored in the report.
+    fn map_paths(&mut self, f: impl Fn(&Path) -> PathBuf) {
+        for entry in &mut self.mutants {
+            entry.mutant_path = f(&entry.mutant_path);
+            entry.original_file = f(&entry.original_file);
+            entry.prover_log = entry.prover_log.as_deref().map(&f);
+        }
+        for entry in &mut self.skipped {
+            entry.original_file = f(&entry.original_file);
+        }
+    }
+
+    /// Returns the version of the report structure.
+    #[must_use]
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
+    /// Upgrades the report loaded from an older schema version to the current one.
+    fn migrate(&mut self) -> Result<()> {
+        if self.schema_version > REPORT_SCHEMA_VERSION {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Report schema version {} is newer than the supported version {REPORT_SCHEMA_VERSION}",
+                    self.schema_version
+                ),
+            ));
+        }
+
+        // Versions 3 to 9 added optional fields only (line and column numbers of ranges, the operator
+        // catalog, the killing condition, the package root, the prover diagnostics, the prover log
+        // and the triage decision), so no migration is needed.
+        if self.schema_version < 2 {
+            // Version 1 didn't store mutant IDs.
+            for mutant in &mut self.mutants {
+                if mutant.mutant_id.is_empty() {
+                    mutant.mutant_id = mutant.compute_mutant_id();
+                }
+            }
+        }
+
+        if self.schema_version != REPORT_SCHEMA_VERSION {
+            debug!(
+                "Report migrated from schema version {} to {REPORT_SCHEMA_VERSION}",
+                self.schema_version
+            );
+            self.schema_version = REPORT_SCHEMA_VERSION;
+        }
+
+        Ok(())
     }
 
     /// Saves the `Report` as a text file.
@@ -69,6 +301,7 @@ impl Report {
         info!("Saving report to {}", path.display());
 
         for entry in &self.mutants {
+            writeln!(file, "Mutant ID: {}", entry.mutant_id)?;
             writeln!(file, "Mutant path: {}", entry.mutant_path.display())?;
             writeln!(file, "Original file: {}", entry.original_file.display())?;
             writeln!(file, "Module name: {}", entry.module_name)?;
@@ -89,6 +322,22 @@ impl Report {
             writeln!(file, "----------------------------------------")?;
         }
 
+        for entry in &self.skipped {
+            writeln!(file, "Skipped mutation ({})", entry.reason)?;
+            writeln!(file, "Original file: {}", entry.original_file.display())?;
+            writeln!(file, "Module name: {}", entry.module_name)?;
+            writeln!(file, "Function name: {}", entry.function_name)?;
+            writeln!(file, "  Operator: {}", entry.mutation.operator_name)?;
+            writeln!(file, "  Old value: {}", entry.mutation.old_value)?;
+            writeln!(file, "  New value: {}", entry.mutation.new_value)?;
+            writeln!(
+                file,
+                "  Changed place: {}-{}",
+                entry.mutation.changed_place.start, entry.mutation.changed_place.end
+            )?;
+            writeln!(file, "----------------------------------------")?;
+        }
+
         debug!("Report saved to {}", path.display());
 
         Ok(())
@@ -100,11 +349,49 @@ impl Report {
         &self.mutants
     }
 
+    /// Sets the catalog of the mutation operators described in the report.
+    pub fn set_operator_catalog(&mut self, operators: Vec<OperatorCatalogEntry>) {
+        self.operators = operators;
+    }
+
+    /// Returns the catalog of the mutation operators.
+    #[must_use]
+    pub fn get_operator_catalog(&self) -> &[OperatorCatalogEntry] {
+        &self.operators
+    }
+
+    /// Returns the mutations which were not generated.
+    #[must_use]
+    pub fn get_skipped(&self) -> &Vec<SkippedMutation> {
+        &self.skipped
+    }
+
+    /// Returns the mutable vector of `MutationReport` instances (e.g. to update their status).
+    pub fn get_mutants_mut(&mut self) -> &mut Vec<MutationReport> {
+        &mut self.mutants
+    }
+
+    /// Returns the `MutationReport` with the given mutant ID.
+    #[must_use]
+    pub fn get_mutant_by_id(&self, mutant_id: &str) -> Option<&MutationReport> {
+        self.mutants.iter().find(|m| m.mutant_id == mutant_id)
+    }
+
     /// Converts the `Report` to a JSON string.
     #[cfg(test)]
     pub fn to_json(&self) -> serde_json::Result<String> {
-        serde_json::to_string_pretty(&self)
+        serde_json::to_string_pretty(&self.portable())
+    }
+}
+
+/// Makes the path absolute using the current directory, without touching the file system.
+fn absolute_path(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
     }
+    std::env::current_dir()
+        .map(|dir| dir.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
 }
 
 impl Default for Report {
@@ -113,14 +400,95 @@ impl Default for Report {
     }
 }
 
+/// The `OperatorCatalogEntry` struct describes a single mutation operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OperatorCatalogEntry {
+    /// The name of the operator, as used in the mutations.
+    name: String,
+    /// Human readable description of the operator.
+    description: String,
+    /// Example of the code before the mutation.
+    example_original: String,
+    /// Example of the code after the mutation.
+    example_mutated: String,
+}
+
+impl OperatorCatalogEntry {
+    /// Creates a new `OperatorCatalogEntry` instance.
+    #[must_use]
+    pub fn new(
+        name: &str,
+        description: &str,
+        example_original: &str,
+        example_mutated: &str,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            example_original: example_original.to_owned(),
+            example_mutated: example_mutated.to_owned(),
+        }
+    }
+
+    /// Returns the name of the operator.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the description of the operator.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Returns the example transformation (code before and after the mutation).
+    #[must_use]
+    pub fn example(&self) -> (&str, &str) {
+        (&self.example_original, &self.example_mutated)
+    }
+}
+
 /// The `Range` struct represents a range with a start and end.
 /// It is used to represent the location of a mutation inside the source file.
+/// Both values are byte offsets which always lie on UTF-8 character boundaries.
+/// The range can also store the line and column numbers of its start and end (see `Range::locate`).
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub struct Range {
-    /// The start of the range.
+    /// The start of the range (byte offset).
     start: usize,
-    /// The end of the range.
+    /// The end of the range (byte offset).
     end: usize,
+    /// The line and column of the start of the range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start_position: Option<Position>,
+    /// The line and column of the end of the range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end_position: Option<Position>,
+}
+
+/// The `Position` struct represents a place in the source file as 1-based line and column numbers.
+/// Columns are counted in characters (Unicode code points), not bytes.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Position {
+    /// The line number (starting from 1).
+    pub line: usize,
+    /// The column number (starting from 1).
+    pub column: usize,
+}
+
+impl Position {
+    /// Converts the byte offset in the source into the position.
+    /// Returns `None` if the offset is out of bounds or doesn't lie on a character boundary.
+    #[must_use]
+    pub fn from_offset(source: &str, offset: usize) -> Option<Self> {
+        let before = source.get(..offset)?;
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Some(Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        })
+    }
 }
 
 impl Range {
@@ -131,7 +499,42 @@ impl Range {
     #[must_use]
     pub fn new(start: usize, end: usize) -> Self {
         assert!(start <= end);
-        Self { start, end }
+        Self {
+            start,
+            end,
+            start_position: None,
+            end_position: None,
+        }
+    }
+
+    /// Computes the line and column numbers of the range using the source it points into.
+    pub fn locate(&mut self, source: &str) {
+        self.start_position = Position::from_offset(source, self.start);
+        self.end_position = Position::from_offset(source, self.end);
+    }
+
+    /// Returns the line and column of the start of the range (if computed).
+    #[must_use]
+    pub fn start_position(&self) -> Option<Position> {
+        self.start_position
+    }
+
+    /// Returns the line and column of the end of the range (if computed).
+    #[must_use]
+    pub fn end_position(&self) -> Option<Position> {
+        self.end_position
+    }
+
+    /// Returns the start of the range.
+    #[must_use]
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end of the range.
+    #[must_use]
+    pub fn end(&self) -> usize {
+        self.end
     }
 }
 
@@ -172,13 +575,221 @@ impl Mutation {
     pub fn get_operator_name(&self) -> &str {
         &self.operator_name
     }
+
+    /// Returns the location of the modification.
+    #[must_use]
+    pub fn get_changed_place(&self) -> &Range {
+        &self.changed_place
+    }
+
+    /// Computes the line and column numbers of the modification using the original source.
+    pub fn locate(&mut self, original_source: &str) {
+        self.changed_place.locate(original_source);
+    }
+
+    /// Returns the old value.
+    #[must_use]
+    pub fn get_old_value(&self) -> &str {
+        &self.old_value
+    }
+
+    /// Returns the new value.
+    #[must_use]
+    pub fn get_new_value(&self) -> &str {
+        &self.new_value
+    }
+
+    /// Strips the replaced source code from the mutation, keeping the operator and the location.
+    fn anonymize(&mut self) {
+        self.old_value.clear();
+        self.new_value.clear();
+    }
+}
+
+/// The reason why the mutation was skipped.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The mutation is placed in the unreachable code, so the mutant would always survive.
+    Unreachable,
+    /// The verification of the mutant has failed in the sandbox.
+    Sandbox(SandboxFailure),
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SkipReason::Unreachable => write!(f, "unreachable"),
+            SkipReason::Sandbox(failure) => write!(f, "sandbox failure: {failure}"),
+        }
+    }
 }
 
+/// The `SkippedMutation` struct represents a mutation for which the mutant was not generated.
+/// Such mutations are reported separately, so they are not lost.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkippedMutation {
+    /// The path to the original file.
+    original_file: PathBuf,
+    /// The name of the module that the mutation is in.
+    module_name: String,
+    /// The function name that the mutation is in.
+    function_name: String,
+    /// The skipped modification.
+    mutation: Mutation,
+    /// The reason why the mutation was skipped.
+    reason: SkipReason,
+}
+
+impl SkippedMutation {
+    /// Creates a new `SkippedMutation` instance.
+    #[must_use]
+    pub fn new(
+        original_file: &Path,
+        module_name: &str,
+        function_name: &str,
+        mutation: Mutation,
+        reason: SkipReason,
+    ) -> Self {
+        Self {
+            original_file: original_file.to_path_buf(),
+            module_name: module_name.to_owned(),
+            function_name: function_name.to_owned(),
+            mutation,
+            reason,
+        }
+    }
+
+    /// Strips the source code from the skipped mutation if required by the given `detail`.
+    pub fn reduce_detail(&mut self, detail: &ReportDetail) {
+        if detail.anonymize {
+            self.mutation.anonymize();
+        }
+    }
+
+    /// Returns the reason why the mutation was skipped.
+    #[must_use]
+    pub fn reason(&self) -> SkipReason {
+        self.reason
+    }
+}
+
+/// The status of the mutant in its lifecycle.
+///
+/// Mutants are generated by the mutator and their status is later updated by the tools testing them
+/// (e.g. spec-test).
+#[derive(
+    Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MutantStatus {
+    /// The mutant has been generated, but not tested yet.
+    #[default]
+    Generated,
+    /// The mutant is not a valid Move code (e.g. it doesn't compile).
+    Invalid,
+    /// The mutant has been detected by the tests or specification.
+    Killed,
+    /// The mutant has not been detected by the tests or specification.
+    Survived,
+    /// Testing the mutant took too long.
+    Timeout,
+    /// The mutant is equivalent to the original code, so it cannot be killed.
+    Equivalent,
+    /// The mutant has not been tested on purpose.
+    Skipped,
+    /// The mutant survived in the code which is not reached by any specification, so it tells
+    /// about the missing specification rather than a weak one.
+    #[serde(rename = "no-spec")]
+    NoSpec,
+    /// Testing the mutant exceeded the memory limit.
+    #[serde(rename = "out-of-memory")]
+    OutOfMemory,
+}
+
+impl MutantStatus {
+    /// Returns true if the mutant has not been tested yet.
+    #[must_use]
+    pub fn is_generated(&self) -> bool {
+        *self == MutantStatus::Generated
+    }
+}
+
+impl fmt::Display for MutantStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = match self {
+            MutantStatus::Generated => "generated",
+            MutantStatus::Invalid => "invalid",
+            MutantStatus::Killed => "killed",
+            MutantStatus::Survived => "survived",
+            MutantStatus::Timeout => "timeout",
+            MutantStatus::Equivalent => "equivalent",
+            MutantStatus::Skipped => "skipped",
+            MutantStatus::NoSpec => "no-spec",
+            MutantStatus::OutOfMemory => "out-of-memory",
+        };
+        write!(f, "{status}")
+    }
+}
+
+/// Decision made when reviewing a surviving mutant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Triage {
+    /// The specification has to be strengthened to kill the mutant.
+    NeedsSpec,
+    /// The mutant is equivalent to the original code, so it's quarantined.
+    Equivalent,
+    /// The mutant is not worth killing.
+    Ignore,
+}
+
+impl fmt::Display for Triage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let triage = match self {
+            Triage::NeedsSpec => "needs-spec",
+            Triage::Equivalent => "equivalent",
+            Triage::Ignore => "ignore",
+        };
+        write!(f, "{triage}")
+    }
+}
+
+/// Level of detail of the saved reports.
+///
+/// Reports with full diffs can be very big for framework-sized packages, so diffs can be omitted or
+/// truncated and only the surviving mutants can be kept.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReportDetail {
+    /// Don't store the diffs of the mutants.
+    pub omit_diffs: bool,
+    /// Truncate the diffs to the given number of lines.
+    pub max_diff_lines: Option<usize>,
+    /// Keep only the surviving mutants.
+    pub survivors_only: bool,
+    /// Strip the source code (diffs, replaced values and killing conditions), keeping only the
+    /// structural data like operators, spans and statuses.
+    pub anonymize: bool,
+}
+
+impl ReportDetail {
+    /// Returns true if the entry should be kept in the report.
+    #[must_use]
+    pub fn includes(&self, entry: &MutationReport) -> bool {
+        !self.survivors_only || entry.status == MutantStatus::Survived
+    }
+}
+
+/// Number of hex characters used for the mutant ID.
+const MUTANT_ID_LENGTH: usize = 16;
+
 /// The `MutationReport` struct represents an entry in a report.
 /// It contains information about a mutation that was applied to a file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(clippy::module_name_repetitions)]
 pub struct MutationReport {
+    /// The stable identifier of the mutant.
+    #[serde(default)]
+    mutant_id: String,
     /// The path to the mutated file.
     mutant_path: PathBuf,
     /// The path to the original file.
@@ -191,6 +802,21 @@ pub struct MutationReport {
     mutations: Vec<Mutation>,
     /// The diff between the original and mutated file.
     diff: String,
+    /// The status of the mutant. Freshly generated mutants don't store it to keep the report concise.
+    #[serde(default, skip_serializing_if = "MutantStatus::is_generated")]
+    status: MutantStatus,
+    /// The specification condition (or other check) which killed the mutant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    killed_by: Option<String>,
+    /// The diagnostics of the tool which killed the mutant (e.g. the prover errors).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    diagnostics: Option<String>,
+    /// The path to the log of the prover run on the mutant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prover_log: Option<PathBuf>,
+    /// The decision made when reviewing the surviving mutant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    triage: Option<Triage>,
 }
 
 impl MutationReport {
@@ -207,19 +833,103 @@ impl MutationReport {
     ) -> Self {
         let patch = diffy::create_patch(original_source, mutated_source);
         Self {
+            mutant_id: String::new(),
             mutant_path: mutant_path.to_path_buf(),
             original_file: original_file.to_path_buf(),
             module_name: module_name.to_owned(),
             function_name: function_name.to_owned(),
             mutations: vec![],
             diff: patch.to_string(),
+            status: MutantStatus::Generated,
+            killed_by: None,
+            diagnostics: None,
+            prover_log: None,
+            triage: None,
         }
     }
 
     /// Adds a `Mutation` to the `MutationReport`.
+    /// The mutant ID is recalculated as it depends on the applied mutations.
     pub fn add_modification(&mut self, modification: Mutation) {
         trace!("Adding modification to report: {modification:?}");
         self.mutations.push(modification);
+        self.mutant_id = self.compute_mutant_id();
+    }
+
+    /// Computes the mutant ID.
+    /// The ID is a hash of the mutated place and the applied mutations, so it stays the same
+    /// between runs as long as the original source is not modified.
+    fn compute_mutant_id(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(
+            format!(
+                "{}|{}|{}",
+                self.original_file
+                    .file_name()
+                    .map(|f| f.to_string_lossy())
+                    .unwrap_or_default(),
+                self.module_name,
+                self.function_name
+            )
+            .as_bytes(),
+        );
+        for mutation in &self.mutations {
+            hasher.update(
+                format!(
+                    "|{}-{}|{}|{}",
+                    mutation.changed_place.start,
+                    mutation.changed_place.end,
+                    mutation.operator_name,
+                    mutation.new_value
+                )
+                .as_bytes(),
+            );
+        }
+
+        let mut id = format!("{:x}", hasher.finalize());
+        id.truncate(MUTANT_ID_LENGTH);
+        id
+    }
+
+    /// Creates a comment header describing the mutant.
+    ///
+    /// The header is prepended to the generated mutant file so the file is self-describing even when
+    /// separated from the report. It contains the mutant ID, the original file along with its hash
+    /// and all the applied mutations. Old and new values are JSON-encoded as they may span multiple lines.
+    #[must_use]
+    pub fn header(&self, original_source: &str) -> String {
+        let mut header = format!(
+            "// Mutant generated by the move-mutator.\n\
+             // mutant_id: {}\n\
+             // original_file: {}\n\
+             // original_file_sha256: {:x}\n\
+             // module: {}\n\
+             // function: {}\n",
+            self.mutant_id,
+            self.original_file.display(),
+            Sha256::digest(original_source.as_bytes()),
+            self.module_name,
+            self.function_name,
+        );
+
+        for mutation in &self.mutations {
+            header.push_str(&format!(
+                "// mutation: operator={} span={}..{} old={} new={}\n",
+                mutation.operator_name,
+                mutation.changed_place.start,
+                mutation.changed_place.end,
+                serde_json::Value::from(mutation.old_value.as_str()),
+                serde_json::Value::from(mutation.new_value.as_str()),
+            ));
+        }
+
+        header
+    }
+
+    /// Return the mutant ID.
+    #[must_use]
+    pub fn mutant_id(&self) -> &str {
+        &self.mutant_id
     }
 
     /// Return the mutant path.
@@ -251,6 +961,96 @@ impl MutationReport {
     pub fn get_diff(&self) -> &str {
         &self.diff
     }
+
+    /// Omits or truncates the diff (or strips all the source code) according to the given `detail`.
+    pub fn reduce_detail(&mut self, detail: &ReportDetail) {
+        if detail.anonymize {
+            self.killed_by = None;
+            self.diagnostics = None;
+            for mutation in &mut self.mutations {
+                mutation.anonymize();
+            }
+        }
+
+        if detail.omit_diffs || detail.anonymize {
+            self.diff.clear();
+            return;
+        }
+
+        if let Some(max_lines) = detail.max_diff_lines {
+            let lines = self.diff.lines().count();
+            if lines > max_lines {
+                let mut truncated = self
+                    .diff
+                    .split_inclusive('\n')
+                    .take(max_lines)
+                    .collect::<String>();
+                truncated.push_str(&format!("... ({} more lines)\n", lines - max_lines));
+                self.diff = truncated;
+            }
+        }
+    }
+
+    /// Return the modifications applied to the file.
+    #[must_use]
+    pub fn get_mutations(&self) -> &[Mutation] {
+        &self.mutations
+    }
+
+    /// Returns the status of the mutant.
+    #[must_use]
+    pub fn status(&self) -> MutantStatus {
+        self.status
+    }
+
+    /// Sets the status of the mutant.
+    pub fn set_status(&mut self, status: MutantStatus) {
+        self.status = status;
+    }
+
+    /// Returns the specification condition (or other check) which killed the mutant, if known.
+    #[must_use]
+    pub fn killed_by(&self) -> Option<&str> {
+        self.killed_by.as_deref()
+    }
+
+    /// Records the specification condition (or other check) which killed the mutant.
+    pub fn set_killed_by(&mut self, killed_by: impl Into<String>) {
+        self.killed_by = Some(killed_by.into());
+    }
+
+    /// Returns the diagnostics of the tool which killed the mutant, if recorded.
+    #[must_use]
+    pub fn diagnostics(&self) -> Option<&str> {
+        self.diagnostics.as_deref()
+    }
+
+    /// Records the diagnostics of the tool which killed the mutant.
+    pub fn set_diagnostics(&mut self, diagnostics: impl Into<String>) {
+        self.diagnostics = Some(diagnostics.into());
+    }
+
+    /// Returns the path to the log of the prover run on the mutant, if saved.
+    #[must_use]
+    pub fn prover_log(&self) -> Option<&Path> {
+        self.prover_log.as_deref()
+    }
+
+    /// Records the path to the log of the prover run on the mutant.
+    pub fn set_prover_log(&mut self, path: impl Into<PathBuf>) {
+        self.prover_log = Some(path.into());
+    }
+
+    /// Returns the decision made when reviewing the mutant, if reviewed.
+    #[must_use]
+    pub fn triage(&self) -> Option<Triage> {
+        self.triage
+    }
+
+    /// Records the decision made when reviewing the mutant.
+    pub fn set_triage(&mut self, triage: Triage) {
+        self.triage = Some(triage);
+    }
 }
 
 #[cfg(test)]
@@ -261,7 +1061,10 @@ mod tests {
     #[test]
     fn test_report() {
         let mut report = Report::new();
-        assert_eq!(report.to_json().unwrap(), "{\n  \"mutants\": []\n}");
+        assert_eq!(
+            report.to_json().unwrap(),
+            "{\n  \"schema_version\": 9,\n  \"mutants\": []\n}"
+        );
 
         let range = Range::new(0, 10);
         let modification = Mutation::new(
@@ -283,10 +1086,52 @@ mod tests {
         report.add_entry(report_entry.clone());
         assert_eq!(
             report.to_json().unwrap(),
-            "{\n  \"mutants\": [\n    {\n      \"mutant_path\": \"file\",\n      \"original_file\": \"original_file\",\n      \"module_name\": \"module\",\n      \"function_name\": \"function\",\n      \"mutations\": [\n        {\n          \"changed_place\": {\n            \"start\": 0,\n            \"end\": 10\n          },\n          \"operator_name\": \"operator\",\n          \"old_value\": \"old\",\n          \"new_value\": \"new\"\n        }\n      ],\n      \"diff\": \"--- original\\n+++ modified\\n@@ -1 +1 @@\\n-diff\\n+\\n\"\n    }\n  ]\n}"
+            "{\n  \"schema_version\": 9,\n  \"mutants\": [\n    {\n      \"mutant_id\": \"6f30041a6bad8bc5\",\n      \"mutant_path\": \"file\",\n      \"original_file\": \"original_file\",\n      \"module_name\": \"module\",\n      \"function_name\": \"function\",\n      \"mutations\": [\n        {\n          \"changed_place\": {\n            \"start\": 0,\n            \"end\": 10\n          },\n          \"operator_name\": \"operator\",\n          \"old_value\": \"old\",\n          \"new_value\": \"new\"\n        }\n      ],\n      \"diff\": \"--- original\\n+++ modified\\n@@ -1 +1 @@\\n-diff\\n+\\n\"\n    }\n  ]\n}"
         );
     }
 
+    #[test]
+    fn legacy_report_is_migrated() {
+        let legacy = r#"{
+            "mutants": [{
+                "mutant_path": "file",
+                "original_file": "original_file",
+                "module_name": "module",
+                "function_name": "function",
+                "mutations": [{
+                    "changed_place": { "start": 0, "end": 10 },
+                    "operator_name": "operator",
+                    "old_value": "old",
+                    "new_value": "new"
+                }],
+                "diff": ""
+            }]
+        }"#;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        fs::write(&path, legacy).unwrap();
+
+        let report = Report::load_from_json_file(&path).unwrap();
+        assert_eq!(report.schema_version(), REPORT_SCHEMA_VERSION);
+        assert_eq!(report.get_mutants()[0].mutant_id(), "6f30041a6bad8bc5");
+    }
+
+    #[test]
+    fn newer_report_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        fs::write(
+            &path,
+            format!(
+                "{{\"schema_version\": {}, \"mutants\": []}}",
+                REPORT_SCHEMA_VERSION + 1
+            ),
+        )
+        .unwrap();
+
+        assert!(Report::load_from_json_file(&path).is_err());
+    }
+
     #[test]
     fn test_range() {
         let range = Range::new(0, 10);
@@ -296,6 +1141,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn range_is_located_in_source() {
+        let source = "ab\nżc+d\n";
+        let mut range = Range::new(6, 7);
+        range.locate(source);
+        assert_eq!(
+            range.start_position(),
+            Some(Position { line: 2, column: 3 })
+        );
+        assert_eq!(range.end_position(), Some(Position { line: 2, column: 4 }));
+        assert_eq!(
+            serde_json::to_string(&range).unwrap(),
+            "{\"start\":6,\"end\":7,\"start_position\":{\"line\":2,\"column\":3},\"end_position\":{\"line\":2,\"column\":4}}"
+        );
+
+        assert_eq!(
+            Position::from_offset(source, 0),
+            Some(Position { line: 1, column: 1 })
+        );
+        assert_eq!(Position::from_offset(source, 4), None);
+    }
+
     #[test]
     fn test_modification() {
         let range = Range::new(0, 10);
@@ -308,6 +1175,322 @@ mod tests {
         assert_eq!(serde_json::to_string(&modification).unwrap(), "{\"changed_place\":{\"start\":0,\"end\":10},\"operator_name\":\"operator\",\"old_value\":\"old\",\"new_value\":\"new\"}");
     }
 
+    #[test]
+    fn mutant_id_is_stable_and_depends_on_mutations() {
+        let new_entry = |new_value: &str| {
+            let mut entry = MutationReport::new(
+                Path::new("file"),
+                Path::new("sources/original_file.move"),
+                "module",
+                "function",
+                "\n",
+                "diff\n",
+            );
+            entry.add_modification(Mutation::new(
+                Range::new(0, 10),
+                "operator".to_string(),
+                "old".to_string(),
+                new_value.to_string(),
+            ));
+            entry
+        };
+
+        let entry = new_entry("new");
+        assert_eq!(entry.mutant_id().len(), MUTANT_ID_LENGTH);
+        assert_eq!(entry.mutant_id(), new_entry("new").mutant_id());
+        assert_ne!(entry.mutant_id(), new_entry("other").mutant_id());
+
+        let mut report = Report::new();
+        report.add_entry(entry.clone());
+        assert!(report.get_mutant_by_id(entry.mutant_id()).is_some());
+        assert!(report.get_mutant_by_id("unknown").is_none());
+    }
+
+    #[test]
+    fn status_is_serialized_only_after_update() {
+        let mut entry = MutationReport::new(
+            Path::new("file"),
+            Path::new("original_file"),
+            "module",
+            "function",
+            "\n",
+            "diff\n",
+        );
+        assert_eq!(entry.status(), MutantStatus::Generated);
+        assert!(!serde_json::to_string(&entry).unwrap().contains("status"));
+
+        entry.set_status(MutantStatus::Survived);
+        let json = serde_json::to_string(&entry).unwrap();
+        assert!(json.contains("\"status\":\"survived\""));
+
+        let loaded: MutationReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.status(), MutantStatus::Survived);
+    }
+
+    #[test]
+    fn merge_deduplicates_mutants_by_id() {
+        let new_entry = |new_value: &str| {
+            let mut entry = MutationReport::new(
+                Path::new("file"),
+                Path::new("sources/original_file.move"),
+                "module",
+                "function",
+                "\n",
+                "diff\n",
+            );
+            entry.add_modification(Mutation::new(
+                Range::new(0, 10),
+                "operator".to_string(),
+                "old".to_string(),
+                new_value.to_string(),
+            ));
+            entry
+        };
+        let skipped = SkippedMutation::new(
+            Path::new("sources/original_file.move"),
+            "module",
+            "function",
+            Mutation::new(
+                Range::new(0, 1),
+                "operator".to_string(),
+                "old".to_string(),
+                "new".to_string(),
+            ),
+            SkipReason::Unreachable,
+        );
+
+        let mut first = Report::new();
+        first.add_entry(new_entry("a"));
+        first.add_entry(new_entry("b"));
+        first.add_skipped_entry(skipped.clone());
+
+        let mut second = Report::new();
+        second.add_entry(new_entry("b"));
+        second.add_entry(new_entry("c"));
+        second.add_skipped_entry(skipped);
+
+        first.merge(second);
+        assert_eq!(first.get_mutants().len(), 3);
+        assert_eq!(first.get_skipped().len(), 1);
+        assert!(first.get_mutant_by_id(new_entry("c").mutant_id()).is_some());
+    }
+
+    #[test]
+    fn remove_files_drops_entries_of_given_files() {
+        let new_entry = |file: &str| {
+            MutationReport::new(
+                Path::new("file"),
+                Path::new(file),
+                "module",
+                "function",
+                "\n",
+                "diff\n",
+            )
+        };
+        let mut report = Report::new();
+        report.add_entry(new_entry("sources/Sum.move"));
+        report.add_entry(new_entry("sources/Mul.move"));
+        report.add_skipped_entry(SkippedMutation::new(
+            Path::new("sources/Sum.move"),
+            "module",
+            "function",
+            Mutation::new(
+                Range::new(0, 1),
+                "operator".to_string(),
+                "old".to_string(),
+                "new".to_string(),
+            ),
+            SkipReason::Unreachable,
+        ));
+
+        let sum = std::env::current_dir().unwrap().join("sources/Sum.move");
+        let removed = report.remove_files(&[sum]);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(
+            removed[0].original_file_path(),
+            Path::new("sources/Sum.move")
+        );
+        assert_eq!(report.get_mutants().len(), 1);
+        assert_eq!(
+            report.get_mutants()[0].original_file_path(),
+            Path::new("sources/Mul.move")
+        );
+        assert!(report.get_skipped().is_empty());
+    }
+
+    #[test]
+    fn paths_are_stored_relative_to_package_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("package");
+        let mut report = Report::new();
+        report.add_entry(MutationReport::new(
+            Path::new("/elsewhere/mutant.move"),
+            &root.join("sources/Sum.move"),
+            "Sum",
+            "sum",
+            "\n",
+            "diff\n",
+        ));
+        report.set_package_root(&root);
+
+        let json = report.to_json().unwrap();
+        assert!(json.contains("\"original_file\": \"sources/Sum.move\""));
+        assert!(json.contains("\"mutant_path\": \"/elsewhere/mutant.move\""));
+
+        let path = dir.path().join("report.json");
+        report.save_to_json_file(&path).unwrap();
+        let mut loaded = Report::load_from_json_file(&path).unwrap();
+        assert_eq!(loaded.package_root(), Some(root.as_path()));
+        assert_eq!(
+            loaded.get_mutants()[0].original_file_path(),
+            &root.join("sources/Sum.move")
+        );
+
+        loaded.reroot(Path::new("/ci/package"));
+        assert_eq!(
+            loaded.get_mutants()[0].original_file_path(),
+            Path::new("/ci/package/sources/Sum.move")
+        );
+        assert_eq!(
+            loaded.get_mutants()[0].mutant_path(),
+            Path::new("/elsewhere/mutant.move")
+        );
+    }
+
+    #[test]
+    fn reduce_detail_limits_report_size() {
+        let new_entry = |status: MutantStatus| {
+            let mut entry = MutationReport::new(
+                Path::new("file"),
+                Path::new("original_file"),
+                "module",
+                "function",
+                "a\nb\nc\n",
+                "x\ny\nz\n",
+            );
+            entry.set_status(status);
+            entry
+        };
+
+        let mut entry = new_entry(MutantStatus::Survived);
+        let full_diff = entry.get_diff().to_owned();
+        entry.reduce_detail(&ReportDetail {
+            max_diff_lines: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(
+            entry.get_diff(),
+            format!(
+                "--- original\n+++ modified\n... ({} more lines)\n",
+                full_diff.lines().count() - 2
+            )
+        );
+
+        let mut report = Report::new();
+        report.add_entry(new_entry(MutantStatus::Survived));
+        report.add_entry(new_entry(MutantStatus::Killed));
+        report.reduce_detail(&ReportDetail {
+            omit_diffs: true,
+            survivors_only: true,
+            ..Default::default()
+        });
+        assert_eq!(report.get_mutants().len(), 1);
+        assert_eq!(report.get_mutants()[0].status(), MutantStatus::Survived);
+        assert!(report.get_mutants()[0].get_diff().is_empty());
+    }
+
+    #[test]
+    fn anonymize_strips_source_code() {
+        let mut entry = MutationReport::new(
+            Path::new("file"),
+            Path::new("original_file"),
+            "module",
+            "function",
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(2, 3),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "-".to_string(),
+        ));
+        entry.set_status(MutantStatus::Killed);
+        entry.set_killed_by("post-condition does not hold (ensures result == x + y;)");
+        entry.set_diagnostics("error: post-condition does not hold");
+        let mutant_id = entry.mutant_id().to_owned();
+
+        let mut report = Report::new();
+        report.add_entry(entry);
+        report.reduce_detail(&ReportDetail {
+            anonymize: true,
+            ..Default::default()
+        });
+
+        let entry = &report.get_mutants()[0];
+        assert_eq!(entry.mutant_id(), mutant_id);
+        assert_eq!(entry.status(), MutantStatus::Killed);
+        assert!(entry.get_diff().is_empty());
+        assert!(entry.killed_by().is_none());
+        assert!(entry.diagnostics().is_none());
+        let mutation = &entry.get_mutations()[0];
+        assert_eq!(mutation.get_operator_name(), "binary_operator_replacement");
+        assert_eq!(mutation.get_changed_place().start(), 2);
+        assert!(mutation.get_old_value().is_empty() && mutation.get_new_value().is_empty());
+    }
+
+    #[test]
+    fn header_describes_the_mutant() {
+        let mut entry = MutationReport::new(
+            Path::new("file"),
+            Path::new("sources/original_file.move"),
+            "module",
+            "function",
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(2, 3),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "-\n".to_string(),
+        ));
+
+        let header = entry.header("x + y\n");
+        assert!(header.lines().all(|l| l.starts_with("// ")));
+        assert!(header.contains(&format!("// mutant_id: {}\n", entry.mutant_id())));
+        assert!(header.contains("// original_file: sources/original_file.move\n"));
+        assert!(header.contains(&format!(
+            "// original_file_sha256: {:x}\n",
+            Sha256::digest(b"x + y\n")
+        )));
+        assert!(header.contains(
+            "// mutation: operator=binary_operator_replacement span=2..3 old=\"+\" new=\"-\\n\"\n"
+        ));
+    }
+
+    #[test]
+    fn killed_by_is_stored_only_when_known() {
+        let mut entry = MutationReport::new(
+            Path::new("file"),
+            Path::new("original_file"),
+            "module",
+            "function",
+            "\n",
+            "diff\n",
+        );
+        assert!(!serde_json::to_string(&entry).unwrap().contains("killed_by"));
+
+        entry.set_status(MutantStatus::Killed);
+        entry.set_killed_by("post-condition does not hold (ensures result == x + y;)");
+        let json = serde_json::to_string(&entry).unwrap();
+        let loaded: MutationReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            loaded.killed_by(),
+            Some("post-condition does not hold (ensures result == x + y;)")
+        );
+    }
+
     #[test]
     fn saves_report_as_text_file_successfully() {
         let mut report = Report::new();
@@ -335,6 +1518,7 @@ mod tests {
         let mut file = fs::File::open(path).unwrap();
         let mut contents = String::new();
         file.read_to_string(&mut contents).unwrap();
+        assert!(contents.contains("Mutant ID: "));
         assert!(contents.contains("Mutant path: file"));
         assert!(contents.contains("Original file: original_file"));
         assert!(contents.contains("Module name: module"));
@@ -0,0 +1,139 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{MutationReport, Position, Report};
+use std::path::{Path, PathBuf};
+
+impl Report {
+    /// Creates GitHub Actions workflow commands (`::warning ...`) for all mutations in the report.
+    ///
+    /// Printing the commands in a GitHub Actions job displays the mutants as annotations inline in the
+    /// PR diff, so the report is meant to contain surviving mutants only. File paths are made relative
+    /// to the `GITHUB_WORKSPACE` directory (or the current directory if not set), as required by GitHub.
+    #[must_use]
+    pub fn to_github_annotations(&self) -> Vec<String> {
+        let root = std::env::var_os("GITHUB_WORKSPACE")
+            .map(PathBuf::from)
+            .or_else(|| std::env::current_dir().ok());
+
+        self.mutants
+            .iter()
+            .flat_map(|entry| github_annotations(entry, root.as_deref()))
+            .collect()
+    }
+}
+
+/// Creates annotations for all mutations of the given mutant.
+fn github_annotations(entry: &MutationReport, root: Option<&Path>) -> Vec<String> {
+    let file = root
+        .and_then(|root| entry.original_file.strip_prefix(root).ok())
+        .unwrap_or(&entry.original_file)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    // Source is read only if the positions are not stored in the report.
+    let mut source = None;
+
+    entry
+        .mutations
+        .iter()
+        .map(|mutation| {
+            let range = &mutation.changed_place;
+            let mut position = |stored: Option<Position>, offset: usize| {
+                stored.or_else(|| {
+                    let source = source.get_or_insert_with(|| {
+                        std::fs::read_to_string(&entry.original_file).unwrap_or_default()
+                    });
+                    Position::from_offset(source, offset)
+                })
+            };
+            let start = position(range.start_position, range.start);
+            let end = position(range.end_position, range.end);
+
+            let mut properties = vec![format!("file={}", escape_property(&file))];
+            if let Some(start) = start {
+                properties.push(format!("line={}", start.line));
+                properties.push(format!("col={}", start.column));
+            }
+            if let Some(end) = end {
+                properties.push(format!("endLine={}", end.line));
+                properties.push(format!("endColumn={}", end.column));
+            }
+            properties.push(format!(
+                "title={}",
+                escape_property(&format!("Surviving mutant {}", entry.mutant_id))
+            ));
+
+            format!(
+                "::warning {}::{}",
+                properties.join(","),
+                escape_data(&format!(
+                    "Surviving mutant {} ({}): `{}` replaced with `{}` in {}::{}",
+                    entry.mutant_id,
+                    mutation.operator_name,
+                    mutation.old_value,
+                    mutation.new_value,
+                    entry.module_name,
+                    entry.function_name
+                ))
+            )
+        })
+        .collect()
+}
+
+/// Escapes the message of the workflow command.
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes the property value of the workflow command.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Mutation, Range};
+
+    #[test]
+    fn annotation_is_created_for_each_mutation() {
+        let source =
+            "module 0x42::Sum {\n    fun sum(x: u64, y: u64): u64 {\n        x + y\n    }\n}\n";
+        let mut entry = MutationReport::new(
+            Path::new("Sum_mut0.move"),
+            Path::new("/ws/sources/Sum.move"),
+            "Sum",
+            "sum",
+            &source.replace('+', "-"),
+            source,
+        );
+        let mut mutation = Mutation::new(
+            Range::new(64, 65),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "-\n".to_string(),
+        );
+        mutation.locate(source);
+        entry.add_modification(mutation);
+
+        let annotations = github_annotations(&entry, Some(Path::new("/ws")));
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(
+            annotations[0],
+            format!(
+                "::warning file=sources/Sum.move,line=3,col=11,endLine=3,endColumn=12,title=Surviving mutant {id}::Surviving mutant {id} (binary_operator_replacement): `+` replaced with `-%0A` in Sum::sum",
+                id = entry.mutant_id()
+            )
+        );
+    }
+
+    #[test]
+    fn properties_are_escaped() {
+        assert_eq!(escape_property("a:b,c%\n"), "a%3Ab%2Cc%25%0A");
+        assert_eq!(escape_data("a:b,c%\n"), "a:b,c%25%0A");
+    }
+}
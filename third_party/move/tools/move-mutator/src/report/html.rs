@@ -0,0 +1,367 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{MutantStatus, MutationReport, Report};
+use diffy::{Line, Patch};
+use std::{fmt::Write, io::Result, path::Path};
+
+/// Keywords of the Move language highlighted in the HTML report.
+const MOVE_KEYWORDS: &[&str] = &[
+    "abort",
+    "acquires",
+    "as",
+    "break",
+    "const",
+    "continue",
+    "copy",
+    "else",
+    "false",
+    "friend",
+    "fun",
+    "has",
+    "if",
+    "inline",
+    "let",
+    "loop",
+    "module",
+    "move",
+    "mut",
+    "native",
+    "public",
+    "return",
+    "script",
+    "spec",
+    "struct",
+    "true",
+    "use",
+    "while",
+    "assert",
+    "ensures",
+    "requires",
+    "aborts_if",
+    "invariant",
+    "modifies",
+    "pragma",
+    "schema",
+    "include",
+    "apply",
+    "old",
+];
+
+/// Styles of the HTML report.
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+.mutant { border: 1px solid #ccc; border-radius: 4px; margin-bottom: 1.5em; }
+.mutant h2 { font-size: 1em; margin: 0; padding: 0.5em; background: #f6f8fa; }
+.status { float: right; }
+.survived .status { color: #e05d44; }
+.killed .status { color: #4c1; }
+table.diff { border-collapse: collapse; width: 100%; table-layout: fixed; font-family: monospace; }
+table.diff td { padding: 0 0.5em; white-space: pre-wrap; vertical-align: top; }
+table.diff td.num { width: 3em; text-align: right; color: #999; }
+table.diff th { text-align: left; padding: 0.25em 0.5em; background: #fafbfc; }
+td.del { background: #ffebe9; }
+td.ins { background: #e6ffec; }
+td.hunk { color: #999; background: #f6f8fa; }
+.kw { color: #cf222e; font-weight: bold; }
+.num-lit { color: #0550ae; }
+.str { color: #0a3069; }
+.comment { color: #6e7781; font-style: italic; }
+";
+
+impl Report {
+    /// Converts the `Report` into a standalone HTML page.
+    ///
+    /// Each mutant is shown with the original and mutated code side by side (rebuilt from the mutant
+    /// diff) with syntax highlighting for Move, which is easier to review than unified diffs, especially
+    /// for operators changing multiple lines like statement deletion. The mutant status is shown if known.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<title>Mutation testing report</title>\n");
+        let _ = writeln!(html, "<style>\n{STYLE}</style>\n</head>\n<body>");
+        let _ = writeln!(
+            html,
+            "<h1>Mutation testing report</h1>\n<p>{} mutants</p>",
+            self.mutants.len()
+        );
+
+        for entry in &self.mutants {
+            html_mutant(&mut html, entry);
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// Saves the `Report` as an HTML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written to.
+    pub fn save_to_html_file(&self, path: &Path) -> Result<()> {
+        info!("Saving HTML report to {}", path.display());
+        std::fs::write(path, self.to_html())
+    }
+}
+
+/// Renders a single mutant with its side-by-side diff.
+fn html_mutant(html: &mut String, entry: &MutationReport) {
+    let _ = writeln!(
+        html,
+        "<div class=\"mutant {status}\" id=\"{id}\">\n<h2>{id} &mdash; {file} ({module}::{function})\
+         {status_label}</h2>",
+        status = entry.status,
+        id = escape_html(&entry.mutant_id),
+        file = escape_html(&entry.original_file.to_string_lossy()),
+        module = escape_html(&entry.module_name),
+        function = escape_html(&entry.function_name),
+        status_label = if entry.status == MutantStatus::Generated {
+            String::new()
+        } else {
+            format!("<span class=\"status\">{}</span>", entry.status)
+        },
+    );
+
+    html.push_str("<ul>\n");
+    for mutation in &entry.mutations {
+        let _ = writeln!(
+            html,
+            "<li>{}: <code>{}</code> &rarr; <code>{}</code></li>",
+            escape_html(&mutation.operator_name),
+            escape_html(&mutation.old_value),
+            escape_html(&mutation.new_value)
+        );
+    }
+    html.push_str("</ul>\n");
+
+    match Patch::from_str(&entry.diff) {
+        Ok(patch) if !patch.hunks().is_empty() => html_side_by_side(html, &patch),
+        _ => html.push_str("<p><em>Diff is not available.</em></p>\n"),
+    }
+
+    html.push_str("</div>\n");
+}
+
+/// Renders the patch as a table with the original code on the left and the mutated code on the right.
+fn html_side_by_side(html: &mut String, patch: &Patch<str>) {
+    html.push_str("<table class=\"diff\">\n<tr><th colspan=\"2\">Original</th><th colspan=\"2\">Mutant</th></tr>\n");
+
+    for hunk in patch.hunks() {
+        let _ = writeln!(
+            html,
+            "<tr><td class=\"hunk\" colspan=\"4\">@@ -{} +{} @@</td></tr>",
+            hunk.old_range(),
+            hunk.new_range()
+        );
+
+        let mut old_line = hunk.old_range().start();
+        let mut new_line = hunk.new_range().start();
+        let mut deleted = Vec::new();
+        let mut inserted = Vec::new();
+
+        for line in hunk.lines() {
+            match line {
+                Line::Delete(text) => deleted.push(*text),
+                Line::Insert(text) => inserted.push(*text),
+                Line::Context(text) => {
+                    flush_changes(
+                        html,
+                        &mut deleted,
+                        &mut inserted,
+                        &mut old_line,
+                        &mut new_line,
+                    );
+                    html_row(html, Some((old_line, text, "")), Some((new_line, text, "")));
+                    old_line += 1;
+                    new_line += 1;
+                },
+            }
+        }
+        flush_changes(
+            html,
+            &mut deleted,
+            &mut inserted,
+            &mut old_line,
+            &mut new_line,
+        );
+    }
+
+    html.push_str("</table>\n");
+}
+
+/// Renders the block of changed lines, pairing deleted lines with the inserted ones.
+fn flush_changes(
+    html: &mut String,
+    deleted: &mut Vec<&str>,
+    inserted: &mut Vec<&str>,
+    old_line: &mut usize,
+    new_line: &mut usize,
+) {
+    for index in 0..deleted.len().max(inserted.len()) {
+        let left = deleted.get(index).map(|text| {
+            *old_line += 1;
+            (*old_line - 1, *text, "del")
+        });
+        let right = inserted.get(index).map(|text| {
+            *new_line += 1;
+            (*new_line - 1, *text, "ins")
+        });
+        html_row(html, left, right);
+    }
+    deleted.clear();
+    inserted.clear();
+}
+
+/// Renders a single row of the side-by-side table. Each side is given as the line number, the line
+/// and the CSS class (or `None` if the side is empty).
+fn html_row(
+    html: &mut String,
+    left: Option<(usize, &str, &str)>,
+    right: Option<(usize, &str, &str)>,
+) {
+    html.push_str("<tr>");
+    for side in [left, right] {
+        match side {
+            Some((number, text, class)) => {
+                let _ = write!(
+                    html,
+                    "<td class=\"num\">{number}</td><td class=\"{class}\">{}</td>",
+                    highlight_move(text.trim_end_matches('\n'))
+                );
+            },
+            None => html.push_str("<td class=\"num\"></td><td></td>"),
+        }
+    }
+    html.push_str("</tr>\n");
+}
+
+/// Highlights a single line of Move code. The line is HTML-escaped.
+fn highlight_move(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let chars = line.char_indices().collect::<Vec<_>>();
+    let mut index = 0;
+
+    let span = |result: &mut String, class: &str, text: &str| {
+        let _ = write!(
+            result,
+            "<span class=\"{class}\">{}</span>",
+            escape_html(text)
+        );
+    };
+
+    while index < chars.len() {
+        let (start, c) = chars[index];
+        let end_of = |index: usize| chars.get(index).map_or(line.len(), |(offset, _)| *offset);
+
+        if line[start..].starts_with("//") {
+            span(&mut result, "comment", &line[start..]);
+            break;
+        }
+
+        if c == '"' || (matches!(c, 'b' | 'x') && line[start..].chars().nth(1) == Some('"')) {
+            let mut end = index + if c == '"' { 1 } else { 2 };
+            while end < chars.len() && chars[end].1 != '"' {
+                end += if chars[end].1 == '\\' { 2 } else { 1 };
+            }
+            let end = (end + 1).min(chars.len());
+            span(&mut result, "str", &line[start..end_of(end)]);
+            index = end;
+            continue;
+        }
+
+        if c.is_ascii_alphanumeric() || c == '_' {
+            let mut end = index;
+            while end < chars.len() && (chars[end].1.is_ascii_alphanumeric() || chars[end].1 == '_')
+            {
+                end += 1;
+            }
+            let word = &line[start..end_of(end)];
+            if c.is_ascii_digit() {
+                span(&mut result, "num-lit", word);
+            } else if MOVE_KEYWORDS.contains(&word) {
+                span(&mut result, "kw", word);
+            } else {
+                result.push_str(&escape_html(word));
+            }
+            index = end;
+            continue;
+        }
+
+        result.push_str(&escape_html(&line[start..end_of(index + 1)]));
+        index += 1;
+    }
+
+    result
+}
+
+/// Escapes the characters which have a special meaning in HTML.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Mutation, Range};
+
+    const ORIGINAL: &str = "fun sum(x: u64): u64 {\n    let y = 1;\n    x + y\n}\n";
+    const MUTATED: &str = "fun sum(x: u64): u64 {\n    x + y\n}\n";
+
+    #[test]
+    fn highlight_move_marks_tokens() {
+        assert_eq!(
+            highlight_move("let x = 10; // a < b"),
+            "<span class=\"kw\">let</span> x = <span class=\"num-lit\">10</span>; \
+             <span class=\"comment\">// a &lt; b</span>"
+        );
+        assert_eq!(
+            highlight_move("b\"a\\\"b\" x"),
+            "<span class=\"str\">b&quot;a\\&quot;b&quot;</span> x"
+        );
+    }
+
+    #[test]
+    fn html_shows_original_and_mutant_side_by_side() {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            "Sum",
+            "sum",
+            MUTATED,
+            ORIGINAL,
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(27, 42),
+            "delete_statement".to_string(),
+            "let y = 1;".to_string(),
+            String::new(),
+        ));
+        entry.set_status(MutantStatus::Survived);
+
+        let mut report = Report::new();
+        report.add_entry(entry);
+
+        let html = report.to_html();
+        assert!(html.contains("<div class=\"mutant survived\""));
+        assert!(html.contains("<span class=\"status\">survived</span>"));
+        assert!(html.contains(
+            "<tr><td class=\"num\">2</td><td class=\"del\">    <span class=\"kw\">let</span> y = \
+             <span class=\"num-lit\">1</span>;</td><td class=\"num\"></td><td></td></tr>"
+        ));
+        assert!(html.contains(
+            "<td class=\"num\">3</td><td class=\"\">    x + y</td><td class=\"num\">2</td>"
+        ));
+    }
+}
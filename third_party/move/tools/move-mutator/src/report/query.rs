@@ -0,0 +1,223 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{MutantStatus, MutationReport, Report};
+use std::{collections::BTreeMap, path::Path};
+
+/// A view over the subset of mutants from the `Report`.
+///
+/// Views are created with `Report::query` and narrowed down with the filter methods, e.g.
+/// `report.query().module("Sum").status(MutantStatus::Survived)`. They can be grouped by any of
+/// the supported keys, so consumers don't need to iterate over the mutants on their own.
+#[derive(Debug, Clone, Default)]
+pub struct MutantView<'a> {
+    mutants: Vec<&'a MutationReport>,
+}
+
+impl<'a> MutantView<'a> {
+    /// Keeps the mutants placed in the given original file.
+    #[must_use]
+    pub fn file(self, path: &Path) -> Self {
+        self.filter(|m| m.original_file == path)
+    }
+
+    /// Keeps the mutants created by the given operator.
+    #[must_use]
+    pub fn operator(self, operator: &str) -> Self {
+        self.filter(|m| m.mutations.iter().any(|mt| mt.operator_name == operator))
+    }
+
+    /// Keeps the mutants with the given status.
+    #[must_use]
+    pub fn status(self, status: MutantStatus) -> Self {
+        self.filter(|m| m.status == status)
+    }
+
+    /// Keeps the mutants placed in the given module.
+    #[must_use]
+    pub fn module(self, module: &str) -> Self {
+        self.filter(|m| m.module_name == module)
+    }
+
+    /// Keeps the mutants placed in the given function.
+    #[must_use]
+    pub fn function(self, module: &str, function: &str) -> Self {
+        self.filter(|m| m.module_name == module && m.function_name == function)
+    }
+
+    /// Keeps the mutants matching the predicate.
+    #[must_use]
+    pub fn filter<F>(mut self, mut predicate: F) -> Self
+    where
+        F: FnMut(&MutationReport) -> bool,
+    {
+        self.mutants.retain(|m| predicate(m));
+        self
+    }
+
+    /// Groups the mutants by the original file.
+    #[must_use]
+    pub fn group_by_file(&self) -> BTreeMap<&'a Path, MutantView<'a>> {
+        self.group_by(|m| m.original_file.as_path())
+    }
+
+    /// Groups the mutants by the operator. Mutants with multiple mutations are put into the group of
+    /// each operator they use.
+    #[must_use]
+    pub fn group_by_operator(&self) -> BTreeMap<&'a str, MutantView<'a>> {
+        let mut groups: BTreeMap<&'a str, MutantView<'a>> = BTreeMap::new();
+        for mutant in &self.mutants {
+            let mut operators = mutant
+                .mutations
+                .iter()
+                .map(|m| m.operator_name.as_str())
+                .collect::<Vec<_>>();
+            operators.sort_unstable();
+            operators.dedup();
+            for operator in operators {
+                groups.entry(operator).or_default().mutants.push(mutant);
+            }
+        }
+        groups
+    }
+
+    /// Groups the mutants by the status.
+    #[must_use]
+    pub fn group_by_status(&self) -> BTreeMap<MutantStatus, MutantView<'a>> {
+        self.group_by(|m| m.status)
+    }
+
+    /// Groups the mutants by the module.
+    #[must_use]
+    pub fn group_by_module(&self) -> BTreeMap<&'a str, MutantView<'a>> {
+        self.group_by(|m| m.module_name.as_str())
+    }
+
+    /// Returns the number of mutants in the view.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mutants.len()
+    }
+
+    /// Returns true if there are no mutants in the view.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mutants.is_empty()
+    }
+
+    /// Returns the iterator over the mutants in the view.
+    pub fn iter(&self) -> impl Iterator<Item = &'a MutationReport> + '_ {
+        self.mutants.iter().copied()
+    }
+
+    /// Returns the mutants in the view.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<&'a MutationReport> {
+        self.mutants
+    }
+
+    fn group_by<K, F>(&self, key: F) -> BTreeMap<K, MutantView<'a>>
+    where
+        K: Ord,
+        F: Fn(&'a MutationReport) -> K,
+    {
+        let mut groups: BTreeMap<K, MutantView<'a>> = BTreeMap::new();
+        for mutant in &self.mutants {
+            groups.entry(key(mutant)).or_default().mutants.push(mutant);
+        }
+        groups
+    }
+}
+
+impl<'a> IntoIterator for MutantView<'a> {
+    type IntoIter = std::vec::IntoIter<&'a MutationReport>;
+    type Item = &'a MutationReport;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.mutants.into_iter()
+    }
+}
+
+impl Report {
+    /// Returns the view over all the mutants in the report, which can be filtered and grouped.
+    #[must_use]
+    pub fn query(&self) -> MutantView<'_> {
+        MutantView {
+            mutants: self.mutants.iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{Mutation, Range};
+
+    fn entry(file: &str, module: &str, operator: &str, status: MutantStatus) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new(file),
+            module,
+            "function",
+            "\n",
+            "diff\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(0, 1),
+            operator.to_string(),
+            "old".to_string(),
+            "new".to_string(),
+        ));
+        entry.set_status(status);
+        entry
+    }
+
+    fn report() -> Report {
+        let mut report = Report::new();
+        report.add_entry(entry("a.move", "A", "binary", MutantStatus::Killed));
+        report.add_entry(entry("a.move", "A", "unary", MutantStatus::Survived));
+        report.add_entry(entry("b.move", "B", "binary", MutantStatus::Survived));
+        report
+    }
+
+    #[test]
+    fn query_filters_mutants() {
+        let report = report();
+        assert_eq!(report.query().len(), 3);
+        assert_eq!(report.query().file(Path::new("a.move")).len(), 2);
+        assert_eq!(report.query().operator("binary").len(), 2);
+        assert_eq!(report.query().module("B").len(), 1);
+        assert_eq!(report.query().function("A", "function").len(), 2);
+
+        let survived = report
+            .query()
+            .operator("binary")
+            .status(MutantStatus::Survived)
+            .into_vec();
+        assert_eq!(survived.len(), 1);
+        assert_eq!(survived[0].get_module_name(), "B");
+    }
+
+    #[test]
+    fn query_groups_mutants() {
+        let report = report();
+
+        let by_file = report.query().group_by_file();
+        assert_eq!(by_file[Path::new("a.move")].len(), 2);
+        assert_eq!(by_file[Path::new("b.move")].len(), 1);
+
+        let by_operator = report.query().group_by_operator();
+        assert_eq!(by_operator["binary"].len(), 2);
+        assert_eq!(by_operator["unary"].len(), 1);
+
+        let by_status = report.query().module("A").group_by_status();
+        assert_eq!(by_status[&MutantStatus::Killed].len(), 1);
+        assert_eq!(by_status[&MutantStatus::Survived].len(), 1);
+
+        let by_module = report.query().group_by_module();
+        assert_eq!(by_module.keys().copied().collect::<Vec<_>>(), vec![
+            "A", "B"
+        ]);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{MutationReport, Position, Range, Report};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeSet,
+    io::{Error, Result},
+    path::Path,
+};
+
+/// Version of the SARIF format produced by the report.
+const SARIF_VERSION: &str = "2.1.0";
+/// Schema of the SARIF format produced by the report.
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+/// Name of the tool reported in the SARIF file.
+const TOOL_NAME: &str = "move-mutator";
+
+impl Report {
+    /// Converts the `Report` into the SARIF 2.1.0 format.
+    ///
+    /// Each mutant is reported as a single result placed at the exact mutated span, so the report
+    /// is meant to contain surviving mutants only (e.g. the ones not killed by the specification).
+    /// Rules are created for all mutation operators used in the report.
+    #[must_use]
+    pub fn to_sarif(&self) -> Value {
+        let operators = self
+            .mutants
+            .iter()
+            .flat_map(|m| {
+                m.mutations
+                    .iter()
+                    .map(|mutation| mutation.operator_name.as_str())
+            })
+            .collect::<BTreeSet<_>>();
+
+        let rules = operators
+            .iter()
+            .map(|op| {
+                json!({
+                    "id": op,
+                    "name": op,
+                    "shortDescription": { "text": format!("Mutant generated by the {op} operator survived") },
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let results = self
+            .mutants
+            .iter()
+            .flat_map(sarif_results)
+            .collect::<Vec<_>>();
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": SARIF_VERSION,
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": TOOL_NAME,
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }]
+        })
+    }
+
+    /// Saves the `Report` as a SARIF file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written to.
+    pub fn save_to_sarif_file(&self, path: &Path) -> Result<()> {
+        let file = std::fs::File::create(path)?;
+
+        info!("Saving SARIF report to {}", path.display());
+
+        serde_json::to_writer_pretty(file, &self.to_sarif()).map_err(Error::other)
+    }
+}
+
+/// Creates SARIF results for all mutations of the given mutant.
+fn sarif_results(entry: &MutationReport) -> Vec<Value> {
+    // Source is needed to compute line and column numbers if they are not stored in the report.
+    // If it's not available, only byte offsets are reported.
+    let is_located = entry.mutations.iter().all(|m| {
+        m.changed_place.start_position.is_some() && m.changed_place.end_position.is_some()
+    });
+    let source = if is_located {
+        None
+    } else {
+        std::fs::read_to_string(&entry.original_file).ok()
+    };
+
+    entry
+        .mutations
+        .iter()
+        .map(|mutation| {
+            json!({
+                "ruleId": mutation.operator_name,
+                "level": "warning",
+                "message": {
+                    "text": format!(
+                        "Mutant {} is not killed: `{}` replaced with `{}` in {}::{}",
+                        entry.mutant_id,
+                        mutation.old_value,
+                        mutation.new_value,
+                        entry.module_name,
+                        entry.function_name
+                    )
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": entry.original_file.to_string_lossy().replace('\\', "/"),
+                        },
+                        "region": sarif_region(&mutation.changed_place, source.as_deref()),
+                    }
+                }],
+                "partialFingerprints": {
+                    "mutantId": entry.mutant_id,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Creates the SARIF region for the given range.
+/// Line and column numbers stored in the range are used, otherwise they are computed from the source.
+fn sarif_region(range: &Range, source: Option<&str>) -> Value {
+    let mut region = json!({
+        "byteOffset": range.start,
+        "byteLength": range.end - range.start,
+    });
+
+    let positions = match (range.start_position, range.end_position) {
+        (Some(start), Some(end)) => Some((start, end)),
+        _ => source.and_then(|source| {
+            Some((
+                Position::from_offset(source, range.start)?,
+                Position::from_offset(source, range.end)?,
+            ))
+        }),
+    };
+
+    if let Some((start, end)) = positions {
+        region["startLine"] = start.line.into();
+        region["startColumn"] = start.column.into();
+        region["endLine"] = end.line.into();
+        region["endColumn"] = end.column.into();
+    }
+
+    region
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Mutation;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn report_is_converted_to_sarif() {
+        let dir = tempdir().unwrap();
+        let original_file = dir.path().join("Sum.move");
+        fs::write(
+            &original_file,
+            "module 0x1::Sum {\n    fun sum(x: u64): u64 { x + 1 }\n}\n",
+        )
+        .unwrap();
+
+        let mut entry = MutationReport::new(
+            &dir.path().join("Sum_0.move"),
+            &original_file,
+            "Sum",
+            "sum",
+            "",
+            "",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(47, 48),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "-".to_string(),
+        ));
+
+        let mut report = Report::new();
+        report.add_entry(entry.clone());
+
+        let sarif = report.to_sarif();
+        assert_eq!(sarif["version"], SARIF_VERSION);
+        let run = &sarif["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0]["id"],
+            "binary_operator_replacement"
+        );
+
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "binary_operator_replacement");
+        assert_eq!(result["partialFingerprints"]["mutantId"], entry.mutant_id());
+        let region = &result["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 2);
+        assert_eq!(region["startColumn"], 30);
+        assert_eq!(region["endColumn"], 31);
+        assert_eq!(region["byteOffset"], 47);
+        assert_eq!(region["byteLength"], 1);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{MutationReport, Report, SkippedMutation};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Error, ErrorKind, Result, Write},
+    path::Path,
+};
+
+/// Single line of the streamed (JSONL) report.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum StreamRecord {
+    /// Generated (and possibly already tested) mutant.
+    Mutant(MutationReport),
+    /// Mutation which was not generated.
+    Skipped(SkippedMutation),
+}
+
+/// Writer appending report entries to a JSONL file as soon as they are available.
+///
+/// Each entry is written as a single line and flushed immediately, so a crashed or cancelled run
+/// still leaves a usable partial report which can be loaded with `Report::load_from_jsonl_file`.
+#[derive(Debug)]
+pub struct ReportStreamWriter {
+    file: File,
+}
+
+impl ReportStreamWriter {
+    /// Creates a new stream file, truncating the existing one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created.
+    pub fn create(path: &Path) -> Result<Self> {
+        info!("Streaming report entries to {}", path.display());
+        Ok(Self {
+            file: File::create(path)?,
+        })
+    }
+
+    /// Opens the stream file for appending, creating it if needed (e.g. to resume a previous run).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub fn append(path: &Path) -> Result<Self> {
+        info!("Appending report entries to {}", path.display());
+        Ok(Self {
+            file: OpenOptions::new().create(true).append(true).open(path)?,
+        })
+    }
+
+    /// Appends the mutant to the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written.
+    pub fn write_entry(&mut self, entry: &MutationReport) -> Result<()> {
+        self.write_record(&StreamRecord::Mutant(entry.clone()))
+    }
+
+    /// Appends the skipped mutation to the stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written.
+    pub fn write_skipped(&mut self, entry: &SkippedMutation) -> Result<()> {
+        self.write_record(&StreamRecord::Skipped(entry.clone()))
+    }
+
+    fn write_record(&mut self, record: &StreamRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record).map_err(Error::other)?;
+        line.push('\n');
+        // The whole line is written at once, so concurrent or interrupted writes don't interleave lines.
+        self.file.write_all(line.as_bytes())?;
+        self.file.flush()
+    }
+}
+
+impl Report {
+    /// Loads the `Report` from a JSONL file written by `ReportStreamWriter`.
+    ///
+    /// If the same mutant is written more than once (e.g. its status was updated), the last entry
+    /// wins. A malformed last line (e.g. left by a crash during writing) is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or any other line is malformed.
+    pub fn load_from_jsonl_file(path: &Path) -> Result<Self> {
+        info!("Reading streamed report from {}", path.display());
+
+        let lines = BufReader::new(File::open(path)?)
+            .lines()
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut report = Report::new();
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record = match serde_json::from_str::<StreamRecord>(line) {
+                Ok(record) => record,
+                Err(e) if index + 1 == lines.len() => {
+                    warn!("Ignoring incomplete last line of {}: {e}", path.display());
+                    break;
+                },
+                Err(e) => return Err(Error::new(ErrorKind::InvalidData, e)),
+            };
+
+            match record {
+                StreamRecord::Mutant(entry) => {
+                    if let Some(existing) = report
+                        .mutants
+                        .iter_mut()
+                        .find(|m| m.mutant_id == entry.mutant_id)
+                    {
+                        *existing = entry;
+                    } else {
+                        report.add_entry(entry);
+                    }
+                },
+                StreamRecord::Skipped(entry) => report.add_skipped_entry(entry),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MutantStatus, Mutation, Range, SkipReason};
+    use tempfile::tempdir;
+
+    fn entry(new_value: &str) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("file"),
+            Path::new("sources/original_file.move"),
+            "module",
+            "function",
+            "\n",
+            "diff\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(0, 10),
+            "operator".to_string(),
+            "old".to_string(),
+            new_value.to_string(),
+        ));
+        entry
+    }
+
+    #[test]
+    fn streamed_report_is_loaded() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("report.jsonl");
+
+        let mut writer = ReportStreamWriter::create(&path).unwrap();
+        writer.write_entry(&entry("a")).unwrap();
+        writer
+            .write_skipped(&SkippedMutation::new(
+                Path::new("sources/original_file.move"),
+                "module",
+                "function",
+                Mutation::new(
+                    Range::new(0, 1),
+                    "operator".to_string(),
+                    "old".to_string(),
+                    "new".to_string(),
+                ),
+                SkipReason::Unreachable,
+            ))
+            .unwrap();
+        drop(writer);
+
+        // Resume the run and update the status of the first mutant.
+        let mut writer = ReportStreamWriter::append(&path).unwrap();
+        let mut updated = entry("a");
+        updated.set_status(MutantStatus::Killed);
+        writer.write_entry(&updated).unwrap();
+        writer.write_entry(&entry("b")).unwrap();
+        drop(writer);
+
+        // Simulate a crash in the middle of writing the line.
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"kind\":\"mutant\",\"mutant_id\":")
+            .unwrap();
+
+        let report = Report::load_from_jsonl_file(&path).unwrap();
+        assert_eq!(report.get_mutants().len(), 2);
+        assert_eq!(report.get_mutants()[0].status(), MutantStatus::Killed);
+        assert_eq!(report.get_skipped().len(), 1);
+    }
+
+    #[test]
+    fn malformed_line_in_the_middle_is_an_error() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("report.jsonl");
+
+        let mut writer = ReportStreamWriter::create(&path).unwrap();
+        writer.file.write_all(b"not json\n").unwrap();
+        writer.write_entry(&entry("a")).unwrap();
+        drop(writer);
+
+        assert!(Report::load_from_jsonl_file(&path).is_err());
+    }
+}
@@ -0,0 +1,265 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{MutantStatus, Mutation, Position, Range, Report, SkipReason};
+use crate::sandbox::SandboxFailure;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Error, Result},
+    path::Path,
+};
+
+/// Version of the mutation-testing-elements schema produced by the report.
+const SCHEMA_VERSION: &str = "1";
+/// Mutation score (in percents) at or above which the result is considered good.
+const THRESHOLD_HIGH: u32 = 80;
+/// Mutation score (in percents) below which the result is considered bad.
+const THRESHOLD_LOW: u32 = 60;
+
+/// Status of the mutant as defined by the mutation-testing-elements schema used by Stryker.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+enum StrykerStatus {
+    /// The mutant was detected.
+    Killed,
+    /// The mutant was not detected.
+    Survived,
+    /// Testing the mutant took too long.
+    Timeout,
+    /// Testing the mutant failed for a reason other than detecting it.
+    RuntimeError,
+    /// The mutant doesn't compile.
+    CompileError,
+    /// The mutant is placed in the code not covered by any test or specification.
+    NoCoverage,
+    /// The mutant was not tested on purpose.
+    Ignored,
+    /// The mutant has not been tested yet.
+    Pending,
+}
+
+impl From<MutantStatus> for StrykerStatus {
+    fn from(status: MutantStatus) -> Self {
+        match status {
+            MutantStatus::Generated => StrykerStatus::Pending,
+            MutantStatus::Invalid => StrykerStatus::CompileError,
+            MutantStatus::Killed => StrykerStatus::Killed,
+            MutantStatus::Survived => StrykerStatus::Survived,
+            MutantStatus::Timeout => StrykerStatus::Timeout,
+            MutantStatus::NoSpec => StrykerStatus::NoCoverage,
+            MutantStatus::OutOfMemory => StrykerStatus::RuntimeError,
+            MutantStatus::Equivalent | MutantStatus::Skipped => StrykerStatus::Ignored,
+        }
+    }
+}
+
+impl Report {
+    /// Converts the `Report` into the mutation-testing-elements JSON format used by Stryker.
+    ///
+    /// The format can be consumed by the Stryker dashboard and the HTML viewers from the
+    /// mutation-testing-elements project. Mutants are grouped by the original file and include the
+    /// whole source of that file. The mutant status is taken from the report (untested mutants are
+    /// pending). Skipped mutations are reported as ignored (unreachable code) or as timeouts and
+    /// runtime errors (sandbox failures).
+    #[must_use]
+    pub fn to_stryker(&self) -> Value {
+        // Each original file is read only once - its source is needed to compute mutant locations.
+        let sources = self
+            .mutants
+            .iter()
+            .map(|m| m.original_file.as_path())
+            .chain(self.skipped.iter().map(|s| s.original_file.as_path()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|path| (path, std::fs::read_to_string(path).unwrap_or_default()))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut files = Map::new();
+        let mut push_mutant = |path: &Path, mutant: Value| {
+            let file = files
+                .entry(path.to_string_lossy().replace('\\', "/"))
+                .or_insert_with(|| {
+                    json!({
+                        "language": "move",
+                        "source": sources[path],
+                        "mutants": [],
+                    })
+                });
+
+            if let Some(mutants) = file["mutants"].as_array_mut() {
+                mutants.push(mutant);
+            }
+        };
+
+        for entry in &self.mutants {
+            let status = StrykerStatus::from(entry.status);
+            let single = entry.mutations.len() == 1;
+            for (index, mutation) in entry.mutations.iter().enumerate() {
+                let id = if single {
+                    entry.mutant_id.clone()
+                } else {
+                    format!("{}-{index}", entry.mutant_id)
+                };
+                let source = &sources[entry.original_file.as_path()];
+                push_mutant(
+                    &entry.original_file,
+                    stryker_mutant(id, mutation, source, status, entry.killed_by.clone()),
+                );
+            }
+        }
+
+        for (index, entry) in self.skipped.iter().enumerate() {
+            let status = match entry.reason {
+                SkipReason::Unreachable => StrykerStatus::Ignored,
+                SkipReason::Sandbox(SandboxFailure::Timeout) => StrykerStatus::Timeout,
+                SkipReason::Sandbox(_) => StrykerStatus::RuntimeError,
+            };
+            let source = &sources[entry.original_file.as_path()];
+            push_mutant(
+                &entry.original_file,
+                stryker_mutant(
+                    format!("skipped-{index}"),
+                    &entry.mutation,
+                    source,
+                    status,
+                    Some(entry.reason.to_string()),
+                ),
+            );
+        }
+
+        json!({
+            "schemaVersion": SCHEMA_VERSION,
+            "thresholds": {
+                "high": THRESHOLD_HIGH,
+                "low": THRESHOLD_LOW,
+            },
+            "files": files,
+        })
+    }
+
+    /// Saves the `Report` as a mutation-testing-elements JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written to.
+    pub fn save_to_stryker_file(&self, path: &Path) -> Result<()> {
+        let file = std::fs::File::create(path)?;
+
+        info!("Saving Stryker report to {}", path.display());
+
+        serde_json::to_writer_pretty(file, &self.to_stryker()).map_err(Error::other)
+    }
+}
+
+/// Creates a mutant object for the given mutation.
+fn stryker_mutant(
+    id: String,
+    mutation: &Mutation,
+    source: &str,
+    status: StrykerStatus,
+    status_reason: Option<String>,
+) -> Value {
+    let mut mutant = json!({
+        "id": id,
+        "mutatorName": mutation.operator_name,
+        "replacement": mutation.new_value,
+        "location": stryker_location(&mutation.changed_place, source),
+        "status": status,
+    });
+
+    if let Some(reason) = status_reason {
+        mutant["statusReason"] = reason.into();
+    }
+
+    mutant
+}
+
+/// Creates the location object for the given range. The location is required by the schema, so the
+/// beginning of the file is used if the range doesn't match the source.
+fn stryker_location(range: &Range, source: &str) -> Value {
+    let start = range
+        .start_position
+        .or_else(|| Position::from_offset(source, range.start))
+        .unwrap_or(Position { line: 1, column: 1 });
+    let end = range
+        .end_position
+        .or_else(|| Position::from_offset(source, range.end))
+        .unwrap_or(start);
+
+    json!({
+        "start": { "line": start.line, "column": start.column },
+        "end": { "line": end.line, "column": end.column },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MutationReport, SkippedMutation};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn report_is_converted_to_stryker_format() {
+        let dir = tempdir().unwrap();
+        let original_file = dir.path().join("Sum.move");
+        let source =
+            "module 0x42::Sum {\n    fun sum(x: u64, y: u64): u64 {\n        x + y\n    }\n}\n";
+        fs::write(&original_file, source).unwrap();
+
+        let mut entry = MutationReport::new(
+            &dir.path().join("Sum_mut0.move"),
+            &original_file,
+            "Sum",
+            "sum",
+            &source.replace('+', "-"),
+            source,
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(64, 65),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "-".to_string(),
+        ));
+
+        entry.set_status(MutantStatus::Survived);
+
+        let mut report = Report::new();
+        report.add_entry(entry.clone());
+        report.add_skipped_entry(SkippedMutation::new(
+            &original_file,
+            "Sum",
+            "sum",
+            Mutation::new(
+                Range::new(62, 63),
+                "literal_replacement".to_string(),
+                "x".to_string(),
+                "0".to_string(),
+            ),
+            SkipReason::Sandbox(SandboxFailure::Timeout),
+        ));
+
+        let stryker = report.to_stryker();
+        assert_eq!(stryker["schemaVersion"], "1");
+
+        let file = &stryker["files"][original_file.to_string_lossy().replace('\\', "/")];
+        assert_eq!(file["language"], "move");
+        assert_eq!(file["source"], source);
+
+        let mutant = &file["mutants"][0];
+        assert_eq!(mutant["id"], entry.mutant_id());
+        assert_eq!(mutant["mutatorName"], "binary_operator_replacement");
+        assert_eq!(mutant["replacement"], "-");
+        assert_eq!(mutant["status"], "Survived");
+        assert_eq!(
+            mutant["location"],
+            json!({ "start": { "line": 3, "column": 11 }, "end": { "line": 3, "column": 12 } })
+        );
+
+        let skipped = &file["mutants"][1];
+        assert_eq!(skipped["status"], "Timeout");
+        assert_eq!(skipped["statusReason"], "sandbox failure: timeout");
+    }
+}
@@ -0,0 +1,538 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fmt,
+    io::{Read, Write},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::OnceLock,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Name of the hidden subcommand the current executable is started with to run a sandboxed task.
+pub const TASK_SUBCOMMAND: &str = "sandbox-task";
+/// Environment variable holding the name of the task the sandbox process should run.
+pub const TASK_ENV: &str = "MOVE_SANDBOX_TASK";
+/// Prefix of the line on which the sandbox process reports the result of the task.
+const REPORT_MARKER: &str = "MOVE_SANDBOX_REPORT:";
+/// Interval between checks of the sandbox process status.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Interval between checks of the memory used by the sandbox process.
+const MEMORY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Arguments the current executable is started with to run a sandboxed task.
+static TASK_ARGS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Limits applied to the sandboxed process.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxLimits {
+    /// Maximum resident memory of the sandbox process in MiB. Supported on Linux only.
+    pub memory_limit_mb: Option<u64>,
+    /// Maximum resident memory in MiB of the whole process group, i.e. the sandbox process along
+    /// with all the processes it spawns (e.g. the SMT solvers). Supported on Linux only.
+    pub group_memory_limit_mb: Option<u64>,
+    /// Maximum time the process can run.
+    pub timeout: Option<Duration>,
+}
+
+impl SandboxLimits {
+    /// Returns true if any limit is set, so the sandbox should be used.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.memory_limit_mb.is_some()
+            || self.group_memory_limit_mb.is_some()
+            || self.timeout.is_some()
+    }
+}
+
+/// Reason of the sandboxed process failure.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SandboxFailure {
+    /// The process has been killed after exceeding the timeout.
+    Timeout,
+    /// The process has run out of memory.
+    MemoryLimit,
+    /// The process has crashed (e.g. has been killed by a signal).
+    Crash,
+}
+
+impl fmt::Display for SandboxFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SandboxFailure::Timeout => write!(f, "timeout"),
+            SandboxFailure::MemoryLimit => write!(f, "memory limit exceeded"),
+            SandboxFailure::Crash => write!(f, "crash"),
+        }
+    }
+}
+
+/// Result of the sandboxed run.
+#[derive(Debug)]
+pub enum SandboxResult<T> {
+    /// The task has finished with the given result.
+    Finished(anyhow::Result<T>),
+    /// The sandbox has failed and the task result is unknown.
+    Failed(SandboxFailure),
+}
+
+/// Task which can be run in the sandbox process.
+///
+/// The task is passed to the sandbox process serialized, so it has to carry all the data it needs.
+pub trait SandboxTask: Serialize + DeserializeOwned {
+    /// Name identifying the task in the sandbox process.
+    const NAME: &'static str;
+
+    /// Result of the task passed back from the sandbox process.
+    type Output: Serialize + DeserializeOwned;
+
+    /// Runs the task.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the task fails. The error message is passed back from the sandbox process.
+    fn run(self) -> anyhow::Result<Self::Output>;
+}
+
+/// Sets the arguments the current executable is started with to run the sandboxed tasks, in place of
+/// the hidden `sandbox-task` subcommand (e.g. to run the test serving the tasks of a test binary).
+/// Has no effect if the arguments are already set.
+pub fn set_task_args(args: Vec<String>) {
+    let _ = TASK_ARGS.set(args);
+}
+
+/// Returns the name of the task the current process has been started to run, if it's a sandbox
+/// process. The entry point of the executable should then run the task with `serve` instead of its
+/// usual work.
+#[must_use]
+pub fn requested_task() -> Option<String> {
+    std::env::var(TASK_ENV).ok()
+}
+
+/// Runs the task in the sandbox process: reads the task from the standard input, runs it and reports
+/// the result on the standard output.
+///
+/// # Errors
+///
+/// Returns an error if the task cannot be read or the result cannot be reported.
+pub fn serve<T: SandboxTask>() -> anyhow::Result<()> {
+    let _ = pretty_env_logger::try_init();
+
+    let task: T = serde_json::from_reader(std::io::stdin().lock())?;
+    let report = task.run().map_err(|e| format!("{e:#}"));
+
+    // The task may have written to the standard output too, so the report starts on a new line.
+    let mut stdout = std::io::stdout().lock();
+    writeln!(
+        stdout,
+        "\n{REPORT_MARKER}{}",
+        serde_json::to_string(&report)?
+    )?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Runs the task in a separate process with the given limits applied.
+///
+/// The current executable is started again (with the hidden `sandbox-task` subcommand), so the task
+/// runs in a fresh process which cannot affect the caller. The task is passed on the standard input
+/// and its result (or the error message) is passed back on the standard output.
+///
+/// The sandbox process runs in its own process group. If it exceeds the timeout or the memory limits,
+/// it's killed along with all the processes it has spawned. The memory limits are enforced by
+/// checking the resident memory of the processes periodically.
+///
+/// # Arguments
+///
+/// * `limits` - the limits applied to the sandbox process.
+/// * `task` - the task to run.
+///
+/// # Returns
+///
+/// * `SandboxResult<T::Output>` - the result of the task or the reason of the sandbox failure.
+pub fn run_sandboxed<T: SandboxTask>(limits: &SandboxLimits, task: &T) -> SandboxResult<T::Output> {
+    match spawn::<T>() {
+        Ok(child) => wait_for_task::<T>(child, limits, task),
+        Err(e) => SandboxResult::Finished(Err(anyhow::anyhow!(
+            "Cannot start the sandbox process: {e}"
+        ))),
+    }
+}
+
+/// Starts the sandbox process for the task.
+fn spawn<T: SandboxTask>() -> anyhow::Result<Child> {
+    let mut command = Command::new(std::env::current_exe()?);
+    match TASK_ARGS.get() {
+        Some(args) => command.args(args),
+        None => command.arg(TASK_SUBCOMMAND),
+    };
+    command
+        .env(TASK_ENV, T::NAME)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit());
+
+    // The processes spawned by the task (e.g. the SMT solvers) join the sandbox process group, so they
+    // are killed along with it.
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    Ok(command.spawn()?)
+}
+
+/// Passes the task to the sandbox process and waits for its result, killing the process once any
+/// limit is exceeded.
+fn wait_for_task<T: SandboxTask>(
+    mut child: Child,
+    limits: &SandboxLimits,
+    task: &T,
+) -> SandboxResult<T::Output> {
+    if (limits.memory_limit_mb.is_some() || limits.group_memory_limit_mb.is_some())
+        && !cfg!(target_os = "linux")
+    {
+        warn!("Sandbox memory limits are supported on Linux only and will be ignored");
+    }
+
+    // The process may exit before reading the whole task, which is reported once it's reaped.
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Err(e) = serde_json::to_writer(&mut stdin, task) {
+            debug!("Cannot pass the task to the sandbox process: {e}");
+        }
+    }
+
+    // The output is read on a separate thread, so the process doesn't block on a full pipe.
+    let output = child.stdout.take().map(|mut stdout| {
+        thread::spawn(move || {
+            let mut output = vec![];
+            let _ = stdout.read_to_end(&mut output);
+            output
+        })
+    });
+
+    let start = Instant::now();
+    let mut memory_checked = start;
+    let failure = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                let output = output
+                    .and_then(|output| output.join().ok())
+                    .unwrap_or_default();
+                return task_result::<T>(status, &output);
+            },
+            Ok(None) => {},
+            Err(e) => {
+                kill(&mut child);
+                return SandboxResult::Finished(Err(anyhow::anyhow!(
+                    "Cannot wait for the sandbox process: {e}"
+                )));
+            },
+        }
+
+        if limits.timeout.is_some_and(|t| start.elapsed() > t) {
+            warn!(
+                "Sandbox process {} exceeded the timeout and will be killed",
+                child.id()
+            );
+            break SandboxFailure::Timeout;
+        }
+
+        if memory_checked.elapsed() >= MEMORY_POLL_INTERVAL {
+            memory_checked = Instant::now();
+            if let Some((used, limit)) = exceeded_memory(child.id(), limits) {
+                warn!(
+                    "Sandbox process {} uses {used} MiB (limit {limit} MiB) and will be killed",
+                    child.id()
+                );
+                break SandboxFailure::MemoryLimit;
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    kill(&mut child);
+    if let Some(output) = output {
+        let _ = output.join();
+    }
+    SandboxResult::Failed(failure)
+}
+
+/// Reads the result of the task from the output of the finished sandbox process.
+fn task_result<T: SandboxTask>(status: ExitStatus, output: &[u8]) -> SandboxResult<T::Output> {
+    let output = String::from_utf8_lossy(output);
+    let mut report = None;
+    for line in output.lines() {
+        match line.strip_prefix(REPORT_MARKER) {
+            Some(line) => report = Some(line),
+            None if !line.is_empty() => debug!("Sandbox process: {line}"),
+            None => {},
+        }
+    }
+
+    let Some(report) = report else {
+        warn!("Sandbox process exited ({status}) without reporting the result of the task");
+        return SandboxResult::Failed(SandboxFailure::Crash);
+    };
+
+    match serde_json::from_str::<Result<T::Output, String>>(report) {
+        Ok(report) => SandboxResult::Finished(report.map_err(|e| anyhow::anyhow!(e))),
+        Err(e) => SandboxResult::Finished(Err(anyhow::anyhow!(
+            "Cannot read the result reported by the sandbox process: {e}"
+        ))),
+    }
+}
+
+/// Kills the sandbox process along with the processes it has spawned and reaps it.
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // SAFETY: the process has not been reaped yet, so its process group cannot be reused.
+        unsafe { libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL) };
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Checks the memory used by the sandbox process and its process group against the limits.
+///
+/// # Returns
+///
+/// * `Option<(u64, u64)>` - the used memory and the exceeded limit (in MiB), if any limit is exceeded.
+fn exceeded_memory(pid: u32, limits: &SandboxLimits) -> Option<(u64, u64)> {
+    if let Some(limit) = limits.memory_limit_mb {
+        let used = process_memory_mb(pid);
+        if used > limit {
+            return Some((used, limit));
+        }
+    }
+    if let Some(limit) = limits.group_memory_limit_mb {
+        let used = group_memory_mb(pid);
+        if used > limit {
+            return Some((used, limit));
+        }
+    }
+    None
+}
+
+/// Returns the resident memory (in MiB) used by the process.
+#[cfg(target_os = "linux")]
+fn process_memory_mb(pid: u32) -> u64 {
+    std::fs::read_to_string(format!("/proc/{pid}/stat"))
+        .ok()
+        .and_then(|stat| stat_fields(&stat))
+        .map_or(0, |(_, rss)| pages_to_mb(rss))
+}
+
+/// Returns the resident memory (in MiB) used by all the processes of the given process group.
+#[cfg(target_os = "linux")]
+fn group_memory_mb(pgid: u32) -> u64 {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return 0;
+    };
+
+    let pages = entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().parse::<u32>().is_ok())
+        .filter_map(|entry| std::fs::read_to_string(entry.path().join("stat")).ok())
+        .filter_map(|stat| stat_fields(&stat))
+        .filter(|(group, _)| *group == pgid)
+        .map(|(_, rss)| rss)
+        .sum::<u64>();
+    pages_to_mb(pages)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_memory_mb(_pid: u32) -> u64 {
+    0
+}
+
+#[cfg(not(target_os = "linux"))]
+fn group_memory_mb(_pgid: u32) -> u64 {
+    0
+}
+
+/// Converts the number of memory pages to MiB.
+#[cfg(target_os = "linux")]
+fn pages_to_mb(pages: u64) -> u64 {
+    // SAFETY: `sysconf` has no memory safety implications.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).unwrap_or(4096);
+    pages * page_size / (1024 * 1024)
+}
+
+/// Parses the process group and the resident set size (in pages) from the `/proc/<PID>/stat` content.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn stat_fields(stat: &str) -> Option<(u32, u64)> {
+    // The command name is enclosed in parentheses and may contain spaces, so the fields are
+    // counted after it: state, ppid, pgrp, ... and rss as the 22nd one.
+    let (_, fields) = stat.rsplit_once(')')?;
+    let fields = fields.split_whitespace().collect::<Vec<_>>();
+    Some((fields.get(2)?.parse().ok()?, fields.get(21)?.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Tasks run in the sandbox by the tests.
+    #[derive(Serialize, Deserialize)]
+    enum TestTask {
+        Echo(String),
+        Fail(String),
+        Abort,
+        Sleep(u64),
+        Allocate(usize),
+        SpawnSleep(PathBuf),
+    }
+
+    impl SandboxTask for TestTask {
+        type Output = String;
+
+        const NAME: &'static str = "test";
+
+        fn run(self) -> anyhow::Result<String> {
+            match self {
+                TestTask::Echo(message) => Ok(message),
+                TestTask::Fail(message) => {
+                    Err(anyhow::anyhow!(message).context("Test task failed"))
+                },
+                TestTask::Abort => std::process::abort(),
+                TestTask::Sleep(secs) => {
+                    thread::sleep(Duration::from_secs(secs));
+                    Ok(String::new())
+                },
+                TestTask::Allocate(mb) => {
+                    // Touch the pages, so they are resident.
+                    let memory = vec![1u8; mb * 1024 * 1024];
+                    thread::sleep(Duration::from_secs(30));
+                    Ok(memory.len().to_string())
+                },
+                TestTask::SpawnSleep(pid_file) => {
+                    let child = Command::new("sleep").arg("30").spawn()?;
+                    std::fs::write(pid_file, child.id().to_string())?;
+                    thread::sleep(Duration::from_secs(30));
+                    Ok(String::new())
+                },
+            }
+        }
+    }
+
+    /// Runs the task in the sandbox, which starts the test binary again to run `sandbox_task`.
+    fn run_test_task(limits: &SandboxLimits, task: &TestTask) -> SandboxResult<String> {
+        set_task_args(vec![
+            "sandbox::tests::sandbox_task".to_owned(),
+            "--exact".to_owned(),
+        ]);
+        run_sandboxed(limits, task)
+    }
+
+    // Serves the tasks of the other tests in the sandbox process.
+    #[test]
+    fn sandbox_task() {
+        if requested_task().is_some() {
+            serve::<TestTask>().unwrap();
+        }
+    }
+
+    #[test]
+    fn limits_are_disabled_by_default() {
+        assert!(!SandboxLimits::default().is_enabled());
+        assert!(SandboxLimits {
+            memory_limit_mb: None,
+            group_memory_limit_mb: None,
+            timeout: Some(Duration::from_secs(1)),
+        }
+        .is_enabled());
+    }
+
+    #[test]
+    fn sandboxed_task_result_is_passed_back() {
+        let limits = SandboxLimits::default();
+        assert!(matches!(
+            run_test_task(&limits, &TestTask::Echo("result".to_owned())),
+            SandboxResult::Finished(Ok(output)) if output == "result"
+        ));
+        match run_test_task(&limits, &TestTask::Fail("reason".to_owned())) {
+            SandboxResult::Finished(Err(e)) => {
+                assert_eq!(e.to_string(), "Test task failed: reason")
+            },
+            res => panic!("unexpected result {res:?}"),
+        }
+    }
+
+    #[test]
+    fn sandboxed_task_crash_is_reported() {
+        assert!(matches!(
+            run_test_task(&SandboxLimits::default(), &TestTask::Abort),
+            SandboxResult::Failed(SandboxFailure::Crash)
+        ));
+    }
+
+    #[test]
+    fn stat_fields_are_parsed() {
+        let stat = "4242 (z3 (solver)) S 4241 4200 4200 0 -1 4194560 1 0 0 0 5 1 0 0 20 0 1 0 \
+                    100 123456789 2560 18446744073709551615";
+        assert_eq!(stat_fields(stat), Some((4200, 2560)));
+        assert_eq!(stat_fields("garbage"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn sandboxed_task_is_killed_after_exceeding_memory() {
+        for limits in [
+            SandboxLimits {
+                memory_limit_mb: Some(64),
+                timeout: Some(Duration::from_secs(30)),
+                ..Default::default()
+            },
+            SandboxLimits {
+                group_memory_limit_mb: Some(64),
+                timeout: Some(Duration::from_secs(30)),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(
+                run_test_task(&limits, &TestTask::Allocate(256)),
+                SandboxResult::Failed(SandboxFailure::MemoryLimit)
+            ));
+        }
+    }
+
+    #[test]
+    fn sandboxed_task_is_killed_after_timeout() {
+        let limits = SandboxLimits {
+            memory_limit_mb: None,
+            group_memory_limit_mb: None,
+            timeout: Some(Duration::from_millis(500)),
+        };
+        assert!(matches!(
+            run_test_task(&limits, &TestTask::Sleep(10)),
+            SandboxResult::Failed(SandboxFailure::Timeout)
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn processes_spawned_by_sandboxed_task_are_killed_after_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("pid");
+        let limits = SandboxLimits {
+            memory_limit_mb: None,
+            group_memory_limit_mb: None,
+            timeout: Some(Duration::from_secs(2)),
+        };
+        assert!(matches!(
+            run_test_task(&limits, &TestTask::SpawnSleep(pid_file.clone())),
+            SandboxResult::Failed(SandboxFailure::Timeout)
+        ));
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        thread::sleep(Duration::from_millis(100));
+        // The process is either gone or a zombie waiting to be reaped.
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).unwrap_or_default();
+        assert!(stat.is_empty() || stat.contains(") Z "));
+    }
+}
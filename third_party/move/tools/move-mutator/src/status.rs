@@ -0,0 +1,211 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::Report;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Name of the report written once all the mutants are generated.
+const REPORT_FILE: &str = "report.json";
+/// Name of the report streamed while the mutants are generated.
+const STREAM_FILE: &str = "report.jsonl";
+
+/// Progress of the mutation campaign kept in the output directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CampaignStatus {
+    /// The directory with the mutants.
+    pub output_dir: PathBuf,
+    /// Tells if the generation of the mutants has finished (the final report was written).
+    pub complete: bool,
+    /// The number of the generated mutants.
+    pub generated: usize,
+    /// The number of the mutations which were not generated.
+    pub skipped: usize,
+    /// The number of the mutants already verified (e.g. by the specification test).
+    pub verified: usize,
+    /// The number of the mutants yet to be verified.
+    pub remaining: usize,
+    /// The number of the verified mutants by their status.
+    pub statuses: BTreeMap<String, usize>,
+}
+
+impl CampaignStatus {
+    /// Reads the progress of the campaign from the reports in the output directory.
+    ///
+    /// The final report (`report.json`) is used if the generation has finished, otherwise the
+    /// partial report streamed during the generation (`report.jsonl`) is used. The statuses of the
+    /// mutants are taken from the given results (e.g. the report streamed by `move spec-test
+    /// --stream-output`), falling back to the statuses recorded in the output directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_dir` - The directory with the mutants.
+    /// * `results` - The JSON (or streamed JSONL) report with the statuses of the verified mutants.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no report in the output directory or the reports can't be read.
+    ///
+    /// # Returns
+    ///
+    /// * `anyhow::Result<CampaignStatus>` - The progress of the campaign.
+    pub fn load(output_dir: &Path, results: Option<&Path>) -> anyhow::Result<Self> {
+        let (report_file, stream_file) =
+            (output_dir.join(REPORT_FILE), output_dir.join(STREAM_FILE));
+        let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        // The stream is written before the final report, so a newer stream belongs to a new run.
+        let complete = match (modified(&report_file), modified(&stream_file)) {
+            (Some(report), Some(stream)) => stream <= report,
+            (report, _) => report.is_some(),
+        };
+
+        let report = if complete {
+            Report::load_from_json_file(&report_file)?
+        } else if stream_file.exists() {
+            Report::load_from_jsonl_file(&stream_file)?
+        } else {
+            anyhow::bail!("No mutator report found in {}", output_dir.display());
+        };
+        let results = results.map(load_report).transpose()?;
+
+        let mut statuses = BTreeMap::new();
+        for mutant in report.get_mutants() {
+            let status = results
+                .as_ref()
+                .and_then(|results| results.get_mutant_by_id(mutant.mutant_id()))
+                .map_or(mutant.status(), |m| m.status());
+            if !status.is_generated() {
+                *statuses.entry(status.to_string()).or_default() += 1;
+            }
+        }
+        let generated = report.get_mutants().len();
+        let verified = statuses.values().sum();
+
+        Ok(Self {
+            output_dir: output_dir.to_path_buf(),
+            complete,
+            generated,
+            skipped: report.get_skipped().len(),
+            verified,
+            remaining: generated - verified,
+            statuses,
+        })
+    }
+
+    /// Renders the progress of the campaign in a human readable way.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut output = format!("Mutation campaign in {}\n", self.output_dir.display());
+        let generation = if self.complete {
+            "complete"
+        } else {
+            "in progress or interrupted"
+        };
+        let _ = writeln!(output, "Generation: {generation}");
+        let _ = writeln!(output, "Generated mutants: {}", self.generated);
+        let _ = writeln!(output, "Skipped mutations: {}", self.skipped);
+        let _ = write!(output, "Verified mutants: {}", self.verified);
+        if !self.statuses.is_empty() {
+            let statuses = self
+                .statuses
+                .iter()
+                .map(|(status, count)| format!("{status}: {count}"))
+                .collect::<Vec<_>>();
+            let _ = write!(output, " ({})", statuses.join(", "));
+        }
+        let _ = writeln!(output, "\nRemaining mutants: {}", self.remaining);
+        output
+    }
+}
+
+/// Loads the JSON or the streamed JSONL report, depending on the file extension.
+fn load_report(path: &Path) -> std::io::Result<Report> {
+    if path.extension().is_some_and(|ext| ext == "jsonl") {
+        Report::load_from_jsonl_file(path)
+    } else {
+        Report::load_from_json_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{MutantStatus, Mutation, MutationReport, Range, ReportStreamWriter};
+
+    fn mutant(function: &str) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            "Sum",
+            function,
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(2, 3),
+            "binary_operator_replacement".to_owned(),
+            "+".to_owned(),
+            "-".to_owned(),
+        ));
+        entry
+    }
+
+    #[test]
+    fn status_counts_verified_mutants() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut report = Report::new();
+        for function in ["sum", "sub", "mul"] {
+            report.add_entry(mutant(function));
+        }
+        report
+            .save_to_json_file(&dir.path().join(REPORT_FILE))
+            .unwrap();
+
+        let results_file = dir.path().join("results.jsonl");
+        let mut results = ReportStreamWriter::create(&results_file).unwrap();
+        for (function, status) in [
+            ("sum", MutantStatus::Killed),
+            ("sub", MutantStatus::Survived),
+        ] {
+            let mut entry = mutant(function);
+            entry.set_status(status);
+            results.write_entry(&entry).unwrap();
+        }
+
+        let status = CampaignStatus::load(dir.path(), Some(&results_file)).unwrap();
+        assert!(status.complete);
+        assert_eq!(
+            (status.generated, status.verified, status.remaining),
+            (3, 2, 1)
+        );
+        assert_eq!(status.render().lines().skip(1).collect::<Vec<_>>(), vec![
+            "Generation: complete",
+            "Generated mutants: 3",
+            "Skipped mutations: 0",
+            "Verified mutants: 2 (killed: 1, survived: 1)",
+            "Remaining mutants: 1",
+        ]);
+    }
+
+    #[test]
+    fn interrupted_generation_uses_streamed_report() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(CampaignStatus::load(dir.path(), None).is_err());
+
+        let mut stream = ReportStreamWriter::create(&dir.path().join(STREAM_FILE)).unwrap();
+        stream.write_entry(&mutant("sum")).unwrap();
+
+        let status = CampaignStatus::load(dir.path(), None).unwrap();
+        assert!(!status.complete);
+        assert_eq!(
+            (status.generated, status.verified, status.remaining),
+            (1, 0, 1)
+        );
+    }
+}
@@ -2,7 +2,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use move_mutator::cli::{CLIOptions, ModuleFilter};
+use move_mutator::cli::{CLIOptions, FunctionFilter, ModuleFilter};
 use move_package::BuildConfig;
 use std::path::{Path, PathBuf};
 use tempfile::tempdir;
@@ -25,12 +25,14 @@ fn check_mutator_works_correctly() {
     let options = CLIOptions {
         move_sources: vec![],
         mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
         out_mutant_dir: Some(outdir.clone()),
         verify_mutants: false,
         no_overwrite: false,
         downsample_filter: None,
         downsampling_ratio_percentage: None,
         configuration_file: None,
+        ..Default::default()
     };
 
     let config = BuildConfig::default();
@@ -56,12 +58,14 @@ fn check_mutator_verify_mutants_correctly() {
     let options = CLIOptions {
         move_sources: vec![],
         mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
         out_mutant_dir: Some(outdir.clone()),
         verify_mutants: true,
         no_overwrite: false,
         downsample_filter: None,
         downsampling_ratio_percentage: None,
         configuration_file: None,
+        ..Default::default()
     };
 
     let config = BuildConfig::default();
@@ -86,12 +90,14 @@ fn check_mutator_fails_on_non_existing_path() {
     let options = CLIOptions {
         move_sources: vec![],
         mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
         out_mutant_dir: Some(outdir.clone()),
         verify_mutants: false,
         no_overwrite: false,
         downsample_filter: None,
         downsampling_ratio_percentage: None,
         configuration_file: None,
+        ..Default::default()
     };
 
     let config = BuildConfig::default();
@@ -108,12 +114,14 @@ fn check_mutator_fails_on_non_existing_output_path() {
     let options = CLIOptions {
         move_sources: vec![],
         mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
         out_mutant_dir: Some("/very/bad/path".into()),
         verify_mutants: false,
         no_overwrite: false,
         downsample_filter: None,
         downsampling_ratio_percentage: None,
         configuration_file: None,
+        ..Default::default()
     };
 
     let config = BuildConfig::default();
@@ -132,12 +140,14 @@ fn check_mutator_works_with_single_files() {
     let options = CLIOptions {
         move_sources: vec!["tests/move-assets/file_without_package/Sub.move".into()],
         mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
         out_mutant_dir: Some(outdir.clone()),
         verify_mutants: false,
         no_overwrite: false,
         downsample_filter: None,
         downsampling_ratio_percentage: None,
         configuration_file: None,
+        ..Default::default()
     };
 
     let config = BuildConfig::default();
@@ -163,12 +173,14 @@ fn check_mutator_fails_verify_file_without_package() {
     let options = CLIOptions {
         move_sources: vec!["tests/move-assets/file_without_package/Sub.move".into()],
         mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
         out_mutant_dir: Some(outdir.clone()),
         verify_mutants: true,
         no_overwrite: false,
         downsample_filter: None,
         downsampling_ratio_percentage: None,
         configuration_file: None,
+        ..Default::default()
     };
 
     let config = BuildConfig::default();
@@ -184,3 +196,342 @@ fn check_mutator_fails_verify_file_without_package() {
     let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
     assert!(report.get_mutants().is_empty());
 }
+
+// Check if the mutator mutates scripts placed in the scripts directory.
+#[test]
+fn check_mutator_works_with_scripts() {
+    let outdir = tempdir().unwrap().into_path();
+
+    let options = CLIOptions {
+        move_sources: vec![],
+        mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: true,
+        no_overwrite: false,
+        downsample_filter: None,
+        downsampling_ratio_percentage: None,
+        configuration_file: None,
+        ..Default::default()
+    };
+
+    let config = BuildConfig::default();
+
+    let package_path = Path::new("tests/move-assets/scripts");
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    assert!(report_path.exists());
+
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+    let script_mutants = report
+        .get_mutants()
+        .iter()
+        .filter(|m| m.get_module_name() == "script")
+        .collect::<Vec<_>>();
+    assert!(!script_mutants.is_empty());
+    assert!(script_mutants
+        .iter()
+        .all(|m| m.mutant_path().starts_with(outdir.join("scripts"))));
+}
+
+// Check if the mutator resolves named addresses from the manifest for single files placed inside a package.
+#[test]
+fn check_mutator_resolves_named_addresses_for_single_files() {
+    let outdir = tempdir().unwrap().into_path();
+
+    let options = CLIOptions {
+        move_sources: vec!["tests/move-assets/scripts/sources/Counter.move".into()],
+        mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: false,
+        no_overwrite: false,
+        downsample_filter: None,
+        downsampling_ratio_percentage: None,
+        configuration_file: None,
+        ..Default::default()
+    };
+
+    let config = BuildConfig::default();
+
+    let package_path = Path::new(".");
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    assert!(report_path.exists());
+
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+    assert!(!report.get_mutants().is_empty());
+}
+
+// Check if the mutator skips mutants placed in the unreachable code and reports them separately.
+#[test]
+fn check_mutator_skips_unreachable_code() {
+    let outdir = tempdir().unwrap().into_path();
+
+    let options = CLIOptions {
+        move_sources: vec![],
+        mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: false,
+        no_overwrite: false,
+        downsample_filter: None,
+        downsampling_ratio_percentage: None,
+        configuration_file: None,
+        ..Default::default()
+    };
+
+    let config = BuildConfig::default();
+
+    let package_path = Path::new("tests/move-assets/unreachable");
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    assert!(report_path.exists());
+
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+    assert!(!report.get_mutants().is_empty());
+    assert!(report
+        .get_skipped()
+        .iter()
+        .any(|s| s.reason() == move_mutator::report::SkipReason::Unreachable));
+    assert!(report.get_mutants().iter().all(|m| {
+        !m.get_diff().contains("-        x + 1") && !m.get_diff().contains("-            x * 2")
+    }));
+}
+
+// Check if the mutator mutates only the selected modules and functions.
+#[test]
+fn check_mutator_mutates_only_selected_functions() {
+    let outdir = tempdir().unwrap().into_path();
+
+    let options = CLIOptions {
+        move_sources: vec![],
+        mutate_modules: ModuleFilter::Selected(vec!["Operators".to_owned()]),
+        mutate_functions: FunctionFilter::Selected(vec![
+            "sum".to_owned(),
+            "Negation::neg_log".to_owned(),
+        ]),
+        out_mutant_dir: Some(outdir.clone()),
+        ..Default::default()
+    };
+
+    let config = BuildConfig::default();
+
+    let package_path = Path::new("tests/move-assets/simple");
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, package_path);
+    assert!(result.is_ok());
+
+    let report =
+        move_mutator::report::Report::load_from_json_file(&outdir.join("report.json")).unwrap();
+    assert!(!report.get_mutants().is_empty());
+    assert!(report
+        .get_mutants()
+        .iter()
+        .all(|m| m.get_module_name() == "Operators" && m.get_function_name() == "sum"));
+}
+
+// Check if the mutator skips the operators of the binary operator groups not enabled in the configuration.
+#[test]
+fn check_mutator_skips_disabled_binary_operator_groups() {
+    let outdir = tempdir().unwrap().into_path();
+    let configuration_file = outdir.join("config.toml");
+    std::fs::write(
+        &configuration_file,
+        format!(
+            r#"
+            project_path = "tests/move-assets/simple"
+            individual = []
+            [project]
+            move_sources = []
+            out_mutant_dir = "{}"
+            mutate_modules = {{ Selected = ["Operators"] }}
+            [mutation]
+            operators = []
+            categories = []
+            [mutation.binary_operator_replacement]
+            groups = ["Arithmetic"]
+            "#,
+            outdir.display()
+        ),
+    )
+    .unwrap();
+
+    let options = CLIOptions {
+        configuration_file: Some(configuration_file),
+        ..Default::default()
+    };
+
+    let config = BuildConfig::default();
+
+    let package_path = Path::new("tests/move-assets/simple");
+
+    let result = move_mutator::run_move_mutator(options, &config, package_path);
+    assert!(result.is_ok());
+
+    let report =
+        move_mutator::report::Report::load_from_json_file(&outdir.join("report.json")).unwrap();
+    let binary_mutants = |function: &str| {
+        report
+            .get_mutants()
+            .iter()
+            .filter(|m| m.get_function_name() == function)
+            .flat_map(|m| m.get_mutations())
+            .filter(|m| m.get_operator_name().starts_with("binary_operator_"))
+            .count()
+    };
+    assert_ne!(binary_mutants("sum"), 0);
+    for function in ["and", "or", "xor", "lsh", "rsh"] {
+        assert_eq!(binary_mutants(function), 0, "{function}");
+    }
+}
+
+// Check if the mutator generates identical mutants in two runs with the same seed.
+#[test]
+fn check_mutator_is_deterministic_with_seed() {
+    let config = BuildConfig::default();
+    let package_path = Path::new("tests/move-assets/simple");
+
+    let run = |seed| {
+        let outdir = tempdir().unwrap().into_path();
+        let options = CLIOptions {
+            out_mutant_dir: Some(outdir.clone()),
+            downsampling_ratio_percentage: Some(50),
+            seed: Some(seed),
+            ..Default::default()
+        };
+        move_mutator::run_move_mutator(options, &config, package_path).unwrap();
+
+        move_mutator::report::Report::load_from_json_file(&outdir.join("report.json"))
+            .unwrap()
+            .get_mutants()
+            .iter()
+            .map(|m| m.mutant_id().to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    let mutants = run(42);
+    assert!(!mutants.is_empty());
+    assert_eq!(mutants, run(42));
+}
+
+// Serves the sandboxed tasks of the other tests, as the sandbox starts the test binary again.
+#[test]
+fn sandbox_task() {
+    if let Some(task) = move_mutator::sandbox::requested_task() {
+        move_mutator::run_sandbox_task(&task).unwrap();
+    }
+}
+
+// Check if the mutator verifies mutants correctly in the sandbox.
+#[test]
+fn check_mutator_verify_mutants_in_sandbox_correctly() {
+    move_mutator::sandbox::set_task_args(vec!["sandbox_task".to_owned(), "--exact".to_owned()]);
+    let outdir = tempdir().unwrap().into_path();
+
+    let options = CLIOptions {
+        move_sources: vec![],
+        mutate_modules: ModuleFilter::All,
+        mutate_functions: FunctionFilter::All,
+        out_mutant_dir: Some(outdir.clone()),
+        verify_mutants: true,
+        no_overwrite: false,
+        downsample_filter: None,
+        downsampling_ratio_percentage: None,
+        configuration_file: None,
+        verification_memory_limit: Some(4096),
+        verification_timeout: Some(60),
+        omit_diffs: false,
+        max_diff_lines: None,
+        anonymize: false,
+        seed: None,
+    };
+
+    let config = BuildConfig::default();
+
+    let package_path = Path::new(PACKAGE_PATHS[1]);
+
+    let result = move_mutator::run_move_mutator(options.clone(), &config, package_path);
+    assert!(result.is_ok());
+
+    let report_path = outdir.join("report.json");
+    assert!(report_path.exists());
+
+    let report = move_mutator::report::Report::load_from_json_file(&report_path).unwrap();
+    assert!(!report.get_mutants().is_empty());
+    assert!(report.get_skipped().is_empty());
+}
+
+// Check if regenerating the mutants of a single file keeps the mutants of the other files.
+#[test]
+fn check_mutator_regenerates_mutants_of_selected_files() {
+    let outdir = tempdir().unwrap().into_path();
+    let options = CLIOptions {
+        out_mutant_dir: Some(outdir.clone()),
+        seed: Some(42),
+        ..Default::default()
+    };
+    let config = BuildConfig::default();
+    let package_path = Path::new("tests/move-assets/simple");
+    let load =
+        || move_mutator::report::Report::load_from_json_file(&outdir.join("report.json")).unwrap();
+    let mutant_ids = |report: &move_mutator::report::Report| {
+        let mut ids = report
+            .get_mutants()
+            .iter()
+            .map(|m| m.mutant_id().to_owned())
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids
+    };
+
+    move_mutator::run_move_mutator(options.clone(), &config, package_path).unwrap();
+    let full = load();
+
+    let sum = package_path
+        .join("sources/Sum.move")
+        .canonicalize()
+        .unwrap();
+    move_mutator::run_move_mutator_for_files(
+        options,
+        &config,
+        package_path,
+        std::slice::from_ref(&sum),
+    )
+    .unwrap();
+    let partial = load();
+
+    assert_eq!(mutant_ids(&full), mutant_ids(&partial));
+    assert!(partial
+        .get_mutants()
+        .iter()
+        .any(|m| m.original_file_path().canonicalize().unwrap() == sum));
+    assert!(partial
+        .get_mutants()
+        .iter()
+        .all(|m| m.mutant_path().exists()));
+    assert_eq!(count_move_files(&outdir), partial.get_mutants().len());
+}
+
+fn count_move_files(dir: &Path) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .map(|path| {
+            if path.is_dir() {
+                count_move_files(&path)
+            } else {
+                usize::from(path.extension().is_some_and(|ext| ext == "move"))
+            }
+        })
+        .sum()
+}
@@ -0,0 +1,8 @@
+script {
+    use TestAccount::Counter;
+
+    fun double_increment(x: u64) {
+        let y = Counter::increment(x) * 2;
+        assert!(y > x, 1);
+    }
+}
@@ -0,0 +1,5 @@
+module TestAccount::Counter {
+    public fun increment(x: u64): u64 {
+        x + 1
+    }
+}
@@ -0,0 +1,14 @@
+module TestAccount::Unreachable {
+    fun after_abort(x: u64): u64 {
+        abort 1;
+        x + 1
+    }
+
+    fun constant_condition(x: u64): u64 {
+        if (false) {
+            x * 2
+        } else {
+            x - 1
+        }
+    }
+}
@@ -454,7 +454,9 @@ impl BuildPlan {
     fn clean(build_root: &Path, keep_paths: BTreeSet<PackageName>) -> Result<()> {
         for dir in std::fs::read_dir(build_root)? {
             let path = dir?.path();
-            if !keep_paths.iter().any(|name| path.ends_with(name.as_str())) {
+            // Only the package directories are cleaned, so the files of the tools (e.g. the state of
+            // `move prove --changed-only`) are kept.
+            if path.is_dir() && !keep_paths.iter().any(|name| path.ends_with(name.as_str())) {
                 std::fs::remove_dir_all(&path)?;
             }
         }
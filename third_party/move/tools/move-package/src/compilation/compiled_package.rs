@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    compilation::{build_plan::CompilerDriverResult, package_layout::CompiledPackageLayout},
+    compilation::{
+        build_plan::CompilerDriverResult, module_graph::ModuleDependencyGraph,
+        package_layout::CompiledPackageLayout,
+    },
     resolution::resolution_graph::{Renaming, ResolvedGraph, ResolvedPackage, ResolvedTable},
     source_package::{
         layout::{SourcePackageLayout, REFERENCE_TEMPLATE_FILENAME},
@@ -71,6 +74,10 @@ pub struct CompiledPackageInfo {
     /// The hash of the source directory at the time of compilation. `None` if the source for this
     /// package is not available/this package was not compiled.
     pub source_digest: Option<PackageDigest>,
+    /// The hash of the source directory combined with the hashes of the transitive dependencies at
+    /// the time of compilation. `None` if this package was not compiled or the build predates it.
+    #[serde(default)]
+    pub transitive_digest: Option<PackageDigest>,
     /// The build flags that were used when compiling this package.
     pub build_flags: BuildConfig,
 }
@@ -288,11 +295,16 @@ impl OnDiskCompiledPackage {
     pub(crate) fn has_source_changed_since_last_compile(
         &self,
         resolved_package: &ResolvedPackage,
+        resolution_graph: &ResolvedGraph,
     ) -> bool {
-        match &self.package.compiled_package_info.source_digest {
+        let info = &self.package.compiled_package_info;
+        match &info.source_digest {
             // Don't have source available to us
             None => false,
-            Some(digest) => digest != &resolved_package.source_digest,
+            // The transitive digest also changes when any of the dependencies has changed
+            Some(_) => {
+                info.transitive_digest != Some(resolved_package.transitive_digest(resolution_graph))
+            },
         }
     }
 
@@ -424,6 +436,11 @@ impl CompiledPackage {
             .filter(|unit| matches!(unit.unit, CompiledUnit::Module(_)))
     }
 
+    /// Returns the module-level dependency graph of this package and its transitive dependencies
+    pub fn module_dependency_graph(&self) -> ModuleDependencyGraph {
+        ModuleDependencyGraph::new(self)
+    }
+
     pub fn get_module_by_name(
         &self,
         package_name: &str,
@@ -518,7 +535,7 @@ impl CompiledPackage {
         is_root_package: bool,
     ) -> bool {
         // TODO: add more tests for the different caching cases
-        !(package.has_source_changed_since_last_compile(resolved_package) // recompile if source has changed
+        !(package.has_source_changed_since_last_compile(resolved_package, resolution_graph) // recompile if source or dependencies have changed
             // Recompile if the flags are different
                 || package.are_build_flags_different(&resolution_graph.build_options)
                 // Force root package recompilation in test mode
@@ -793,11 +810,13 @@ impl CompiledPackage {
             }
         };
 
+        let transitive_digest = resolved_package.transitive_digest(resolution_graph);
         let compiled_package = CompiledPackage {
             compiled_package_info: CompiledPackageInfo {
                 package_name: resolved_package.source_package.package.name,
                 address_alias_instantiation: resolved_package.resolution_table,
                 source_digest: Some(resolved_package.source_digest),
+                transitive_digest: Some(transitive_digest),
                 build_flags: resolution_graph.build_options.clone(),
             },
             root_compiled_units,
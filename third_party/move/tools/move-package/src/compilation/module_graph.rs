@@ -0,0 +1,121 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    compilation::compiled_package::{CompiledPackage, CompiledUnitWithSource},
+    source_package::parsed_manifest::PackageName,
+};
+use anyhow::Result;
+use move_binary_format::access::ModuleAccess;
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    path::{Path, PathBuf},
+};
+
+/// Module-level dependency graph of a compiled package (who imports whom), covering the modules of
+/// the root package and of all its transitive dependencies. Modules are identified by their short
+/// ids, e.g. `0x1::vector`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleDependencyGraph {
+    pub modules: BTreeMap<String, ModuleNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleNode {
+    /// The package the module belongs to
+    pub package: PackageName,
+    /// Whether the module belongs to the root package
+    pub is_root: bool,
+    /// The source file of the module
+    pub source_path: PathBuf,
+    /// The modules used directly by the module
+    pub dependencies: BTreeSet<String>,
+}
+
+impl ModuleDependencyGraph {
+    pub fn new(package: &CompiledPackage) -> Self {
+        let root_package = package.compiled_package_info.package_name;
+        let units = package
+            .root_compiled_units
+            .iter()
+            .map(|unit| (root_package, true, unit))
+            .chain(
+                package
+                    .deps_compiled_units
+                    .iter()
+                    .map(|(package, unit)| (*package, false, unit)),
+            );
+
+        let mut modules = BTreeMap::new();
+        for (package, is_root, unit) in units {
+            let CompiledUnitWithSource {
+                unit: CompiledUnit::Module(NamedCompiledModule { module, .. }),
+                source_path,
+            } = unit
+            else {
+                continue;
+            };
+            let dependencies = module
+                .immediate_dependencies()
+                .iter()
+                .map(|id| id.short_str_lossless())
+                .collect();
+            modules.insert(module.self_id().short_str_lossless(), ModuleNode {
+                package,
+                is_root,
+                source_path: source_path.clone(),
+                dependencies,
+            });
+        }
+        Self { modules }
+    }
+
+    /// Returns the modules used by the module, directly or transitively.
+    pub fn dependencies(&self, module: &str) -> BTreeSet<String> {
+        self.reachable(module, |module| {
+            self.modules
+                .get(module)
+                .map(|node| node.dependencies.iter().cloned().collect())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the modules using the module, directly or transitively. Only these modules (and the
+    /// module itself) can be affected by a change of the module.
+    pub fn dependents(&self, module: &str) -> BTreeSet<String> {
+        self.reachable(module, |module| {
+            self.modules
+                .iter()
+                .filter(|(_, node)| node.dependencies.contains(module))
+                .map(|(id, _)| id.clone())
+                .collect()
+        })
+    }
+
+    /// Returns the modules reachable from the module (excluding itself) over the given edges.
+    fn reachable(&self, module: &str, edges: impl Fn(&str) -> Vec<String>) -> BTreeSet<String> {
+        let mut reached = BTreeSet::new();
+        let mut queue = VecDeque::from(edges(module));
+        while let Some(next) = queue.pop_front() {
+            if next != module && reached.insert(next.clone()) {
+                queue.extend(edges(&next));
+            }
+        }
+        reached
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn save_to_json_file(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    pub fn load_from_json_file(path: &Path) -> Result<Self> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+}
@@ -148,6 +148,10 @@ pub struct BuildConfig {
     #[clap(long = "skip-fetch-latest-git-deps", global = true)]
     pub skip_fetch_latest_git_deps: bool,
 
+    /// Use only the dependencies downloaded before and fail instead of accessing the network
+    #[clap(long = "offline", global = true)]
+    pub offline: bool,
+
     #[clap(flatten)]
     pub compiler_config: CompilerConfig,
 }
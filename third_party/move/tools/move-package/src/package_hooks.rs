@@ -1,11 +1,11 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::source_package::parsed_manifest::CustomDepInfo;
+use crate::source_package::parsed_manifest::{CustomDepInfo, PackageDigest};
 use anyhow::bail;
 use move_symbol_pool::Symbol;
 use once_cell::sync::Lazy;
-use std::sync::Mutex;
+use std::{collections::BTreeMap, sync::Mutex};
 
 // TODO: remove static hooks and refactor this crate for better customizability
 
@@ -16,44 +16,100 @@ pub trait PackageHooks {
     fn custom_package_info_fields(&self) -> Vec<String>;
 
     /// Returns a custom key for dependencies, if available. This is the string used
-    /// in dependencies `{ <key> = value, address = addr }`.
-    fn custom_dependency_key(&self) -> Option<String>;
+    /// in dependencies `{ <key> = value, address = addr }`. Prefer registering a
+    /// `CustomDependencyResolver`, which allows more than one kind of custom dependencies.
+    fn custom_dependency_key(&self) -> Option<String> {
+        None
+    }
 
     /// A resolver for custom dependencies in the manifest. This is called to download the
     /// dependency from the dependency into the `info.local_path` location, similar as with git
     /// dependencies.
     fn resolve_custom_dependency(
+        &self,
+        _dep_name: Symbol,
+        _info: &CustomDepInfo,
+    ) -> anyhow::Result<()> {
+        bail!("use of unsupported custom dependency in package manifest")
+    }
+}
+
+/// A resolver of the custom dependencies declared with a particular key, e.g. the packages
+/// published on chain: `{ <key> = <node url>, address = <addr> }`. Any number of resolvers (with
+/// distinct keys) can be registered globally.
+pub trait CustomDependencyResolver {
+    /// Returns the key of the dependencies handled by the resolver.
+    fn dependency_key(&self) -> String;
+
+    /// Downloads the dependency into the `info.download_to` location, similar as with git
+    /// dependencies. Returns the digest of the downloaded package if the resolver knows it; it is
+    /// then checked against the digest of the downloaded sources during resolution, like the
+    /// `digest` field of a dependency.
+    fn resolve(
         &self,
         dep_name: Symbol,
         info: &CustomDepInfo,
-    ) -> anyhow::Result<()>;
+    ) -> anyhow::Result<Option<PackageDigest>>;
 }
+
 static HOOKS: Lazy<Mutex<Option<Box<dyn PackageHooks + Send + Sync>>>> =
     Lazy::new(|| Mutex::new(None));
 
+type Resolvers = BTreeMap<String, Box<dyn CustomDependencyResolver + Send + Sync>>;
+
+static RESOLVERS: Lazy<Mutex<Resolvers>> = Lazy::new(|| Mutex::new(BTreeMap::new()));
+
 /// Registers package hooks for the process in which the package system is used.
 pub fn register_package_hooks(hooks: Box<dyn PackageHooks + Send + Sync>) {
     *HOOKS.lock().unwrap() = Some(hooks)
 }
 
-/// Calls any registered hook to resolve a node dependency. Bails if none is registered.
+/// Registers a resolver of custom dependencies for the process in which the package system is
+/// used, replacing any resolver registered before for the same key.
+pub fn register_custom_dependency_resolver(
+    resolver: Box<dyn CustomDependencyResolver + Send + Sync>,
+) {
+    RESOLVERS
+        .lock()
+        .unwrap()
+        .insert(resolver.dependency_key(), resolver);
+}
+
+/// Calls the resolver registered for the key of a custom dependency, or else any registered hook,
+/// to resolve it. Bails if none is registered.
 pub(crate) fn resolve_custom_dependency(
     dep_name: Symbol,
     info: &CustomDepInfo,
-) -> anyhow::Result<()> {
-    if let Some(hooks) = &*HOOKS.lock().unwrap() {
-        hooks.resolve_custom_dependency(dep_name, info)
+) -> anyhow::Result<Option<PackageDigest>> {
+    if let Some(resolver) = RESOLVERS.lock().unwrap().get(info.key.as_str()) {
+        resolver.resolve(dep_name, info)
+    } else if let Some(hooks) = &*HOOKS.lock().unwrap() {
+        hooks.resolve_custom_dependency(dep_name, info)?;
+        Ok(None)
     } else {
         bail!("use of unsupported custom dependency in package manifest")
     }
 }
 
-pub(crate) fn custom_dependency_key() -> Option<String> {
-    if let Some(hooks) = &*HOOKS.lock().unwrap() {
-        hooks.custom_dependency_key()
-    } else {
-        None
+/// Returns the keys of all the supported custom dependencies.
+pub(crate) fn custom_dependency_keys() -> Vec<String> {
+    let mut keys = RESOLVERS
+        .lock()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    if let Some(key) = HOOKS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|hooks| hooks.custom_dependency_key())
+    {
+        if !keys.contains(&key) {
+            keys.push(key)
+        }
     }
+    keys
 }
 
 /// Calls any registered hook to return custom package fields.
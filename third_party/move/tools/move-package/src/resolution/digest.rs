@@ -4,48 +4,142 @@
 
 use crate::source_package::{layout::SourcePackageLayout, parsed_manifest::PackageDigest};
 use anyhow::Result;
-use move_command_line_common::files::MOVE_EXTENSION;
+use move_command_line_common::files::{FileHash, MOVE_EXTENSION};
+use move_compiler::parser::lexer::{Lexer, Tok};
+use rayon::prelude::*;
 use sha2::{Digest, Sha256};
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+/// How the contents of the Move source files are hashed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DigestMode {
+    /// The raw bytes of the files are hashed.
+    #[default]
+    Raw,
+    /// The tokens of the Move source files are hashed, so formatting-only changes (of the
+    /// whitespace and the comments) don't change the digest. Files which cannot be tokenized and
+    /// the manifests are hashed raw.
+    Semantic,
+}
 
 pub fn compute_digest(paths: &[PathBuf]) -> Result<PackageDigest> {
-    let mut hashed_files = Vec::new();
-    let mut hash = |path: &Path| {
-        let contents = std::fs::read(path)?;
-        hashed_files.push(format!("{:X}", Sha256::digest(&contents)));
-        Ok(())
-    };
-    let mut maybe_hash_file = |path: &Path| -> Result<()> {
-        match path.extension() {
-            Some(x) if MOVE_EXTENSION == x => hash(path),
-            _ if path.ends_with(SourcePackageLayout::Manifest.path()) => hash(path),
-            _ => Ok(()),
-        }
+    compute_digest_with_mode(paths, DigestMode::Raw)
+}
+
+/// Computes the digest of the Move source files and manifests found in the given paths, hashing
+/// their contents as chosen by the `mode`.
+pub fn compute_digest_with_mode(paths: &[PathBuf], mode: DigestMode) -> Result<PackageDigest> {
+    let mut hashed_files = collect_files(paths)
+        .par_iter()
+        .map(|path| hash_file(path, mode))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Sort the hashed files to ensure that the order of files is always stable
+    hashed_files.sort();
+
+    let mut hasher = Sha256::new();
+    for file_hash in hashed_files.into_iter() {
+        hasher.update(file_hash.as_bytes());
+    }
+
+    Ok(PackageDigest::from(format!("{:X}", hasher.finalize())))
+}
+
+/// Computes the digests of the individual Move source files and manifests found in the given paths,
+/// keyed by the file path. Unlike `compute_digest`, it allows telling which files have changed.
+pub fn compute_file_digests(paths: &[PathBuf]) -> Result<BTreeMap<PathBuf, PackageDigest>> {
+    compute_file_digests_with_mode(paths, DigestMode::Raw)
+}
+
+/// Computes the digests of the individual Move source files and manifests found in the given paths,
+/// hashing their contents as chosen by the `mode`.
+pub fn compute_file_digests_with_mode(
+    paths: &[PathBuf],
+    mode: DigestMode,
+) -> Result<BTreeMap<PathBuf, PackageDigest>> {
+    collect_files(paths)
+        .into_par_iter()
+        .map(|path| {
+            let digest = PackageDigest::from(hash_file(&path, mode)?);
+            Ok((path, digest))
+        })
+        .collect()
+}
+
+/// Collects the Move source files and manifests found in the given paths. The files are collected
+/// first, so they can be hashed in parallel.
+fn collect_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let is_hashed = |path: &Path| {
+        path.extension().is_some_and(|x| MOVE_EXTENSION == x)
+            || path.ends_with(SourcePackageLayout::Manifest.path())
     };
 
+    let mut files = Vec::new();
     for path in paths {
         if path.is_file() {
-            maybe_hash_file(path)?;
+            if is_hashed(path) {
+                files.push(path.clone());
+            }
         } else {
             for entry in walkdir::WalkDir::new(path)
                 .follow_links(true)
                 .into_iter()
                 .filter_map(|e| e.ok())
             {
-                if entry.file_type().is_file() {
-                    maybe_hash_file(entry.path())?
+                if entry.file_type().is_file() && is_hashed(entry.path()) {
+                    files.push(entry.into_path());
                 }
             }
         }
     }
+    files
+}
 
-    // Sort the hashed files to ensure that the order of files is always stable
-    hashed_files.sort();
+/// Hashes the contents of the file as chosen by the `mode`. The raw contents are streamed into the
+/// hasher instead of being read whole.
+fn hash_file(path: &Path, mode: DigestMode) -> Result<String> {
+    if mode == DigestMode::Semantic && path.extension().is_some_and(|x| MOVE_EXTENSION == x) {
+        let contents = std::fs::read(path)?;
+        let tokens = std::str::from_utf8(&contents).ok().and_then(hash_tokens);
+        return Ok(tokens.unwrap_or_else(|| format!("{:X}", Sha256::digest(&contents))));
+    }
 
     let mut hasher = Sha256::new();
-    for file_hash in hashed_files.into_iter() {
-        hasher.update(file_hash.as_bytes());
+    std::io::copy(&mut BufReader::new(File::open(path)?), &mut hasher)?;
+    Ok(format!("{:X}", hasher.finalize()))
+}
+
+/// Hashes the tokens of the Move source, skipping the whitespace and the comments. Returns `None`
+/// if the source cannot be tokenized.
+fn hash_tokens(source: &str) -> Option<String> {
+    let mut lexer = Lexer::new(source, FileHash::new(source));
+    let mut hasher = Sha256::new();
+    lexer.advance().ok()?;
+    while lexer.peek() != Tok::EOF {
+        hasher.update(lexer.content().as_bytes());
+        hasher.update([0]);
+        lexer.advance().ok()?;
     }
+    Some(format!("{:X}", hasher.finalize()))
+}
 
-    Ok(PackageDigest::from(format!("{:X}", hasher.finalize())))
+/// Returns the files which were added, changed or removed between the two sets of file digests
+/// (computed by `compute_file_digests`), in a stable order.
+pub fn get_changed_files(
+    old: &BTreeMap<PathBuf, PackageDigest>,
+    new: &BTreeMap<PathBuf, PackageDigest>,
+) -> Vec<PathBuf> {
+    let mut changed = new
+        .iter()
+        .filter(|(path, digest)| old.get(*path) != Some(*digest))
+        .map(|(path, _)| path.clone())
+        .chain(old.keys().filter(|path| !new.contains_key(*path)).cloned())
+        .collect::<Vec<_>>();
+    changed.sort();
+    changed
 }
@@ -2,5 +2,5 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-mod digest;
+pub mod digest;
 pub mod resolution_graph;
@@ -24,6 +24,7 @@ use move_compiler::command_line::DEFAULT_OUTPUT_DIR;
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::Symbol;
 use petgraph::{algo, graphmap::DiGraphMap};
+use sha2::{Digest, Sha256};
 use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
@@ -105,6 +106,11 @@ impl ResolvingGraph {
                 build_options.architecture = info.architecture;
             }
         }
+        let root_package = Self::apply_patches(
+            root_package.clone(),
+            &root_package.patches,
+            &root_package_path,
+        );
         let mut resolution_graph = Self {
             root_package_path: root_package_path.clone(),
             build_options,
@@ -201,6 +207,8 @@ impl ResolvingGraph {
         is_root_package: bool,
         writer: &mut W,
     ) -> Result<()> {
+        let package =
+            Self::apply_patches(package, &self.root_package.patches, &self.root_package_path);
         let package_name = package.package.name;
         let package_node_id = match self.package_table.get(&package_name) {
             None => self.get_or_add_node(package_name)?,
@@ -395,10 +403,10 @@ impl ResolvingGraph {
         root_path: PathBuf,
         writer: &mut W,
     ) -> Result<(Renaming, ResolvingTable)> {
-        Self::download_and_update_if_remote(
+        let resolved_digest = Self::download_and_update_if_remote(
             dep_name_in_pkg,
             &dep,
-            self.build_options.skip_fetch_latest_git_deps,
+            &self.build_options,
             writer,
         )?;
         let (dep_package, dep_package_dir) =
@@ -416,22 +424,21 @@ impl ResolvingGraph {
             );
         }
 
-        match dep.digest {
-            None => (),
-            Some(fixed_digest) => {
-                let resolved_pkg = self
-                    .package_table
-                    .get(&dep_name_in_pkg)
-                    .context("Unable to find resolved package by name")?;
-                if fixed_digest != resolved_pkg.source_digest {
-                    bail!(
-                        "Source digest mismatch in dependency '{}'. Expected '{}' but got '{}'.",
-                        dep_name_in_pkg,
-                        fixed_digest,
-                        resolved_pkg.source_digest
-                    )
-                }
-            },
+        // Check the digest fixed in the manifest, as well as the one reported by the resolver of
+        // a custom dependency
+        for fixed_digest in dep.digest.iter().chain(resolved_digest.iter()) {
+            let resolved_pkg = self
+                .package_table
+                .get(&dep_name_in_pkg)
+                .context("Unable to find resolved package by name")?;
+            if *fixed_digest != resolved_pkg.source_digest {
+                bail!(
+                    "Source digest mismatch in dependency '{}'. Expected '{}' but got '{}'.",
+                    dep_name_in_pkg,
+                    fixed_digest,
+                    resolved_pkg.source_digest
+                )
+            }
         }
 
         let resolving_dep = &self.package_table[&dep_name_in_pkg];
@@ -523,12 +530,53 @@ impl ResolvingGraph {
         }
     }
 
+    /// Overrides the dependencies of the package which are patched in the manifest of the root
+    /// package. The patches keep the address substitutions of the overridden dependencies, and
+    /// their local paths are relative to the root package.
+    fn apply_patches(
+        mut package: SourceManifest,
+        patches: &Dependencies,
+        root_path: &Path,
+    ) -> SourceManifest {
+        for (dep_name, dep) in package
+            .dependencies
+            .iter_mut()
+            .chain(package.dev_dependencies.iter_mut())
+        {
+            if let Some(patch) = patches.get(dep_name) {
+                *dep = Dependency {
+                    local: root_path.join(&patch.local),
+                    subst: dep.subst.take(),
+                    ..patch.clone()
+                };
+            }
+        }
+        package
+    }
+
     pub fn download_dependency_repos<W: Write>(
         manifest: &SourceManifest,
         build_options: &BuildConfig,
         root_path: &Path,
         writer: &mut W,
     ) -> Result<()> {
+        Self::download_patched_dependency_repos(
+            manifest,
+            &manifest.patches,
+            build_options,
+            root_path,
+            writer,
+        )
+    }
+
+    fn download_patched_dependency_repos<W: Write>(
+        manifest: &SourceManifest,
+        patches: &Dependencies,
+        build_options: &BuildConfig,
+        root_path: &Path,
+        writer: &mut W,
+    ) -> Result<()> {
+        let manifest = &Self::apply_patches(manifest.clone(), patches, root_path);
         // include dev dependencies if in dev mode
         let empty_deps;
         let additional_deps = if build_options.dev_mode {
@@ -539,28 +587,31 @@ impl ResolvingGraph {
         };
 
         for (dep_name, dep) in manifest.dependencies.iter().chain(additional_deps.iter()) {
-            Self::download_and_update_if_remote(
-                *dep_name,
-                dep,
-                build_options.skip_fetch_latest_git_deps,
-                writer,
-            )?;
+            Self::download_and_update_if_remote(*dep_name, dep, build_options, writer)?;
 
             let (dep_manifest, _) =
                 Self::parse_package_manifest(dep, dep_name, root_path.to_path_buf())
                     .with_context(|| format!("While processing dependency '{}'", *dep_name))?;
             // download dependencies of dependencies
-            Self::download_dependency_repos(&dep_manifest, build_options, root_path, writer)?;
+            Self::download_patched_dependency_repos(
+                &dep_manifest,
+                patches,
+                build_options,
+                root_path,
+                writer,
+            )?;
         }
         Ok(())
     }
 
+    /// Downloads or updates the dependency if it is remote. Returns the digest reported by the
+    /// resolver of a custom dependency, if any.
     fn download_and_update_if_remote<W: Write>(
         dep_name: PackageName,
         dep: &Dependency,
-        skip_fetch_latest_git_deps: bool,
+        build_options: &BuildConfig,
         writer: &mut W,
-    ) -> Result<()> {
+    ) -> Result<Option<PackageDigest>> {
         if let Some(git_info) = &dep.git_info {
             let git_url = git_info.git_url.as_str();
             let git_rev = git_info.git_rev.as_str();
@@ -568,6 +619,15 @@ impl ResolvingGraph {
 
             // If there is no cached dependency, download it
             if !git_info.download_to.exists() {
+                if build_options.offline {
+                    bail!(
+                        "Git dependency '{}' ({} at '{}') has not been downloaded yet and \
+                        cannot be fetched in the offline mode",
+                        dep_name,
+                        git_url,
+                        git_rev
+                    );
+                }
                 writeln!(
                     writer,
                     "{} {}",
@@ -578,24 +638,32 @@ impl ResolvingGraph {
                 // Confirm git is available.
                 confirm_git_available()?;
 
-                // If the cached folder does not exist, download and clone accordingly
-                Command::new("git")
-                    .args(["clone", git_url, git_path])
-                    .output()
-                    .map_err(|_| {
-                        anyhow::anyhow!("Failed to clone Git repository for package '{}'", dep_name)
-                    })?;
-                Command::new("git")
-                    .args(["-C", git_path, "checkout", git_rev])
-                    .output()
-                    .map_err(|_| {
-                        anyhow::anyhow!(
-                            "Failed to checkout Git reference '{}' for package '{}'",
-                            git_rev,
-                            dep_name
-                        )
-                    })?;
-            } else if !skip_fetch_latest_git_deps {
+                // Fetch only the requested revision without its history. Not every server allows
+                // fetching an arbitrary revision (e.g. an abbreviated commit hash), so fall back
+                // to the full clone when the shallow fetch fails.
+                if !shallow_clone(git_url, git_rev, git_path) {
+                    let _ = std::fs::remove_dir_all(git_path);
+                    Command::new("git")
+                        .args(["clone", git_url, git_path])
+                        .output()
+                        .map_err(|_| {
+                            anyhow::anyhow!(
+                                "Failed to clone Git repository for package '{}'",
+                                dep_name
+                            )
+                        })?;
+                    Command::new("git")
+                        .args(["-C", git_path, "checkout", git_rev])
+                        .output()
+                        .map_err(|_| {
+                            anyhow::anyhow!(
+                                "Failed to checkout Git reference '{}' for package '{}'",
+                                git_rev,
+                                dep_name
+                            )
+                        })?;
+                }
+            } else if !build_options.skip_fetch_latest_git_deps && !build_options.offline {
                 // Confirm git is available.
                 confirm_git_available()?;
 
@@ -608,7 +676,7 @@ impl ResolvingGraph {
                     if let Ok(parsable_version) = String::from_utf8(rev.stdout) {
                         // If it's exactly the same, then it's a git rev
                         if parsable_version.trim().starts_with(git_rev) {
-                            return Ok(());
+                            return Ok(None);
                         }
                     }
                 }
@@ -623,7 +691,7 @@ impl ResolvingGraph {
                         // Tags don't easily update locally and you can't use reset --hard to cleanup
                         // any extra files
                         if parsable_version.trim().starts_with(git_rev) {
-                            return Ok(());
+                            return Ok(None);
                         }
                     }
                 }
@@ -636,14 +704,18 @@ impl ResolvingGraph {
                 )?;
                 // If the current folder exists, do a fetch and reset to ensure that the branch
                 // is up to date
-                // NOTE: this means that you must run the package system with a working network connection
+                // NOTE: this means that you must run the package system with a working network connection,
+                // unless the offline mode is used
+                // Only shallow clones are fetched shallowly, so full clones keep their history
+                let depth: &[&str] = if is_shallow_repository(git_path) {
+                    &["--depth", "1"]
+                } else {
+                    &[]
+                };
                 let status = Command::new("git")
-                    .args([
-                        "-C",
-                        git_path,
-                        "fetch",
-                        "origin",
-                    ])
+                    .args(["-C", git_path, "fetch"])
+                    .args(depth)
+                    .args(["origin", git_rev])
                     .stdout(Stdio::null())
                     .stderr(Stdio::null())
                     .status()
@@ -667,7 +739,7 @@ impl ResolvingGraph {
                         git_path,
                         "reset",
                         "--hard",
-                        &format!("origin/{}", git_rev)
+                        "FETCH_HEAD",
                     ])
                     .stdout(Stdio::null())
                     .stderr(Stdio::null())
@@ -690,9 +762,17 @@ impl ResolvingGraph {
             }
         }
         if let Some(node_info) = &dep.node_info {
-            package_hooks::resolve_custom_dependency(dep_name, node_info)?
+            if !build_options.offline {
+                return package_hooks::resolve_custom_dependency(dep_name, node_info);
+            } else if !node_info.download_to.exists() {
+                bail!(
+                    "Dependency '{}' has not been downloaded yet and cannot be fetched in the \
+                    offline mode",
+                    dep_name
+                );
+            }
         }
-        Ok(())
+        Ok(None)
     }
 }
 
@@ -944,6 +1024,88 @@ impl ResolvedPackage {
             self.source_package.dependencies.keys().copied().collect()
         }
     }
+
+    /// Returns the digest of the transitive dependencies of this package: their source digests
+    /// along with the pinned revisions of the git ones. It changes whenever any of the dependencies
+    /// changes, but not when only this package changes.
+    pub fn dependencies_digest(&self, resolved_graph: &ResolvedGraph) -> PackageDigest {
+        let mut hasher = Sha256::new();
+        let mut update = |parts: &[&str]| {
+            for part in parts {
+                hasher.update(part.as_bytes());
+                hasher.update([0]);
+            }
+        };
+        // The dependencies are kept in ordered collections, so the digest is stable.
+        let dependencies = self.transitive_dependencies(resolved_graph);
+        for name in &dependencies {
+            let digest = resolved_graph.get_package(name).source_digest;
+            update(&[name.as_str(), digest.as_str()]);
+        }
+        let packages = std::iter::once(self).chain(
+            dependencies
+                .iter()
+                .map(|name| resolved_graph.get_package(name)),
+        );
+        for package in packages {
+            let mut declared = package
+                .source_package
+                .dependencies
+                .iter()
+                .collect::<Vec<_>>();
+            if resolved_graph.build_options.dev_mode {
+                declared.extend(&package.source_package.dev_dependencies);
+            }
+            for (name, dependency) in declared {
+                if let Some(git_info) = &dependency.git_info {
+                    update(&[
+                        name.as_str(),
+                        git_info.git_url.as_str(),
+                        git_info.git_rev.as_str(),
+                    ]);
+                }
+            }
+        }
+        PackageDigest::from(format!("{:X}", hasher.finalize()))
+    }
+
+    /// Returns the digest of this package combined with the digest of its transitive dependencies
+    /// (see `dependencies_digest`), so it changes whenever this package or any of its dependencies
+    /// changes.
+    pub fn transitive_digest(&self, resolved_graph: &ResolvedGraph) -> PackageDigest {
+        let mut hasher = Sha256::new();
+        hasher.update(self.source_digest.as_str().as_bytes());
+        hasher.update([0]);
+        hasher.update(self.dependencies_digest(resolved_graph).as_str().as_bytes());
+        PackageDigest::from(format!("{:X}", hasher.finalize()))
+    }
+}
+
+/// Downloads only the given revision (branch, tag or full commit hash) of the repository, returning
+/// whether it succeeded.
+fn shallow_clone(git_url: &str, git_rev: &str, git_path: &str) -> bool {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(["-C", git_path])
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    };
+    std::fs::create_dir_all(git_path).is_ok()
+        && git(&["init", "--quiet"])
+        && git(&["remote", "add", "origin", git_url])
+        && git(&["fetch", "--depth", "1", "origin", git_rev])
+        && git(&["checkout", "--quiet", "FETCH_HEAD"])
+}
+
+/// Returns whether the repository has been cloned shallowly (e.g. by `shallow_clone`).
+fn is_shallow_repository(git_path: &str) -> bool {
+    Command::new("git")
+        .args(["-C", git_path, "rev-parse", "--is-shallow-repository"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).trim() == "true")
 }
 
 fn confirm_git_available() -> Result<()> {
@@ -22,6 +22,11 @@ const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+const PATCH_NAME: &str = "patch";
+// Defaults of the specification test tool, parsed by the tool itself.
+const SPEC_TEST_NAME: &str = "spec-test";
+// Named sets of command line options, parsed by the Move CLI itself.
+const PROFILE_NAME: &str = "profile";
 
 const KNOWN_NAMES: &[&str] = &[
     PACKAGE_NAME,
@@ -30,6 +35,9 @@ const KNOWN_NAMES: &[&str] = &[
     DEV_ADDRESSES_NAME,
     DEPENDENCY_NAME,
     DEV_DEPENDENCY_NAME,
+    PATCH_NAME,
+    SPEC_TEST_NAME,
+    PROFILE_NAME,
 ];
 
 const REQUIRED_FIELDS: &[&str] = &[PACKAGE_NAME];
@@ -86,6 +94,12 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 .transpose()
                 .context("Error parsing '[dev-dependencies]' section of manifest")?
                 .unwrap_or_default();
+            let patches = table
+                .remove(PATCH_NAME)
+                .map(parse_dependencies)
+                .transpose()
+                .context("Error parsing '[patch]' section of manifest")?
+                .unwrap_or_default();
             Ok(PM::SourceManifest {
                 package,
                 addresses,
@@ -93,6 +107,7 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 build,
                 dependencies,
                 dev_dependencies,
+                patches,
             })
         },
         x => {
@@ -318,10 +333,8 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                 "subdir",
                 "address",
             ];
-            let custom_key_opt = &package_hooks::custom_dependency_key();
-            if let Some(key) = custom_key_opt {
-                known_fields.push(key.as_ref())
-            }
+            let custom_keys = package_hooks::custom_dependency_keys();
+            known_fields.extend(custom_keys.iter().map(String::as_str));
             warn_if_unknown_field_names(&table, known_fields.as_slice());
             let subst = table
                 .remove("addr_subst")
@@ -331,14 +344,28 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
             let digest = table.remove("digest").map(parse_digest).transpose()?;
             let mut git_info = None;
             let mut node_info = None;
+            let mut custom_deps = custom_keys
+                .iter()
+                .filter_map(|key| table.remove(key).map(|value| (key, value)))
+                .collect::<Vec<_>>();
+            let location_error = || {
+                let keys = ["local", "git"]
+                    .into_iter()
+                    .chain(custom_keys.iter().map(String::as_str))
+                    .map(|s| format!("'{}'", s))
+                    .collect::<Vec<_>>();
+                format_err!(
+                    "must provide exactly one of {} for dependency.",
+                    keys.join(" or ")
+                )
+            };
+            if custom_deps.len() > 1 {
+                return Err(location_error());
+            }
             match (
                 table.remove("local"),
                 table.remove("git"),
-                if let Some(key) = custom_key_opt {
-                    table.remove(key)
-                } else {
-                    None
-                },
+                custom_deps.pop(),
             ) {
                 (Some(local), None, None) => {
                     let local_str = local
@@ -395,7 +422,7 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                         node_info,
                     })
                 },
-                (None, None, Some(custom_key)) => {
+                (None, None, Some((key, custom_key))) => {
                     let package_name = Symbol::from(dep_name);
                     let address = match table.remove("address") {
                         None => bail!("Address not supplied for 'node' dependency"),
@@ -415,6 +442,7 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                         package_name
                     ));
                     node_info = Some(PM::CustomDepInfo {
+                        key: Symbol::from(key.as_str()),
                         node_url: Symbol::from(node_url),
                         package_address: address,
                         package_name,
@@ -429,20 +457,7 @@ fn parse_dependency(dep_name: &str, tval: TV) -> Result<PM::Dependency> {
                         node_info,
                     })
                 },
-                _ => {
-                    let mut keys = vec!["local", "git"];
-                    if let Some(k) = custom_key_opt {
-                        keys.push(k.as_str())
-                    }
-                    let keys = keys
-                        .into_iter()
-                        .map(|s| format!("'{}'", s))
-                        .collect::<Vec<_>>();
-                    bail!(
-                        "must provide exactly one of {} for dependency.",
-                        keys.join(" or ")
-                    )
-                },
+                _ => Err(location_error()),
             }
         },
         x => bail!("Malformed dependency {}", x),
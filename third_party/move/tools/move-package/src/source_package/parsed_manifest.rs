@@ -26,6 +26,9 @@ pub struct SourceManifest {
     pub build: Option<BuildInfo>,
     pub dependencies: Dependencies,
     pub dev_dependencies: Dependencies,
+    /// Overrides of the (transitive) dependencies with the given names, honored only in the
+    /// manifest of the root package
+    pub patches: Dependencies,
 }
 
 impl fmt::Display for SourceManifest {
@@ -103,6 +106,8 @@ pub struct GitInfo {
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct CustomDepInfo {
+    /// The key the dependency is declared with, selecting the resolver of the dependency
+    pub key: Symbol,
     /// The url of the node to download from
     pub node_url: Symbol,
     /// The address where the package is published. The representation depends
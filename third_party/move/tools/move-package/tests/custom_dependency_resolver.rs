@@ -0,0 +1,79 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::{
+    package_hooks::{register_custom_dependency_resolver, CustomDependencyResolver},
+    source_package::parsed_manifest::{CustomDepInfo, PackageDigest},
+    BuildConfig,
+};
+use move_symbol_pool::Symbol;
+use std::fs;
+use tempfile::tempdir;
+
+/// Serves the packages from memory, as if downloaded from a registry.
+struct TestRegistry {
+    digest: Option<PackageDigest>,
+}
+
+impl CustomDependencyResolver for TestRegistry {
+    fn dependency_key(&self) -> String {
+        "registry".to_owned()
+    }
+
+    fn resolve(
+        &self,
+        dep_name: Symbol,
+        info: &CustomDepInfo,
+    ) -> anyhow::Result<Option<PackageDigest>> {
+        assert_eq!(info.key.as_str(), "registry");
+        assert_eq!(info.node_url.as_str(), "https://registry.test");
+        fs::create_dir_all(info.download_to.join("sources"))?;
+        fs::write(
+            info.download_to.join("Move.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.0.1\"\n", dep_name),
+        )?;
+        fs::write(
+            info.download_to.join("sources/D.move"),
+            format!("module {}::D {{}}", info.package_address),
+        )?;
+        Ok(self.digest)
+    }
+}
+
+#[test]
+fn custom_dependency_resolver() {
+    let dir = tempdir().unwrap();
+    // The custom dependencies are downloaded to `MOVE_HOME`, keep them away from the user's one.
+    std::env::set_var("MOVE_HOME", dir.path().join("move_home"));
+
+    let root = dir.path().join("root");
+    fs::create_dir_all(root.join("sources")).unwrap();
+    fs::write(
+        root.join("Move.toml"),
+        "[package]\nname = \"Root\"\nversion = \"0.0.1\"\n\n[dependencies]\n\
+        Dep = { registry = \"https://registry.test\", address = \"0x2\" }\n",
+    )
+    .unwrap();
+    fs::write(root.join("sources/R.move"), "module 0x2::R {}").unwrap();
+
+    let resolve = |digest| {
+        register_custom_dependency_resolver(Box::new(TestRegistry { digest }));
+        BuildConfig {
+            install_dir: Some(dir.path().join("out")),
+            ..Default::default()
+        }
+        .resolution_graph_for_package(&root, &mut Vec::new())
+    };
+
+    let graph = resolve(None).unwrap();
+    let digest = graph.get_package(&Symbol::from("Dep")).source_digest;
+
+    // The digest reported by the resolver is checked.
+    resolve(Some(digest)).unwrap();
+    let error = format!("{:#}", resolve(Some(Symbol::from("0BAD"))).unwrap_err());
+    assert!(
+        error.contains("Source digest mismatch in dependency 'Dep'"),
+        "{}",
+        error
+    );
+}
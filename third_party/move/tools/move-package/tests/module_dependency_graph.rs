@@ -0,0 +1,72 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::{compilation::module_graph::ModuleDependencyGraph, BuildConfig};
+use std::{collections::BTreeSet, fs};
+use tempfile::tempdir;
+
+#[test]
+fn module_dependency_graph() {
+    let dir = tempdir().unwrap();
+    let sources = dir.path().join("sources");
+    fs::create_dir_all(&sources).unwrap();
+    fs::write(
+        dir.path().join("Move.toml"),
+        "[package]\nname = \"G\"\nversion = \"0.0.1\"\n",
+    )
+    .unwrap();
+    fs::write(
+        sources.join("A.move"),
+        "module 0x2::A { public fun f(): u64 { 0x2::B::g() } }",
+    )
+    .unwrap();
+    fs::write(
+        sources.join("B.move"),
+        "module 0x2::B { public fun g(): u64 { 0x2::C::h() } }",
+    )
+    .unwrap();
+    fs::write(
+        sources.join("C.move"),
+        "module 0x2::C { public fun h(): u64 { 1 } }",
+    )
+    .unwrap();
+    fs::write(
+        sources.join("D.move"),
+        "module 0x2::D { public fun i(): u64 { 2 } }",
+    )
+    .unwrap();
+
+    let package = BuildConfig {
+        install_dir: Some(dir.path().join("out")),
+        ..Default::default()
+    }
+    .compile_package(dir.path(), &mut Vec::new())
+    .unwrap();
+    let graph = package.module_dependency_graph();
+
+    let ids = |ids: &[&str]| {
+        ids.iter()
+            .map(|id| (*id).to_owned())
+            .collect::<BTreeSet<_>>()
+    };
+    assert_eq!(
+        graph.modules.keys().cloned().collect::<BTreeSet<_>>(),
+        ids(&["0x2::A", "0x2::B", "0x2::C", "0x2::D"])
+    );
+    let a = &graph.modules["0x2::A"];
+    assert!(a.is_root);
+    assert_eq!(a.package.as_str(), "G");
+    assert!(a.source_path.ends_with("sources/A.move"));
+    assert_eq!(a.dependencies, ids(&["0x2::B"]));
+
+    assert_eq!(graph.dependencies("0x2::A"), ids(&["0x2::B", "0x2::C"]));
+    assert_eq!(graph.dependents("0x2::C"), ids(&["0x2::A", "0x2::B"]));
+    assert!(graph.dependents("0x2::D").is_empty());
+
+    let file = dir.path().join("graph.json");
+    graph.save_to_json_file(&file).unwrap();
+    assert_eq!(
+        ModuleDependencyGraph::load_from_json_file(&file).unwrap(),
+        graph
+    );
+}
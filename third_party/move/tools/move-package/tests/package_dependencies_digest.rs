@@ -0,0 +1,54 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::BuildConfig;
+use std::{fs, path::Path};
+use tempfile::tempdir;
+
+#[test]
+fn package_dependencies_digest() {
+    let dir = tempdir().unwrap();
+    let write_package = |path: &Path, manifest: &str, module: &str| {
+        fs::create_dir_all(path.join("sources")).unwrap();
+        fs::write(path.join("Move.toml"), manifest).unwrap();
+        fs::write(path.join("sources/M.move"), module).unwrap();
+    };
+    let (root, dep) = (dir.path().join("root"), dir.path().join("dep"));
+    write_package(
+        &root,
+        "[package]\nname = \"Root\"\nversion = \"0.0.1\"\n\n[dependencies]\nDep = { local = \"../dep\" }\n",
+        "module 0x2::R {}",
+    );
+    write_package(
+        &dep,
+        "[package]\nname = \"Dep\"\nversion = \"0.0.1\"\n",
+        "module 0x2::D {}",
+    );
+
+    let digests = || {
+        let graph = BuildConfig {
+            install_dir: Some(dir.path().join("out")),
+            ..Default::default()
+        }
+        .resolution_graph_for_package(&root, &mut Vec::new())
+        .unwrap();
+        let package = graph.get_package(&graph.root_package.package.name);
+        (
+            package.dependencies_digest(&graph),
+            package.transitive_digest(&graph),
+        )
+    };
+    let (dependencies, transitive) = digests();
+
+    // Changes of the package itself change only the transitive digest.
+    fs::write(root.join("sources/M.move"), "module 0x2::R { fun f() {} }").unwrap();
+    let (changed_dependencies, changed_transitive) = digests();
+    assert_eq!(changed_dependencies, dependencies);
+    assert_ne!(changed_transitive, transitive);
+
+    // Changes of a dependency change both.
+    fs::write(dep.join("sources/M.move"), "module 0x2::D { fun f() {} }").unwrap();
+    let (dep_changed_dependencies, dep_changed_transitive) = digests();
+    assert_ne!(dep_changed_dependencies, dependencies);
+    assert_ne!(dep_changed_transitive, changed_transitive);
+}
@@ -0,0 +1,35 @@
+// Copyright (c) The Diem Core Contributors
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::resolution::digest::{compute_file_digests, get_changed_files};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn package_digest_changed_files() {
+    let dir = tempdir().unwrap();
+    let sources = dir.path().join("sources");
+    fs::create_dir_all(&sources).unwrap();
+    fs::write(dir.path().join("Move.toml"), "[package]\nname = \"A\"\n").unwrap();
+    fs::write(sources.join("A.move"), "module 0x1::A {}").unwrap();
+    fs::write(sources.join("B.move"), "module 0x1::B {}").unwrap();
+    fs::write(sources.join("notes.txt"), "hello").unwrap();
+
+    let paths = [dir.path().to_path_buf()];
+    let old = compute_file_digests(&paths).unwrap();
+    assert_eq!(old.len(), 3);
+    assert!(get_changed_files(&old, &compute_file_digests(&paths).unwrap()).is_empty());
+
+    fs::write(sources.join("A.move"), "module 0x1::A { fun f() {} }").unwrap();
+    fs::remove_file(sources.join("B.move")).unwrap();
+    fs::write(sources.join("C.move"), "module 0x1::C {}").unwrap();
+    fs::write(sources.join("notes.txt"), "changed").unwrap();
+
+    let new = compute_file_digests(&paths).unwrap();
+    assert_eq!(get_changed_files(&old, &new), vec![
+        sources.join("A.move"),
+        sources.join("B.move"),
+        sources.join("C.move"),
+    ]);
+}
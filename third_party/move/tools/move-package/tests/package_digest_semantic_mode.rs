@@ -0,0 +1,39 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::resolution::digest::{
+    compute_digest_with_mode, compute_file_digests_with_mode, DigestMode,
+};
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn package_digest_semantic_mode() {
+    let dir = tempdir().unwrap();
+    let sources = dir.path().join("sources");
+    fs::create_dir_all(&sources).unwrap();
+    fs::write(dir.path().join("Move.toml"), "[package]\nname = \"A\"\n").unwrap();
+    let module = sources.join("A.move");
+    fs::write(&module, "module 0x1::A { fun f(): u64 { 1 } }").unwrap();
+
+    let paths = [dir.path().to_path_buf()];
+    let digest = |mode| compute_digest_with_mode(&paths, mode).unwrap();
+    let (raw, semantic) = (digest(DigestMode::Raw), digest(DigestMode::Semantic));
+
+    // Formatting-only changes are ignored by the semantic digest.
+    fs::write(
+        &module,
+        "// The module.\nmodule 0x1::A {\n    /// The function.\n    fun f(): u64 {\n        1 /* one */\n    }\n}\n",
+    )
+    .unwrap();
+    assert_ne!(digest(DigestMode::Raw), raw);
+    assert_eq!(digest(DigestMode::Semantic), semantic);
+
+    fs::write(&module, "module 0x1::A { fun f(): u64 { 2 } }").unwrap();
+    assert_ne!(digest(DigestMode::Semantic), semantic);
+
+    // Files which cannot be tokenized are still hashed.
+    fs::write(sources.join("B.move"), "module 0x1::B { \" }").unwrap();
+    let digests = compute_file_digests_with_mode(&paths, DigestMode::Semantic).unwrap();
+    assert_eq!(digests.len(), 3);
+}
@@ -0,0 +1,94 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::BuildConfig;
+use std::{fs, path::Path, process::Command};
+use tempfile::tempdir;
+
+fn git(path: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=test", "-c", "user.email=test@test"])
+        .arg("-C")
+        .arg(path)
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn package_offline_mode() {
+    let dir = tempdir().unwrap();
+    // The git dependencies are downloaded to `MOVE_HOME`, keep them away from the user's one.
+    std::env::set_var("MOVE_HOME", dir.path().join("move_home"));
+
+    let dep = dir.path().join("dep");
+    fs::create_dir_all(dep.join("sources")).unwrap();
+    fs::write(
+        dep.join("Move.toml"),
+        "[package]\nname = \"Dep\"\nversion = \"0.0.1\"\n",
+    )
+    .unwrap();
+    fs::write(dep.join("sources/D.move"), "module 0x2::D {}").unwrap();
+    git(&dep, &["init", "--quiet"]);
+    git(&dep, &["checkout", "--quiet", "-b", "main"]);
+    git(&dep, &["add", "."]);
+    git(&dep, &["commit", "--quiet", "-m", "first"]);
+    fs::write(dep.join("sources/D.move"), "module 0x2::D { fun f() {} }").unwrap();
+    git(&dep, &["commit", "--quiet", "-am", "second"]);
+
+    let root = dir.path().join("root");
+    fs::create_dir_all(root.join("sources")).unwrap();
+    fs::write(
+        root.join("Move.toml"),
+        format!(
+            "[package]\nname = \"Root\"\nversion = \"0.0.1\"\n\n[dependencies]\n\
+            Dep = {{ git = \"file://{}\", rev = \"main\" }}\n",
+            dep.display()
+        ),
+    )
+    .unwrap();
+    fs::write(root.join("sources/R.move"), "module 0x2::R {}").unwrap();
+
+    let resolve = |offline| {
+        BuildConfig {
+            install_dir: Some(dir.path().join("out")),
+            offline,
+            ..Default::default()
+        }
+        .resolution_graph_for_package(&root, &mut Vec::new())
+    };
+
+    // Nothing has been downloaded yet.
+    let error = format!("{:#}", resolve(true).unwrap_err());
+    assert!(
+        error.contains("cannot be fetched in the offline mode"),
+        "{}",
+        error
+    );
+
+    // Only the requested revision is downloaded.
+    let graph = resolve(false).unwrap();
+    let download = graph
+        .package_table
+        .values()
+        .find(|package| package.source_package.package.name.as_str() == "Dep")
+        .unwrap()
+        .package_path
+        .clone();
+    assert_eq!(
+        git(&download, &["rev-parse", "--is-shallow-repository"]).trim(),
+        "true"
+    );
+    assert_eq!(git(&download, &["rev-list", "--count", "HEAD"]).trim(), "1");
+
+    // The downloaded dependency is used as it is in the offline mode, and updated otherwise.
+    fs::write(dep.join("sources/D.move"), "module 0x2::D { fun g() {} }").unwrap();
+    git(&dep, &["commit", "--quiet", "-am", "third"]);
+    resolve(true).unwrap();
+    let source = || fs::read_to_string(download.join("sources/D.move")).unwrap();
+    assert_eq!(source(), "module 0x2::D { fun f() {} }");
+    resolve(false).unwrap();
+    assert_eq!(source(), "module 0x2::D { fun g() {} }");
+}
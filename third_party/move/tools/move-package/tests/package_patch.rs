@@ -0,0 +1,78 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::BuildConfig;
+use move_symbol_pool::Symbol;
+use std::{fs, path::Path};
+use tempfile::tempdir;
+
+#[test]
+fn package_patch() {
+    let dir = tempdir().unwrap();
+    let write_package = |path: &Path, manifest: &str, module: &str| {
+        fs::create_dir_all(path.join("sources")).unwrap();
+        fs::write(path.join("Move.toml"), manifest).unwrap();
+        fs::write(path.join("sources/M.move"), module).unwrap();
+    };
+    let root = dir.path().join("root");
+    let root_manifest = "[package]\nname = \"Root\"\nversion = \"0.0.1\"\n\n[dependencies]\n\
+        A = { local = \"../a\" }\n";
+    write_package(&root, root_manifest, "module 0x2::R {}");
+    write_package(
+        &dir.path().join("a"),
+        "[package]\nname = \"A\"\nversion = \"0.0.1\"\n\n[dependencies]\n\
+        B = { local = \"../b\", digest = \"0BAD\" }\n",
+        "module 0x2::A {}",
+    );
+    let b = "[package]\nname = \"B\"\nversion = \"0.0.1\"\n";
+    write_package(&dir.path().join("b"), b, "module 0x2::B {}");
+    write_package(
+        &dir.path().join("b_patched"),
+        b,
+        "module 0x2::B { fun f() {} }",
+    );
+
+    let resolve = || {
+        BuildConfig {
+            install_dir: Some(dir.path().join("out")),
+            ..Default::default()
+        }
+        .resolution_graph_for_package(&root, &mut Vec::new())
+    };
+
+    // The digest of the transitive dependency does not match.
+    let error = format!("{:#}", resolve().unwrap_err());
+    assert!(
+        error.contains("Source digest mismatch in dependency 'B'"),
+        "{}",
+        error
+    );
+
+    // The patch overrides the transitive dependency, digest included.
+    fs::write(
+        root.join("Move.toml"),
+        format!(
+            "{}\n[patch]\nB = {{ local = \"../b_patched\" }}\n",
+            root_manifest
+        ),
+    )
+    .unwrap();
+    let graph = resolve().unwrap();
+    let patched = graph.get_package(&Symbol::from("B"));
+    assert_eq!(
+        patched.package_path.canonicalize().unwrap(),
+        dir.path().join("b_patched").canonicalize().unwrap()
+    );
+
+    // The patched dependency is reflected in the digests of the dependents.
+    let root_package = graph.get_package(&Symbol::from("Root"));
+    let patched_digest = root_package.dependencies_digest(&graph);
+    fs::write(
+        dir.path().join("b_patched/sources/M.move"),
+        "module 0x2::B { fun g() {} }",
+    )
+    .unwrap();
+    let graph = resolve().unwrap();
+    let root_package = graph.get_package(&Symbol::from("Root"));
+    assert_ne!(root_package.dependencies_digest(&graph), patched_digest);
+}
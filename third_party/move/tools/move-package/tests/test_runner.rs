@@ -76,6 +76,8 @@ fn run_test_impl(
                 Ok((mut pkg, _)) => {
                     pkg.compiled_package_info.source_digest =
                         Some(PackageDigest::from("ELIDED_FOR_TEST"));
+                    pkg.compiled_package_info.transitive_digest =
+                        Some(PackageDigest::from("ELIDED_FOR_TEST"));
                     pkg.compiled_package_info.build_flags.install_dir =
                         Some(PathBuf::from("ELIDED_FOR_TEST"));
                     format!("{:#?}\n", pkg.compiled_package_info)
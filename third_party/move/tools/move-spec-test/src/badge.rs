@@ -0,0 +1,82 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+/// Label displayed on the left side of the badge.
+const BADGE_LABEL: &str = "mutation score";
+/// Approximate width of a single character (Verdana 11px) used to size the badge.
+const CHAR_WIDTH: usize = 7;
+/// Horizontal padding around each part of the badge.
+const PADDING: usize = 10;
+
+/// Returns the badge color for the given mutation score (in percents).
+fn badge_color(score: f64) -> &'static str {
+    if score >= 80.0 {
+        "#4c1"
+    } else if score >= 60.0 {
+        "#dfb317"
+    } else {
+        "#e05d44"
+    }
+}
+
+/// Renders a shields.io style (flat) SVG badge with the mutation score.
+///
+/// # Arguments
+///
+/// * `score` - the mutation score in percents.
+///
+/// # Returns
+///
+/// * `String` - the SVG document.
+pub fn render_badge(score: f64) -> String {
+    let value = format!("{score:.0}%");
+    let label_width = BADGE_LABEL.len() * CHAR_WIDTH + PADDING;
+    let value_width = value.len() * CHAR_WIDTH + PADDING;
+    let width = label_width + value_width;
+    let color = badge_color(score);
+    // Text is positioned in the middle of each part of the badge.
+    let label_x = label_width / 2;
+    let value_x = label_width + value_width / 2;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{BADGE_LABEL}: {value}">
+  <title>{BADGE_LABEL}: {value}</title>
+  <linearGradient id="s" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="r">
+    <rect width="{width}" height="20" rx="3" fill="#fff"/>
+  </clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="#555"/>
+    <rect x="{label_width}" width="{value_width}" height="20" fill="{color}"/>
+    <rect width="{width}" height="20" fill="url(#s)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="15" fill="#010101" fill-opacity=".3">{BADGE_LABEL}</text>
+    <text x="{label_x}" y="14">{BADGE_LABEL}</text>
+    <text x="{value_x}" y="15" fill="#010101" fill-opacity=".3">{value}</text>
+    <text x="{value_x}" y="14">{value}</text>
+  </g>
+</svg>
+"##
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn badge_contains_score_and_color() {
+        let badge = render_badge(85.5);
+        assert!(badge.starts_with("<svg "));
+        assert!(badge.contains("aria-label=\"mutation score: 86%\""));
+        assert!(badge.contains("fill=\"#4c1\""));
+
+        assert!(render_badge(60.0).contains("fill=\"#dfb317\""));
+        assert!(render_badge(0.0).contains("fill=\"#e05d44\""));
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::prover::ProverOutcome;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// Use of the prover cache during a single run, telling whether the cache helps at all.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    /// Number of the mutants whose prover outcome was found in the cache.
+    pub hits: u64,
+    /// Number of the mutants whose prover outcome was not found in the cache (or was corrupted).
+    pub misses: u64,
+    /// Number of the bytes read from the cache entries.
+    pub bytes_read: u64,
+    /// Number of the bytes written to the cache entries.
+    pub bytes_written: u64,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Prover cache: {} hits, {} misses, {} bytes read, {} bytes written",
+            self.hits, self.misses, self.bytes_read, self.bytes_written
+        )
+    }
+}
+
+/// Local cache of the prover outcomes.
+///
+/// Outcomes are keyed by the hash of the mutated package sources, the package dependencies and the
+/// prover options, so re-running the specification test skips the mutants whose inputs haven't
+/// changed. Timeouts are not cached, as they depend on the machine load and the timeout itself.
+pub(crate) struct ProverCache {
+    dir: PathBuf,
+    /// Digest of the transitive dependencies of the package (part of the keys).
+    dependencies: String,
+    /// Use of the cache since it was opened. The cache is looked up while preparing the mutants
+    /// on a separate thread.
+    stats: Mutex<CacheStats>,
+}
+
+impl ProverCache {
+    /// Opens the cache in the given directory, creating it if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - the directory of the cache.
+    /// * `dependencies` - the digest of the transitive dependencies of the package.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub(crate) fn open(dir: &Path, dependencies: &str) -> anyhow::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            dependencies: dependencies.to_owned(),
+            stats: Mutex::default(),
+        })
+    }
+
+    /// Computes the cache key of the package proved with the given options.
+    ///
+    /// All the files of the package are hashed (in a stable order) except for the build artifacts
+    /// and the Boogie files generated by the prover. The dependencies are covered by their digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the package files cannot be read.
+    pub(crate) fn key(
+        &self,
+        package_path: &Path,
+        prover_conf: &move_prover::cli::Options,
+    ) -> anyhow::Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(prover_conf)?);
+        hasher.update(self.dependencies.as_bytes());
+        hash_dir(&mut hasher, package_path, package_path)?;
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Returns the cached outcome for the given key.
+    pub(crate) fn get(&self, key: &str) -> Option<ProverOutcome> {
+        let content = fs::read(self.entry_path(key)).ok();
+        let outcome = content.as_deref().and_then(|content| {
+            serde_json::from_slice(content)
+                .map_err(|e| warn!("Ignoring corrupted prover cache entry {key}: {e}"))
+                .ok()
+        });
+
+        let mut stats = self.stats.lock().unwrap();
+        stats.bytes_read += content.map_or(0, |content| content.len() as u64);
+        if outcome.is_some() {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+        outcome
+    }
+
+    /// Stores the outcome under the given key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache entry cannot be written.
+    pub(crate) fn put(&self, key: &str, outcome: &ProverOutcome) -> anyhow::Result<()> {
+        if *outcome == ProverOutcome::Timeout {
+            return Ok(());
+        }
+        let content = serde_json::to_vec(outcome)?;
+        fs::write(self.entry_path(key), &content)?;
+        self.stats.lock().unwrap().bytes_written += content.len() as u64;
+        Ok(())
+    }
+
+    /// Returns the use of the cache since it was opened.
+    pub(crate) fn stats(&self) -> CacheStats {
+        *self.stats.lock().unwrap()
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+/// Hashes the paths (relative to the root) and contents of all the files in the directory.
+fn hash_dir(hasher: &mut Sha256, root: &Path, dir: &Path) -> anyhow::Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != "build" {
+                hash_dir(hasher, root, &path)?;
+            }
+        } else if path.extension().map_or(true, |ext| ext != "bpl") {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            hasher.update(relative.to_string_lossy().as_bytes());
+            hasher.update([0]);
+            hasher.update(fs::read(&path)?);
+            hasher.update([0]);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn outcomes_are_cached_by_package_content() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("package");
+        fs::create_dir_all(package.join("sources")).unwrap();
+        fs::create_dir_all(package.join("build")).unwrap();
+        fs::write(package.join("sources/Sum.move"), "x + y").unwrap();
+
+        let options = move_prover::cli::Options::default();
+        let cache = ProverCache::open(&dir.path().join("cache"), "deps").unwrap();
+        let key = cache.key(&package, &options).unwrap();
+
+        // Build artifacts and Boogie files don't change the key.
+        fs::write(package.join("build/artifact"), "data").unwrap();
+        fs::write(package.join("output.bpl"), "procedure").unwrap();
+        assert_eq!(cache.key(&package, &options).unwrap(), key);
+
+        // Changed dependencies do.
+        let changed_deps = ProverCache::open(&dir.path().join("cache"), "new deps").unwrap();
+        assert_ne!(changed_deps.key(&package, &options).unwrap(), key);
+
+        assert_eq!(cache.get(&key), None);
+
+        let outcome = ProverOutcome::Failed {
+            killed_by: "post-condition does not hold".to_owned(),
+            diagnostics: "error: post-condition does not hold".to_owned(),
+        };
+        cache.put(&key, &outcome).unwrap();
+        assert_eq!(cache.get(&key), Some(outcome));
+
+        fs::write(package.join("sources/Sum.move"), "x - y").unwrap();
+        let mutated_key = cache.key(&package, &options).unwrap();
+        assert_ne!(mutated_key, key);
+
+        cache.put(&mutated_key, &ProverOutcome::Timeout).unwrap();
+        assert_eq!(cache.get(&mutated_key), None);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+        assert!(stats.bytes_written > 0);
+        assert_eq!(stats.bytes_read, stats.bytes_written);
+    }
+}
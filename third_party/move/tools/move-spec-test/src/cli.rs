@@ -2,10 +2,12 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use clap::Parser;
-use move_mutator::cli::ModuleFilter;
+use move_command_line_common::output_format::OutputFormat;
+use move_mutator::cli::{FunctionFilter, ModuleFilter};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr, time::Duration};
 
 /// Command line options for specification test tool.
 #[derive(Parser, Default, Debug, Clone, Deserialize, Serialize)]
@@ -15,8 +17,17 @@ pub struct CLIOptions {
     #[clap(long, short, value_parser)]
     pub move_sources: Vec<PathBuf>,
     /// Work only over specified modules.
-    #[clap(long, short, value_parser, default_value = "all")]
+    #[clap(
+        long,
+        short,
+        visible_alias = "modules",
+        value_parser,
+        default_value = "all"
+    )]
     pub include_modules: ModuleFilter,
+    /// Work only over specified functions. Use `<FUNCTION>` or `<MODULE>::<FUNCTION>`, separated by commas.
+    #[clap(long, value_parser, default_value = "all")]
+    pub functions: FunctionFilter,
     /// Optional configuration file for mutator tool.
     #[clap(long, value_parser)]
     pub mutator_conf: Option<PathBuf>,
@@ -26,15 +37,481 @@ pub struct CLIOptions {
     /// Save report to a JSON file.
     #[clap(short, long, value_parser)]
     pub output: Option<PathBuf>,
+    /// Save the mutator report updated with the status (killed or survived) of each mutant.
+    #[clap(long, value_parser)]
+    pub mutator_report_output: Option<PathBuf>,
+    /// Save a single report combining the mutation scores with the mutator report entries (status,
+    /// killing condition and prover diagnostics of each mutant).
+    #[clap(long, value_parser)]
+    pub unified_output: Option<PathBuf>,
+    /// Append each tested mutant with its status to a JSONL file as soon as it's tested.
+    #[clap(long, value_parser)]
+    pub stream_output: Option<PathBuf>,
+    /// Save surviving mutants to a SARIF file (e.g. for GitHub Code Scanning).
+    #[clap(long, value_parser)]
+    pub sarif_output: Option<PathBuf>,
+    /// Save results to a JUnit XML file, where each mutant is a test case (killed mutants pass).
+    #[clap(long, value_parser)]
+    pub junit_output: Option<PathBuf>,
+    /// Save a Markdown summary of the results (e.g. for PR descriptions).
+    #[clap(long, value_parser)]
+    pub markdown_output: Option<PathBuf>,
+    /// Save all the tested mutants with their status to a CSV file.
+    #[clap(long, value_parser)]
+    pub csv_output: Option<PathBuf>,
+    /// Save results in the mutation-testing-elements JSON format used by Stryker.
+    #[clap(long, value_parser)]
+    pub stryker_output: Option<PathBuf>,
+    /// Save an HTML report showing the original and mutated code side by side.
+    #[clap(long, value_parser)]
+    pub html_output: Option<PathBuf>,
+    /// Save the diffs of all the surviving mutants grouped by module to a file.
+    #[clap(long, value_parser)]
+    pub survivors_output: Option<PathBuf>,
+    /// Save all the `ensures`, `aborts_if` and `aborts_with` conditions of the package along with the
+    /// number of mutants killed by each of them to a JSON file.
+    #[clap(long, value_parser)]
+    pub spec_coverage_output: Option<PathBuf>,
+    /// Save a compact JSON summary of the run (outcome, exit code, totals, score, duration and
+    /// per-module scores) for automation.
+    #[clap(long, value_parser)]
+    pub summary_output: Option<PathBuf>,
+    /// Format of the results printed on stdout. With `json`, the summary of the run is printed as
+    /// JSON instead of the tables, so it can be consumed by scripts.
+    #[clap(long, default_value = "human")]
+    pub output_format: OutputFormat,
+    /// Save the prover output, diff and generated Boogie file of each proved mutant to the directory
+    /// (as `<MUTANT_ID>.log` and `<MUTANT_ID>.bpl`), linked from the mutator report entries.
+    #[clap(long, value_parser)]
+    pub prover_logs: Option<PathBuf>,
+    /// Record the scores of the run in the SQLite history database (requires the `history` feature).
+    #[clap(long, value_parser)]
+    pub history: Option<PathBuf>,
+    /// URL of the webhook notified with the JSON summary when the run completes.
+    #[clap(long)]
+    pub webhook: Option<String>,
+    /// Format of the webhook payload (`generic` for the JSON summary, `slack` or `teams`).
+    #[clap(long, default_value = "generic")]
+    pub webhook_format: WebhookFormat,
+    /// Notify the webhook only if the run is a regression against the given JSON report from the
+    /// previous run (newly surviving mutants or a lower mutation score).
+    #[clap(long, value_parser, requires = "webhook")]
+    pub webhook_on_regression: Option<PathBuf>,
+    /// Don't store the diffs of the mutants in the reports.
+    #[clap(long, default_value = "false")]
+    pub omit_diffs: bool,
+    /// Truncate the diffs of the mutants in the reports to the given number of lines.
+    #[clap(long)]
+    pub max_diff_lines: Option<usize>,
+    /// Keep only the surviving mutants in the mutator reports (`--mutator-report-output` and `--stream-output`).
+    #[clap(long, default_value = "false")]
+    pub survivors_only: bool,
+    /// Strip the source code (diffs, replaced values and killing conditions) from all the reports,
+    /// keeping only the structural data like operators, spans, statuses and scores.
+    #[clap(long, default_value = "false")]
+    pub anonymize: bool,
+    /// Fail if the mutation score (in percent) is below the threshold. Use `<PCT>` for the total score
+    /// or `<MODULE>=<PCT>` for the score of a single module. Can be given multiple times.
+    #[clap(long, value_parser)]
+    pub min_score: Vec<ScoreThreshold>,
+    /// Print the diffs of all the surviving mutants grouped by module at the end of the run.
+    #[clap(long, default_value = "false")]
+    pub show_survivors: bool,
+    /// Print the specification conditions which never killed any mutant at the end of the run.
+    #[clap(long, default_value = "false")]
+    pub spec_coverage: bool,
+    /// Print the mutation score of the specification of each function at the end of the run.
+    #[clap(long, default_value = "false")]
+    pub spec_scores: bool,
+    /// Print surviving mutants as GitHub Actions annotations (shown inline in PR diffs).
+    #[clap(long, default_value = "false")]
+    pub github_annotations: bool,
+    /// Prove only the mutants which survived in the previous run, given as the mutator report with
+    /// the mutant statuses (e.g. from `--mutator-report-output`).
+    #[clap(long, value_parser)]
+    pub rerun_survivors: Option<PathBuf>,
+    /// Prove only the given shard of the mutants, given as `<INDEX>/<COUNT>` (e.g. `0/4`). Mutants are
+    /// partitioned by their IDs, so parallel jobs with the same mutants prove disjoint subsets.
+    #[clap(long, value_parser)]
+    pub shard: Option<Shard>,
+    /// File with the IDs of the mutants acknowledged as equivalent (one per line, `#` starts a
+    /// comment). Quarantined mutants are not proved, excluded from the scores and reported separately.
+    #[clap(long, value_parser)]
+    pub quarantine: Option<PathBuf>,
     /// Use previously generated mutants.
     #[clap(long, short, value_parser)]
     pub use_generated_mutants: Option<PathBuf>,
     /// Indicates if mutants should be verified and made sure mutants can compile.
     #[clap(long, default_value = "false")]
     pub verify_mutants: bool,
+    /// Timeout (in seconds) of proving a single mutant. Mutants exceeding it get the `timeout` status.
+    #[clap(long, env = "MOVE_PROVER_TIMEOUT")]
+    pub prover_timeout: Option<u64>,
+    /// Memory limit (in MiB) of proving a single mutant, counting the prover along with the Boogie
+    /// and SMT solver processes (Linux only). Mutants exceeding it get the `out-of-memory` status.
+    /// Requires the prover timeout.
+    #[clap(long)]
+    pub prover_memory_limit: Option<u64>,
+    /// Stop proving a mutant at the first failing condition. The mutated function is verified alone
+    /// first and the rest of the package is proved only if the function verifies.
+    #[clap(long, default_value = "false")]
+    pub stop_on_first_failure: bool,
+    /// Stop the run on the first surviving mutant (e.g. for pre-commit gating). The remaining mutants
+    /// get the `skipped` status and the run fails. By default, all the mutants are proved.
+    #[clap(long, default_value = "false")]
+    pub fail_fast: bool,
+    /// JSON file with the proving times of the functions from the previous runs. Mutants of the
+    /// historically fast functions are proved first. The file is updated after the run.
+    #[clap(long, value_parser)]
+    pub timings: Option<PathBuf>,
+    /// Time budget of proving all the mutants, e.g. `90` (seconds), `45m` or `1h30m`. Once exhausted,
+    /// the mutant being proved is finished and the remaining mutants get the `skipped` status.
+    #[clap(long, value_parser)]
+    pub time_budget: Option<TimeBudget>,
+    /// Seed of the random choices of the mutator (e.g. downsampling). Mutants are proved in the
+    /// order they were generated (ignoring `--timings`), so two runs on the same sources produce
+    /// identical reports.
+    #[clap(long)]
+    pub seed: Option<u64>,
+    /// Lists the planned mutants, the affected modules and the estimated total proving time (based on
+    /// proving the original package) without proving the mutants.
+    #[clap(long)]
+    pub dry_run: bool,
+    /// Directory with the state of the incremental runs. Only the mutants of the modules affected by
+    /// the changes since the previous run are generated and proved, the results of the other mutants
+    /// are carried forward. The state is updated after the run.
+    #[clap(long, value_parser, conflicts_with_all = ["shard", "rerun_survivors"])]
+    pub incremental: Option<PathBuf>,
+    /// Directory of the prover outcomes cache. Mutants whose package sources and prover options
+    /// haven't changed since the previous run are not proved again.
+    #[clap(long, value_parser, env = "MOVE_PROVER_CACHE")]
+    pub prover_cache: Option<PathBuf>,
+    /// Number of cores used by the prover backend to verify the conditions of a mutant in parallel.
+    /// The mutants themselves are proved one at a time.
+    #[clap(long, env = "MOVE_PROVER_CORES")]
+    pub prover_cores: Option<usize>,
     /// Extra arguments to pass to the prover.
     #[clap(long, value_parser)]
     pub extra_prover_args: Option<Vec<String>>,
+    /// Solver used by the prover backend (`z3` or `cvc5`).
+    #[clap(long)]
+    pub prover_backend: Option<ProverBackend>,
+    /// Random seed of the solver used by the prover backend.
+    #[clap(long)]
+    pub prover_seed: Option<usize>,
+    /// Set a prover option, given as `<KEY>=<VALUE>` where the key is the path in the prover
+    /// configuration (e.g. `backend.vc_timeout=40`). Can be given multiple times.
+    #[clap(long, value_parser)]
+    pub prover_option: Vec<ProverOption>,
+    /// Solver timeout (in seconds) of verifying a function, given as `<MODULE>::<FUNCTION>=<SECS>`,
+    /// or as `<SECS>` for all the functions without their own timeout. Unlike `--prover-timeout`,
+    /// a slow function times out on its own without consuming the whole budget of the mutant.
+    /// Can be given multiple times.
+    #[clap(long, value_parser)]
+    pub function_timeout: Vec<FunctionTimeout>,
+}
+
+/// Solver used by the prover backend.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub enum ProverBackend {
+    Z3,
+    Cvc5,
+}
+
+impl FromStr for ProverBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "z3" => Ok(ProverBackend::Z3),
+            "cvc5" => Ok(ProverBackend::Cvc5),
+            _ => Err(format!("Unknown prover backend '{s}', expected z3 or cvc5")),
+        }
+    }
+}
+
+/// Format of the webhook payload.
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub enum WebhookFormat {
+    /// The JSON summary of the run.
+    #[default]
+    Generic,
+    /// Slack incoming webhook message.
+    Slack,
+    /// Microsoft Teams incoming webhook message.
+    Teams,
+}
+
+impl FromStr for WebhookFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "generic" => Ok(WebhookFormat::Generic),
+            "slack" => Ok(WebhookFormat::Slack),
+            "teams" => Ok(WebhookFormat::Teams),
+            _ => Err(format!(
+                "Unknown webhook format '{s}', expected generic, slack or teams"
+            )),
+        }
+    }
+}
+
+/// Format of the report rendered from the results of a completed run.
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub enum ReportFormat {
+    /// The mutation scores in a table, as printed at the end of the run.
+    #[default]
+    Text,
+    /// The JSON report with the mutation scores (as saved with `--output`).
+    Json,
+    /// HTML page showing the original and mutated code side by side.
+    Html,
+    /// Markdown summary (e.g. for PR descriptions).
+    Markdown,
+    /// SARIF with the surviving mutants (e.g. for GitHub Code Scanning).
+    Sarif,
+    /// JUnit XML, where each mutant is a test case.
+    Junit,
+    /// CSV with the status of each mutant.
+    Csv,
+    /// The mutation-testing-elements JSON format used by Stryker.
+    Stryker,
+}
+
+impl ReportFormat {
+    /// Checks if the format needs the status of each mutant, so it can't be rendered from the
+    /// mutation scores only.
+    pub fn needs_mutants(&self) -> bool {
+        !matches!(
+            self,
+            ReportFormat::Text | ReportFormat::Json | ReportFormat::Markdown
+        )
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            "html" => Ok(ReportFormat::Html),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "sarif" => Ok(ReportFormat::Sarif),
+            "junit" => Ok(ReportFormat::Junit),
+            "csv" => Ok(ReportFormat::Csv),
+            "stryker" => Ok(ReportFormat::Stryker),
+            _ => Err(format!(
+                "Unknown report format '{s}', expected text, json, html, markdown, sarif, junit, \
+                 csv or stryker"
+            )),
+        }
+    }
+}
+
+/// Single prover option set from the command line.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ProverOption {
+    /// Path of the option in the prover configuration, with the sections separated by dots.
+    pub key: String,
+    /// Value of the option. Values which are not valid JSON are treated as strings.
+    pub value: serde_json::Value,
+}
+
+impl FromStr for ProverOption {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or_else(|| format!("Invalid prover option '{s}', expected <KEY>=<VALUE>"))?;
+        Ok(ProverOption {
+            key: key.trim().to_owned(),
+            value: serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.to_owned())),
+        })
+    }
+}
+
+/// Solver timeout of verifying a single function (or all functions) set from the command line.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct FunctionTimeout {
+    /// Name of the function qualified with the module name, `None` for all functions.
+    pub function: Option<String>,
+    /// Timeout in seconds.
+    pub secs: usize,
+}
+
+impl FromStr for FunctionTimeout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid function timeout '{s}', expected <MODULE>::<FUNCTION>=<SECS> or <SECS>"
+            )
+        };
+        let (function, secs) = match s.split_once('=') {
+            Some((function, secs)) => {
+                let function = function.trim();
+                let valid = function
+                    .split_once("::")
+                    .is_some_and(|(module, name)| !module.is_empty() && !name.is_empty());
+                if !valid {
+                    return Err(invalid());
+                }
+                (Some(function.to_owned()), secs)
+            },
+            None => (None, s),
+        };
+        Ok(FunctionTimeout {
+            function,
+            secs: secs.trim().parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Time budget of the whole run.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct TimeBudget(pub Duration);
+
+impl FromStr for TimeBudget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid time budget '{s}', expected e.g. 90, 90s, 45m or 1h30m");
+        let s = s.trim();
+        if let Ok(secs) = s.parse::<u64>() {
+            return Ok(TimeBudget(Duration::from_secs(secs)));
+        }
+
+        let mut secs = 0;
+        let mut number = String::new();
+        for c in s.chars() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+            let unit = match c {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return Err(invalid()),
+            };
+            let value = number.parse::<u64>().map_err(|_| invalid())?;
+            secs += value * unit;
+            number.clear();
+        }
+        if !number.is_empty() || s.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(TimeBudget(Duration::from_secs(secs)))
+    }
+}
+
+/// Subset of the mutants proved by a single job when the run is split across parallel jobs.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+pub struct Shard {
+    /// Zero-based index of the shard.
+    pub index: u64,
+    /// Total number of the shards.
+    pub count: u64,
+}
+
+impl Shard {
+    /// Checks if the mutant with the given ID belongs to the shard.
+    ///
+    /// The assignment depends on the mutant ID only, so it's the same for every job regardless of
+    /// the order in which the mutants were generated.
+    #[must_use]
+    pub fn contains(&self, mutant_id: &str) -> bool {
+        let hash = mutant_id
+            .bytes()
+            .fold(0xCBF2_9CE4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+            });
+        hash % self.count == self.index
+    }
+}
+
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || format!("Invalid shard '{s}', expected <INDEX>/<COUNT> with INDEX < COUNT");
+        let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+        let index = index.trim().parse::<u64>().map_err(|_| invalid())?;
+        let count = count.trim().parse::<u64>().map_err(|_| invalid())?;
+        if index >= count {
+            return Err(invalid());
+        }
+        Ok(Shard { index, count })
+    }
+}
+
+/// Minimal mutation score required for the whole package or a single module.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub enum ScoreThreshold {
+    /// Minimal total score of the package.
+    Total(f64),
+    /// Minimal score of the given module.
+    Module(String, f64),
+}
+
+impl FromStr for ScoreThreshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_score = |score: &str| {
+            score
+                .trim_end_matches('%')
+                .parse::<f64>()
+                .ok()
+                .filter(|score| (0.0..=100.0).contains(score))
+                .ok_or_else(|| format!("Invalid score threshold '{score}', expected 0-100"))
+        };
+
+        match s.split_once('=') {
+            Some((module, _)) if module.trim().is_empty() => Err(format!(
+                "Invalid score threshold '{s}', expected <MODULE>=<SCORE> with a module name"
+            )),
+            Some((module, score)) => Ok(ScoreThreshold::Module(
+                module.to_owned(),
+                parse_score(score)?,
+            )),
+            None => Ok(ScoreThreshold::Total(parse_score(s)?)),
+        }
+    }
+}
+
+/// Error returned when the history store is used, but the tool was built without it.
+pub(crate) const HISTORY_UNAVAILABLE: &str =
+    "History store is not available, the tool was built without the `history` feature";
+
+/// This function checks the options which cannot be validated while parsing them (as they may also
+/// come from the manifest), so the run fails upfront rather than after proving all the mutants.
+///
+/// # Errors
+///
+/// Returns an error if an option requires a feature the tool was built without or the prover memory
+/// limit is set without the prover timeout.
+pub fn check_options(options: &CLIOptions) -> anyhow::Result<()> {
+    if options.history.is_some() && !cfg!(feature = "history") {
+        return Err(anyhow!(HISTORY_UNAVAILABLE));
+    }
+    // The memory use is only checked from time to time, so a stuck prover has to be stopped by the
+    // timeout.
+    if options.prover_memory_limit.is_some() && options.prover_timeout.is_none() {
+        return Err(anyhow!(
+            "The prover memory limit requires the prover timeout to be set too"
+        ));
+    }
+    Ok(())
 }
 
 /// This function creates a mutator CLI options from the given spec-test options.
@@ -43,12 +520,27 @@ pub fn create_mutator_options(options: &CLIOptions) -> move_mutator::cli::CLIOpt
     move_mutator::cli::CLIOptions {
         move_sources: options.move_sources.clone(),
         mutate_modules: options.include_modules.clone(),
+        mutate_functions: options.functions.clone(),
         configuration_file: options.mutator_conf.clone(),
         verify_mutants: options.verify_mutants,
+        omit_diffs: options.omit_diffs,
+        max_diff_lines: options.max_diff_lines,
+        seed: options.seed,
         ..Default::default()
     }
 }
 
+/// This function creates the level of detail of the saved reports from the given spec-test options.
+#[must_use]
+pub fn report_detail(options: &CLIOptions) -> move_mutator::report::ReportDetail {
+    move_mutator::report::ReportDetail {
+        omit_diffs: options.omit_diffs,
+        max_diff_lines: options.max_diff_lines,
+        survivors_only: options.survivors_only,
+        anonymize: options.anonymize,
+    }
+}
+
 /// This function generates a prover CLI options from the given spec-test options.
 ///
 /// # Errors
@@ -62,6 +554,58 @@ pub fn generate_prover_options(options: &CLIOptions) -> anyhow::Result<move_prov
         move_prover::cli::Options::default()
     };
 
+    apply_prover_overrides(prover_conf, options)
+}
+
+/// Applies the prover backend, seed and options given on the command line to the prover options.
+fn apply_prover_overrides(
+    mut prover_conf: move_prover::cli::Options,
+    options: &CLIOptions,
+) -> anyhow::Result<move_prover::cli::Options> {
+    if let Some(backend) = options.prover_backend {
+        prover_conf.backend.use_cvc5 = backend == ProverBackend::Cvc5;
+    }
+    if let Some(seed) = options.prover_seed {
+        prover_conf.backend.random_seed = seed;
+    }
+    if let Some(cores) = options.prover_cores {
+        prover_conf.backend.proc_cores = cores;
+    }
+    for timeout in &options.function_timeout {
+        match &timeout.function {
+            Some(function) => {
+                prover_conf
+                    .backend
+                    .function_timeouts
+                    .insert(function.clone(), timeout.secs);
+            },
+            None => prover_conf.backend.vc_timeout = timeout.secs,
+        }
+    }
+    if options.prover_option.is_empty() {
+        return Ok(prover_conf);
+    }
+
+    // Options are set through their serialized form, so any option from the prover configuration
+    // file can be overridden. Unknown keys are rejected while deserializing.
+    let mut value = serde_json::to_value(&prover_conf)?;
+    for option in &options.prover_option {
+        let mut target = &mut value;
+        for section in option.key.split('.') {
+            target = target
+                .as_object_mut()
+                .ok_or_else(|| anyhow!("Invalid prover option key '{}'", option.key))?
+                .entry(section)
+                .or_insert(serde_json::Value::Null);
+        }
+        *target = option.value.clone();
+    }
+
+    let errmapgen = prover_conf.errmapgen;
+    let mut prover_conf: move_prover::cli::Options =
+        serde_json::from_value(value).map_err(|e| anyhow!("Invalid prover option: {e}"))?;
+    // The error map options are not serialized, so they are restored separately.
+    prover_conf.errmapgen = errmapgen;
     Ok(prover_conf)
 }
 
@@ -72,7 +616,8 @@ pub fn generate_prover_options(options: &CLIOptions) -> anyhow::Result<move_prov
 pub fn check_mutator_output_path(options: &move_mutator::cli::CLIOptions) -> Option<PathBuf> {
     if let Some(conf) = &options.configuration_file {
         let c = move_mutator::configuration::Configuration::from_file(conf);
-        if let Ok(c) = c {
+        if let Ok(mut c) = c {
+            c.apply_env_overrides();
             return c.project.out_mutant_dir;
         }
     };
@@ -83,7 +628,59 @@ pub fn check_mutator_output_path(options: &move_mutator::cli::CLIOptions) -> Opt
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{fs, path::PathBuf};
+    use std::{collections::BTreeMap, fs, path::PathBuf};
+
+    #[test]
+    fn history_requires_the_feature() {
+        let options = CLIOptions {
+            history: Some(PathBuf::from("history.db")),
+            ..Default::default()
+        };
+        assert_eq!(check_options(&options).is_ok(), cfg!(feature = "history"));
+        assert!(check_options(&CLIOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn prover_memory_limit_requires_timeout() {
+        let options = CLIOptions {
+            prover_memory_limit: Some(8192),
+            ..Default::default()
+        };
+        assert!(check_options(&options).is_err());
+        assert!(check_options(&CLIOptions {
+            prover_timeout: Some(600),
+            ..options
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn time_budget_is_parsed() {
+        let parse = |s: &str| TimeBudget::from_str(s).map(|budget| budget.0.as_secs());
+        assert_eq!(parse("90"), Ok(90));
+        assert_eq!(parse("90s"), Ok(90));
+        assert_eq!(parse("45m"), Ok(2700));
+        assert_eq!(parse("1h30m"), Ok(5400));
+        assert!(parse("").is_err());
+        assert!(parse("1h30").is_err());
+        assert!(parse("m").is_err());
+        assert!(parse("10d").is_err());
+    }
+
+    #[test]
+    fn shards_partition_mutants() {
+        assert_eq!(Shard::from_str("1/4"), Ok(Shard { index: 1, count: 4 }));
+        assert!(Shard::from_str("4/4").is_err());
+        assert!(Shard::from_str("1").is_err());
+        assert!(Shard::from_str("a/4").is_err());
+
+        let shards = (0..3)
+            .map(|index| Shard { index, count: 3 })
+            .collect::<Vec<_>>();
+        for id in ["3f2a9c1b7d5e8f01", "0000000000000000", "a1b2c3d4e5f60718"] {
+            assert_eq!(shards.iter().filter(|shard| shard.contains(id)).count(), 1);
+        }
+    }
 
     #[test]
     fn cli_options_starts_empty() {
@@ -102,13 +699,48 @@ mod tests {
         options.move_sources.push(PathBuf::from("path/to/file"));
         options.include_modules =
             ModuleFilter::Selected(vec!["test1".to_string(), "test2".to_string()]);
+        options.functions = FunctionFilter::Selected(vec!["test1::sum".to_string()]);
         options.mutator_conf = Some(PathBuf::from("path/to/mutator/conf"));
+        options.seed = Some(42);
 
         let mutator_options = create_mutator_options(&options);
+        assert_eq!(mutator_options.mutate_functions, options.functions);
 
         assert_eq!(mutator_options.move_sources, options.move_sources);
         assert_eq!(mutator_options.mutate_modules, options.include_modules);
         assert_eq!(mutator_options.configuration_file, options.mutator_conf);
+        assert_eq!(mutator_options.seed, Some(42));
+    }
+
+    #[test]
+    fn score_threshold_is_parsed() {
+        assert_eq!("80".parse(), Ok(ScoreThreshold::Total(80.0)));
+        assert_eq!(
+            "Sum=62.5%".parse(),
+            Ok(ScoreThreshold::Module("Sum".to_owned(), 62.5))
+        );
+        assert!("120".parse::<ScoreThreshold>().is_err());
+        assert!("Sum=high".parse::<ScoreThreshold>().is_err());
+        assert!("=80".parse::<ScoreThreshold>().is_err());
+    }
+
+    #[test]
+    fn report_detail_is_taken_from_options() {
+        let options = CLIOptions {
+            omit_diffs: true,
+            max_diff_lines: Some(10),
+            survivors_only: true,
+            ..Default::default()
+        };
+
+        let detail = report_detail(&options);
+        assert!(detail.omit_diffs);
+        assert_eq!(detail.max_diff_lines, Some(10));
+        assert!(detail.survivors_only);
+
+        let mutator_options = create_mutator_options(&options);
+        assert!(mutator_options.omit_diffs);
+        assert_eq!(mutator_options.max_diff_lines, Some(10));
     }
 
     #[test]
@@ -168,4 +800,65 @@ mod tests {
         );
         assert_eq!(prover_options.backend.z3_exe, "/path/to/z3".to_owned());
     }
+
+    #[test]
+    fn generate_prover_options_applies_overrides() {
+        let options = CLIOptions {
+            prover_backend: Some("cvc5".parse().unwrap()),
+            prover_seed: Some(42),
+            prover_cores: Some(2),
+            prover_option: vec![
+                "backend.vc_timeout=40".parse().unwrap(),
+                "backend.boogie_exe=/path/to/boogie".parse().unwrap(),
+                "prover.check_inconsistency=true".parse().unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        let prover_options = generate_prover_options(&options).unwrap();
+        assert!(prover_options.backend.use_cvc5);
+        assert_eq!(prover_options.backend.random_seed, 42);
+        assert_eq!(prover_options.backend.proc_cores, 2);
+        assert_eq!(prover_options.backend.vc_timeout, 40);
+        assert_eq!(prover_options.backend.boogie_exe, "/path/to/boogie");
+        assert!(prover_options.prover.check_inconsistency);
+    }
+
+    #[test]
+    fn generate_prover_options_applies_function_timeouts() {
+        let options = CLIOptions {
+            function_timeout: vec![
+                "30".parse().unwrap(),
+                "Vault::withdraw=120".parse().unwrap(),
+                " Sum::sum = 5".parse().unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        let prover_options = generate_prover_options(&options).unwrap();
+        assert_eq!(prover_options.backend.vc_timeout, 30);
+        assert_eq!(
+            prover_options.backend.function_timeouts,
+            BTreeMap::from([
+                ("Sum::sum".to_owned(), 5),
+                ("Vault::withdraw".to_owned(), 120)
+            ])
+        );
+
+        assert!("withdraw=120".parse::<FunctionTimeout>().is_err());
+        assert!("Vault::=120".parse::<FunctionTimeout>().is_err());
+        assert!("Vault::withdraw=2m".parse::<FunctionTimeout>().is_err());
+    }
+
+    #[test]
+    fn generate_prover_options_rejects_unknown_options() {
+        assert!("no_value".parse::<ProverOption>().is_err());
+        assert!("mystery".parse::<ProverBackend>().is_err());
+
+        let options = CLIOptions {
+            prover_option: vec!["backend.no_such_option=1".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(generate_prover_options(&options).is_err());
+    }
 }
@@ -0,0 +1,134 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_mutator::report::{MutationReport, Report};
+use serde::Serialize;
+use std::{io::Write, path::Path};
+
+/// Single row of the CSV report describing one mutant.
+#[derive(Debug, Serialize)]
+struct CsvRecord<'a> {
+    mutant_id: &'a str,
+    file: String,
+    module: &'a str,
+    function: &'a str,
+    operator: String,
+    span: String,
+    status: String,
+    killer: &'a str,
+}
+
+/// Writes the results of the specification testing in the CSV format.
+///
+/// Each row describes a single mutant. If the mutant consists of multiple mutations, their operators
+/// and spans are joined with `;`. The `killer` column contains the specification condition which killed
+/// the mutant and is empty for surviving mutants.
+///
+/// # Arguments
+///
+/// * `writer` - the writer to which the CSV data is written.
+/// * `report` - the mutator report with the status of each mutant.
+///
+/// # Errors
+///
+/// Returns an error if the data cannot be written.
+pub fn write_csv<W: Write>(writer: W, report: &Report) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+
+    for entry in report.get_mutants() {
+        writer.serialize(CsvRecord {
+            mutant_id: entry.mutant_id(),
+            file: entry.original_file_path().to_string_lossy().into_owned(),
+            module: entry.get_module_name(),
+            function: entry.get_function_name(),
+            operator: join_mutations(entry, |m| m.get_operator_name().to_owned()),
+            span: join_mutations(entry, |m| {
+                let range = m.get_changed_place();
+                format!("{}..{}", range.start(), range.end())
+            }),
+            status: entry.status().to_string(),
+            killer: entry.killed_by().unwrap_or_default(),
+        })?;
+    }
+
+    Ok(writer.flush()?)
+}
+
+/// Saves the CSV report to the given file.
+/// The file is created if it does not exist, otherwise it is overwritten.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_to_csv_file(path: &Path, report: &Report) -> anyhow::Result<()> {
+    info!("Saving CSV report to {}", path.display());
+    write_csv(std::fs::File::create(path)?, report)
+}
+
+/// Joins the values extracted from all the mutations of the mutant.
+fn join_mutations<F>(entry: &MutationReport, value: F) -> String
+where
+    F: FnMut(&move_mutator::report::Mutation) -> String,
+{
+    entry
+        .get_mutations()
+        .iter()
+        .map(value)
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::{MutantStatus, Mutation, Range};
+
+    fn mutant(old: &str, new: &str, status: MutantStatus) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            "Sum",
+            "sum",
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(2, 3),
+            "binary_operator_replacement".to_string(),
+            old.to_string(),
+            new.to_string(),
+        ));
+        entry.set_status(status);
+        entry
+    }
+
+    #[test]
+    fn csv_contains_row_per_mutant() {
+        let mut killed_mutant = mutant("+", "-", MutantStatus::Killed);
+        killed_mutant.set_killed_by("post-condition does not hold, \"ensures\"");
+
+        let mut report = Report::new();
+        report.add_entry(killed_mutant.clone());
+        report.add_entry(mutant("+", "*", MutantStatus::Survived));
+
+        let mut out = Vec::new();
+        write_csv(&mut out, &report).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "mutant_id,file,module,function,operator,span,status,killer"
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "{},sources/Sum.move,Sum,sum,binary_operator_replacement,2..3,killed,\"post-condition does not hold, \"\"ensures\"\"\"",
+                killed_mutant.mutant_id()
+            )
+        );
+        assert!(lines[2].ends_with(",2..3,survived,"));
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::Report;
+use std::{collections::BTreeSet, fmt, path::PathBuf};
+
+/// Surviving mutant as identified in the specification test report.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SurvivingMutant {
+    /// The file where the mutant resides.
+    pub file: PathBuf,
+    /// Module::function where the mutant resides.
+    pub module_func: String,
+    /// The diff of the mutant.
+    pub diff: String,
+}
+
+/// The difference between two specification test reports (e.g. from two CI runs).
+#[derive(Debug)]
+pub struct ReportDiff {
+    /// Mutants which survive in the new report, but didn't survive in the old one.
+    pub newly_surviving: Vec<SurvivingMutant>,
+    /// Mutants which survived in the old report, but don't survive in the new one.
+    pub newly_killed: Vec<SurvivingMutant>,
+    /// Mutation score of the old report (in percents).
+    pub score_before: f64,
+    /// Mutation score of the new report (in percents).
+    pub score_after: f64,
+}
+
+impl ReportDiff {
+    /// Compares two reports.
+    ///
+    /// Mutants are matched by their file, function and diff, so the same mutant generated in both
+    /// runs is recognized even if the mutants were generated in a different order.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - the report from the previous run.
+    /// * `new` - the report from the current run.
+    ///
+    /// # Returns
+    ///
+    /// * `ReportDiff` - the difference between the reports.
+    pub fn new(old: &Report, new: &Report) -> Self {
+        let old_survivors = surviving_mutants(old);
+        let new_survivors = surviving_mutants(new);
+
+        Self {
+            newly_surviving: new_survivors.difference(&old_survivors).cloned().collect(),
+            newly_killed: old_survivors.difference(&new_survivors).cloned().collect(),
+            score_before: old.mutation_score(),
+            score_after: new.mutation_score(),
+        }
+    }
+
+    /// Returns the change of the mutation score (in percentage points).
+    pub fn score_delta(&self) -> f64 {
+        self.score_after - self.score_before
+    }
+
+    /// Returns true if the new report is worse than the old one - there are new surviving mutants or
+    /// the mutation score has dropped.
+    pub fn is_regression(&self) -> bool {
+        !self.newly_surviving.is_empty() || self.score_delta() < 0.0
+    }
+
+    /// Renders only the newly surviving mutants, leaving out the rest of the difference.
+    pub fn render_regressions(&self) -> String {
+        let mut output = format!("New surviving mutants: {}\n", self.newly_surviving.len());
+        for mutant in &self.newly_surviving {
+            output.push_str(&format!("\n{mutant}"));
+        }
+        output
+    }
+}
+
+impl fmt::Display for SurvivingMutant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}::{}\n{}",
+            self.file.display(),
+            self.module_func,
+            self.diff
+        )
+    }
+}
+
+impl fmt::Display for ReportDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Mutation score: {:.2}% -> {:.2}% ({:+.2})",
+            self.score_before,
+            self.score_after,
+            self.score_delta()
+        )?;
+
+        for (title, mutants) in [
+            ("Newly surviving mutants", &self.newly_surviving),
+            ("Newly killed mutants", &self.newly_killed),
+        ] {
+            writeln!(f, "\n{title}: {}", mutants.len())?;
+            for mutant in mutants {
+                write!(f, "\n{mutant}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects all the surviving mutants from the report.
+fn surviving_mutants(report: &Report) -> BTreeSet<SurvivingMutant> {
+    report
+        .entries()
+        .iter()
+        .flat_map(|(file, stats)| {
+            stats.iter().flat_map(move |stat| {
+                stat.mutants_alive_diffs
+                    .iter()
+                    .map(move |diff| SurvivingMutant {
+                        file: file.clone(),
+                        module_func: stat.module_func.clone(),
+                        diff: diff.clone(),
+                    })
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn report(tested: u32, killed: u32, survivors: &[&str]) -> Report {
+        let mut report = Report::new();
+        let path = Path::new("sources/Sum.move");
+        for _ in 0..tested {
+            report.increment_mutants_tested(path, "Sum::sum");
+        }
+        for _ in 0..killed {
+            report.increment_mutants_killed(path, "Sum::sum");
+        }
+        for diff in survivors {
+            report.add_mutants_alive_diff(path, "Sum::sum", diff);
+        }
+        report
+    }
+
+    #[test]
+    fn diff_detects_newly_surviving_and_killed_mutants() {
+        let old = report(4, 2, &["a", "b"]);
+        let new = report(4, 2, &["b", "c"]);
+
+        let diff = ReportDiff::new(&old, &new);
+        assert_eq!(diff.newly_surviving.len(), 1);
+        assert_eq!(diff.newly_surviving[0].diff, "c");
+        assert_eq!(diff.newly_killed.len(), 1);
+        assert_eq!(diff.newly_killed[0].diff, "a");
+        assert_eq!(diff.score_delta(), 0.0);
+        assert!(diff.is_regression());
+    }
+
+    #[test]
+    fn improved_report_is_not_a_regression() {
+        let old = report(4, 2, &["a", "b"]);
+        let new = report(4, 3, &["b"]);
+
+        let diff = ReportDiff::new(&old, &new);
+        assert!(diff.newly_surviving.is_empty());
+        assert_eq!(diff.score_delta(), 25.0);
+        assert!(!diff.is_regression());
+        assert!(diff
+            .to_string()
+            .starts_with("Mutation score: 50.00% -> 75.00% (+25.00)"));
+    }
+
+    #[test]
+    fn regressions_list_only_new_survivors() {
+        let old = report(4, 2, &["a", "b"]);
+        let new = report(4, 2, &["b", "c"]);
+
+        assert_eq!(
+            ReportDiff::new(&old, &new).render_regressions(),
+            "New surviving mutants: 1\n\nsources/Sum.move::Sum::sum\nc\n"
+        );
+        assert_eq!(
+            ReportDiff::new(&old, &old).render_regressions(),
+            "New surviving mutants: 0\n"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::timings::Timings;
+use move_mutator::report::{MutationReport, Report};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    time::Duration,
+};
+
+/// Renders the plan of the run shown by `--dry-run`: the mutants which would be proved, the affected
+/// modules and the estimated total proving time.
+///
+/// The proving time of each mutant is estimated from the timings of its function (if available) or
+/// the proving time of the original package, so it's rather an upper bound (e.g. the prover stops
+/// early for mutants killed with `--stop-on-first-failure`).
+///
+/// # Arguments
+///
+/// * `report` - the mutator report with the planned mutants in the proving order.
+/// * `is_quarantined` - tells whether the mutant is quarantined, so it won't be proved.
+/// * `baseline` - the proving time of the original package.
+/// * `timings` - the proving times of the functions from the previous runs, if any.
+/// * `time_budget` - the time budget of proving all the mutants, if any.
+///
+/// # Returns
+///
+/// * `String` - the rendered plan.
+pub(crate) fn render_plan(
+    report: &Report,
+    is_quarantined: impl Fn(&MutationReport) -> bool,
+    baseline: Duration,
+    timings: Option<&Timings>,
+    time_budget: Option<Duration>,
+) -> String {
+    let (quarantined, planned): (Vec<_>, Vec<_>) =
+        report.get_mutants().iter().partition(|m| is_quarantined(m));
+
+    let mut output = format!("Planned mutants: {}\n", planned.len());
+    for mutant in &planned {
+        let _ = writeln!(output, "  {}", describe(mutant));
+    }
+    if !quarantined.is_empty() {
+        let _ = writeln!(
+            output,
+            "Quarantined mutants (not proved): {}",
+            quarantined.len()
+        );
+    }
+
+    let mut modules = BTreeMap::<&str, (usize, BTreeSet<&str>)>::new();
+    for mutant in &planned {
+        let (mutants, functions) = modules.entry(mutant.get_module_name()).or_default();
+        *mutants += 1;
+        functions.insert(mutant.get_function_name());
+    }
+    let _ = writeln!(output, "\nAffected modules: {}", modules.len());
+    for (module, (mutants, functions)) in &modules {
+        let _ = writeln!(
+            output,
+            "  {module}: {mutants} mutants in {} functions",
+            functions.len()
+        );
+    }
+
+    let costs = planned
+        .iter()
+        .map(|m| {
+            timings
+                .and_then(|t| t.expected_cost(m))
+                .map_or(baseline, Duration::from_millis)
+        })
+        .collect::<Vec<_>>();
+    let total = costs.iter().sum::<Duration>();
+    let _ = writeln!(
+        output,
+        "\nBaseline proving time: {}",
+        format_duration(baseline)
+    );
+    let _ = writeln!(
+        output,
+        "Estimated total proving time: {}",
+        format_duration(total)
+    );
+
+    if let Some(budget) = time_budget.filter(|budget| total > *budget) {
+        let mut elapsed = Duration::ZERO;
+        let proved = costs
+            .iter()
+            .take_while(|cost| {
+                let within = elapsed < budget;
+                elapsed += **cost;
+                within
+            })
+            .count();
+        let _ = writeln!(
+            output,
+            "The time budget of {} is exceeded, about {proved} mutants would be proved",
+            format_duration(budget)
+        );
+    }
+
+    output
+}
+
+/// Describes the mutant with its ID, function, location and operators.
+fn describe(mutant: &MutationReport) -> String {
+    let mut description = format!(
+        "{} {}::{} ({}",
+        mutant.mutant_id(),
+        mutant.get_module_name(),
+        mutant.get_function_name(),
+        mutant.original_file_path().display()
+    );
+    if let Some(position) = mutant
+        .get_mutations()
+        .first()
+        .and_then(|m| m.get_changed_place().start_position())
+    {
+        let _ = write!(description, ":{}:{}", position.line, position.column);
+    }
+    description.push(')');
+
+    let operators = mutant
+        .get_mutations()
+        .iter()
+        .map(|m| m.get_operator_name())
+        .collect::<Vec<_>>();
+    if !operators.is_empty() {
+        let _ = write!(description, " {}", operators.join(", "));
+    }
+    description
+}
+
+/// Formats the duration in a human readable way, e.g. `1h 23m 45s` or `12.3s`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs % 3600 / 60, secs % 60) {
+        (0, 0, _) => format!("{:.1}s", duration.as_secs_f64()),
+        (0, minutes, secs) => format!("{minutes}m {secs}s"),
+        (hours, minutes, secs) => format!("{hours}h {minutes}m {secs}s"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::{Mutation, Range};
+    use std::path::Path;
+
+    fn mutant(module: &str, function: &str, mutated: &str) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            module,
+            function,
+            mutated,
+            "x + y\n",
+        );
+        let mut range = Range::new(2, 3);
+        range.locate("x + y\n");
+        entry.add_modification(Mutation::new(
+            range,
+            "binary_operator_replacement".to_owned(),
+            "+".to_owned(),
+            mutated[2..3].to_owned(),
+        ));
+        entry
+    }
+
+    #[test]
+    fn plan_lists_mutants_and_estimates_time() {
+        let mut report = Report::new();
+        report.add_entry(mutant("Sum", "sum", "x - y\n"));
+        report.add_entry(mutant("Sum", "sub", "x * y\n"));
+        report.add_entry(mutant("Mul", "mul", "x / y\n"));
+        let quarantined = report.get_mutants()[2].mutant_id().to_owned();
+        let first = report.get_mutants()[0].mutant_id().to_owned();
+
+        let plan = render_plan(
+            &report,
+            |m| m.mutant_id() == quarantined,
+            Duration::from_secs(90),
+            None,
+            Some(Duration::from_secs(60)),
+        );
+        assert!(plan.starts_with(&format!(
+            "Planned mutants: 2\n  {first} Sum::sum (sources/Sum.move:1:3) \
+             binary_operator_replacement\n"
+        )));
+        assert!(plan.contains("Quarantined mutants (not proved): 1\n"));
+        assert!(plan.contains("\nAffected modules: 1\n  Sum: 2 mutants in 2 functions\n"));
+        assert!(plan.contains("Baseline proving time: 1m 30s\n"));
+        assert!(plan.contains("Estimated total proving time: 3m 0s\n"));
+        assert!(plan
+            .ends_with("The time budget of 1m 0s is exceeded, about 1 mutants would be proved\n"));
+    }
+
+    #[test]
+    fn durations_are_formatted() {
+        assert_eq!(format_duration(Duration::from_millis(12_345)), "12.3s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m 5s");
+        assert_eq!(format_duration(Duration::from_secs(5025)), "1h 23m 45s");
+    }
+}
@@ -0,0 +1,191 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::report::{Report, Score};
+use rusqlite::{params, Connection};
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tabled::{builder::Builder, settings::Style};
+
+/// SQLite-backed store of the specification test results, used to track the scores over time.
+pub struct HistoryStore {
+    connection: Connection,
+}
+
+/// Results of a single recorded run.
+#[derive(Debug)]
+pub struct RunRecord {
+    /// The ID of the run.
+    pub id: i64,
+    /// The date of the run (UTC).
+    pub date: String,
+    /// The total score of the run.
+    pub total: Score,
+    /// Scores per module.
+    pub modules: BTreeMap<String, Score>,
+}
+
+impl HistoryStore {
+    /// Opens the history database, creating it (and the schema) if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened or initialized.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                tested INTEGER NOT NULL,
+                killed INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS module_scores (
+                run_id INTEGER NOT NULL REFERENCES runs(id),
+                module TEXT NOT NULL,
+                tested INTEGER NOT NULL,
+                killed INTEGER NOT NULL,
+                PRIMARY KEY (run_id, module)
+            );",
+        )?;
+        Ok(Self { connection })
+    }
+
+    /// Records the results of the run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the results cannot be stored.
+    pub fn record(&mut self, report: &Report) -> anyhow::Result<i64> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+
+        let tx = self.connection.transaction()?;
+        tx.execute(
+            "INSERT INTO runs (timestamp, tested, killed) VALUES (?1, ?2, ?3)",
+            params![timestamp, report.mutants_tested(), report.mutants_killed()],
+        )?;
+        let run_id = tx.last_insert_rowid();
+
+        for (module, score) in report.module_scores() {
+            tx.execute(
+                "INSERT INTO module_scores (run_id, module, tested, killed) VALUES (?1, ?2, ?3, ?4)",
+                params![run_id, module, score.tested, score.killed],
+            )?;
+        }
+        tx.commit()?;
+
+        info!("Run {run_id} recorded in the history store");
+        Ok(run_id)
+    }
+
+    /// Returns the last `limit` runs, starting from the oldest one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the history cannot be read.
+    pub fn runs(&self, limit: usize) -> anyhow::Result<Vec<RunRecord>> {
+        let mut statement = self.connection.prepare(
+            "SELECT id, datetime(timestamp, 'unixepoch'), tested, killed FROM runs ORDER BY id DESC LIMIT ?1",
+        )?;
+        let mut runs = statement
+            .query_map(params![limit as i64], |row| {
+                Ok(RunRecord {
+                    id: row.get(0)?,
+                    date: row.get(1)?,
+                    total: Score::new(row.get(2)?, row.get(3)?),
+                    modules: BTreeMap::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        runs.reverse();
+
+        let mut statement = self
+            .connection
+            .prepare("SELECT module, tested, killed FROM module_scores WHERE run_id = ?1")?;
+        for run in &mut runs {
+            run.modules = statement
+                .query_map(params![run.id], |row| {
+                    Ok((row.get(0)?, Score::new(row.get(1)?, row.get(2)?)))
+                })?
+                .collect::<Result<BTreeMap<_, _>, _>>()?;
+        }
+
+        Ok(runs)
+    }
+}
+
+/// Renders the evolution of the scores - one row per run with the total score and the scores of all
+/// the modules seen in these runs.
+pub fn render_trends(runs: &[RunRecord]) -> String {
+    let modules = runs
+        .iter()
+        .flat_map(|run| run.modules.keys().cloned())
+        .collect::<std::collections::BTreeSet<_>>();
+
+    let mut builder = Builder::new();
+    builder.push_record(
+        ["Run", "Date", "Total", "Change"]
+            .into_iter()
+            .map(String::from)
+            .chain(modules.iter().cloned()),
+    );
+
+    let mut previous: Option<f64> = None;
+    for run in runs {
+        let change = previous.map_or_else(String::new, |p| format!("{:+.2}", run.total.score - p));
+        previous = Some(run.total.score);
+
+        builder.push_record(
+            [
+                run.id.to_string(),
+                run.date.clone(),
+                format!("{:.2}%", run.total.score),
+                change,
+            ]
+            .into_iter()
+            .chain(modules.iter().map(|module| {
+                run.modules
+                    .get(module)
+                    .map_or_else(|| "-".to_owned(), |s| format!("{:.2}%", s.score))
+            })),
+        );
+    }
+
+    builder.build().with(Style::modern_rounded()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_are_recorded_and_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = HistoryStore::open(&dir.path().join("history.db")).unwrap();
+
+        let path = Path::new("sources/Sum.move");
+        let mut report = Report::new();
+        report.increment_mutants_tested(path, "Sum::sum");
+        report.increment_mutants_tested(path, "Sum::sum");
+        store.record(&report).unwrap();
+
+        report.increment_mutants_killed(path, "Sum::sum");
+        store.record(&report).unwrap();
+
+        let runs = store.runs(10).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].total.score, 0.0);
+        assert_eq!(runs[1].total.score, 50.0);
+        assert_eq!(runs[1].modules["Sum"], Score::new(2, 1));
+
+        assert_eq!(store.runs(1).unwrap()[0].id, runs[1].id);
+
+        let trends = render_trends(&runs);
+        assert!(trends.contains("+50.00"));
+        assert!(trends.contains("Sum"));
+    }
+}
@@ -0,0 +1,420 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli::CLIOptions;
+use move_mutator::report::{MutantStatus, MutationReport, Report};
+use move_package::{
+    compilation::module_graph::{ModuleDependencyGraph, ModuleNode},
+    resolution::digest::{compute_file_digests, get_changed_files},
+    source_package::parsed_manifest::PackageDigest,
+    BuildConfig,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Name of the file with the digests of the package files from the previous run.
+const STATE_FILE: &str = "state.json";
+/// Name of the file with the mutator report (along with the statuses) from the previous run.
+const REPORT_FILE: &str = "report.json";
+
+/// Digests of the package files and the options recorded after the run.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+struct State {
+    /// Digest of the prover and mutator options.
+    options: String,
+    /// Digests of the package sources and manifest, keyed by the path relative to the package root.
+    files: BTreeMap<PathBuf, String>,
+    /// Digest of the transitive dependencies of the package.
+    #[serde(default)]
+    dependencies: String,
+}
+
+/// Incremental specification test.
+///
+/// The package file digests are compared with the ones recorded in the previous run. Only the
+/// mutants of the modules affected by the changes are generated and proved, while the results of
+/// the mutants of the untouched modules are carried forward from the previous report.
+///
+/// A module is affected if its source file has changed, if any of its mutants wasn't proved in the
+/// previous run (e.g. skipped or quarantined) or if it uses or is used by an affected module (as
+/// the specifications and the code of the modules depend on each other when verified). Changes of
+/// the manifest, the dependencies or the options affect the whole package.
+pub(crate) struct Incremental {
+    /// Directory where the state of the runs is kept.
+    dir: PathBuf,
+    /// State of the current package.
+    state: State,
+    /// Modules affected by the changes, `None` if the whole package is affected.
+    affected: Option<BTreeSet<String>>,
+    /// Mutants of the untouched modules along with their results from the previous run.
+    carried: Report,
+}
+
+impl Incremental {
+    /// Loads the state of the previous run from the given directory (if any) and compares it with
+    /// the current package.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - the directory where the state of the runs is kept.
+    /// * `options` - the command line options.
+    /// * `config` - the build configuration.
+    /// * `package_path` - the path to the package root.
+    /// * `prover_conf` - the prover options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the package files or the previous state cannot be read.
+    pub(crate) fn load(
+        dir: &Path,
+        options: &CLIOptions,
+        config: &BuildConfig,
+        package_path: &Path,
+        prover_conf: &move_prover::cli::Options,
+    ) -> anyhow::Result<Self> {
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(&crate::cli::create_mutator_options(
+            options,
+        ))?);
+        hasher.update(serde_json::to_vec(prover_conf)?);
+        let state = State {
+            options: format!("{:x}", hasher.finalize()),
+            files: file_digests(package_path)?,
+            dependencies: dependencies_digest(config, package_path)?,
+        };
+
+        let mut incremental = Self {
+            dir: dir.to_path_buf(),
+            state,
+            affected: None,
+            carried: Report::new(),
+        };
+
+        let (state_file, report_file) = (dir.join(STATE_FILE), dir.join(REPORT_FILE));
+        if !state_file.exists() || !report_file.exists() {
+            info!("No previous incremental run found, the whole package is tested");
+            return Ok(incremental);
+        }
+        let previous: State = serde_json::from_slice(&fs::read(state_file)?)?;
+        if previous.options != incremental.state.options {
+            info!("Options have changed since the previous run, the whole package is tested");
+            return Ok(incremental);
+        }
+        if previous.dependencies != incremental.state.dependencies {
+            info!("Dependencies have changed since the previous run, the whole package is tested");
+            return Ok(incremental);
+        }
+
+        let changed = get_changed_files(
+            &to_digests(&previous.files),
+            &to_digests(&incremental.state.files),
+        );
+        if changed.iter().any(|file| !is_source(file)) {
+            info!("Manifest has changed since the previous run, the whole package is tested");
+            return Ok(incremental);
+        }
+
+        let mut report = Report::load_from_json_file(&report_file)?;
+        report.reroot(package_path);
+        let graph = module_graph(config, package_path)?;
+        let affected = affected_by_changes(&graph, package_path, &changed, &report);
+        info!("Changed files since the previous run: {changed:?}, affected modules: {affected:?}");
+
+        let modules = graph
+            .modules
+            .iter()
+            .filter(|(_, node)| node.is_root)
+            .map(|(id, _)| module_name(id))
+            .collect::<BTreeSet<_>>();
+        report.get_mutants_mut().retain(|m| {
+            modules.contains(m.get_module_name()) && !affected.contains(m.get_module_name())
+        });
+        incremental.affected = Some(affected);
+        incremental.carried = report;
+        Ok(incremental)
+    }
+
+    /// Checks if the module is affected by the changes, so its mutants must be generated and proved.
+    pub(crate) fn is_affected(&self, module: &str) -> bool {
+        self.affected
+            .as_ref()
+            .map_or(true, |affected| affected.contains(module))
+    }
+
+    /// Returns the modules affected by the changes, `None` if the whole package is affected.
+    pub(crate) fn affected_modules(&self) -> Option<&BTreeSet<String>> {
+        self.affected.as_ref()
+    }
+
+    /// Returns the status of the mutant from the previous run, if its result is carried forward.
+    pub(crate) fn carried_status(&self, mutant: &MutationReport) -> Option<MutantStatus> {
+        self.carried
+            .get_mutant_by_id(mutant.mutant_id())
+            .map(MutationReport::status)
+    }
+
+    /// Adds the mutants carried forward from the previous run to the report.
+    pub(crate) fn carry_forward(&self, report: &mut Report) {
+        report.merge(self.carried.clone());
+    }
+
+    /// Records the state of the package and the report with the results, so the next run can be
+    /// incremental.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state cannot be written.
+    pub(crate) fn save(&self, report: &Report) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        report.save_to_json_file(&self.dir.join(REPORT_FILE))?;
+        fs::write(
+            self.dir.join(STATE_FILE),
+            serde_json::to_vec_pretty(&self.state)?,
+        )?;
+        Ok(())
+    }
+}
+
+/// Compiles the package and returns the dependency graph of its modules.
+///
+/// # Errors
+///
+/// Returns an error if the package cannot be compiled.
+fn module_graph(
+    config: &BuildConfig,
+    package_path: &Path,
+) -> anyhow::Result<ModuleDependencyGraph> {
+    // Compiled out of the package, so the modules always refer to the package sources (instead of
+    // the copies in a cached build).
+    let install_dir = tempfile::tempdir()?;
+    let package = BuildConfig {
+        install_dir: Some(install_dir.path().to_path_buf()),
+        ..config.clone()
+    }
+    .compile_package(package_path, &mut Vec::new())?;
+    Ok(package.module_dependency_graph())
+}
+
+/// Returns the name of the module (e.g. `Sum`) from its id (e.g. `0x42::Sum`).
+fn module_name(id: &str) -> &str {
+    id.rsplit("::").next().unwrap_or(id)
+}
+
+/// Returns the names of the modules affected by the changed files.
+fn affected_by_changes(
+    graph: &ModuleDependencyGraph,
+    package_path: &Path,
+    changed: &[PathBuf],
+    previous: &Report,
+) -> BTreeSet<String> {
+    let not_proved = previous
+        .get_mutants()
+        .iter()
+        .filter(|m| {
+            matches!(
+                m.status(),
+                MutantStatus::Generated | MutantStatus::Skipped | MutantStatus::Equivalent
+            )
+        })
+        .map(|m| m.get_module_name())
+        .collect::<BTreeSet<_>>();
+
+    // The dependencies of the package are not tested, so they don't propagate the changes.
+    let is_changed = |node: &ModuleNode| {
+        node.source_path
+            .strip_prefix(package_path)
+            .is_ok_and(|file| changed.iter().any(|changed| changed == file))
+    };
+    let mut queue = graph
+        .modules
+        .iter()
+        .filter(|(id, node)| {
+            node.is_root && (is_changed(node) || not_proved.contains(module_name(id)))
+        })
+        .map(|(id, _)| id.clone())
+        .collect::<VecDeque<_>>();
+    let mut affected = BTreeSet::new();
+    while let Some(id) = queue.pop_front() {
+        if !graph.modules.get(&id).is_some_and(|node| node.is_root) || !affected.insert(id.clone())
+        {
+            continue;
+        }
+        queue.extend(graph.dependents(&id));
+        queue.extend(graph.dependencies(&id));
+    }
+    affected
+        .iter()
+        .map(|id| module_name(id).to_owned())
+        .collect()
+}
+
+/// Computes the digests of the package sources and manifest (build artifacts are skipped).
+fn file_digests(package_path: &Path) -> anyhow::Result<BTreeMap<PathBuf, String>> {
+    Ok(compute_file_digests(&[package_path.to_path_buf()])?
+        .into_iter()
+        .filter_map(|(path, digest)| {
+            let file = path.strip_prefix(package_path).ok()?.to_path_buf();
+            (!file.starts_with("build")).then(|| (file, digest.to_string()))
+        })
+        .collect())
+}
+
+/// Computes the digest of the transitive dependencies of the package, so the changes of the
+/// dependencies (including the revisions of the git ones) can be detected.
+///
+/// # Errors
+///
+/// Returns an error if the dependencies of the package cannot be resolved.
+pub(crate) fn dependencies_digest(
+    config: &BuildConfig,
+    package_path: &Path,
+) -> anyhow::Result<String> {
+    let graph = config
+        .clone()
+        .resolution_graph_for_package(package_path, &mut Vec::new())?;
+    let root = graph.get_package(&graph.root_package.package.name);
+    Ok(root.dependencies_digest(&graph).to_string())
+}
+
+fn to_digests(files: &BTreeMap<PathBuf, String>) -> BTreeMap<PathBuf, PackageDigest> {
+    files
+        .iter()
+        .map(|(path, digest)| (path.clone(), PackageDigest::from(digest.as_str())))
+        .collect()
+}
+
+fn is_source(file: &Path) -> bool {
+    file.extension().is_some_and(|ext| ext == "move")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes_affect_dependent_modules() {
+        let node = |file: &str, dependencies: &[&str]| ModuleNode {
+            package: "Calc".into(),
+            is_root: true,
+            source_path: Path::new("/calc/sources").join(file),
+            dependencies: dependencies.iter().map(|id| (*id).to_owned()).collect(),
+        };
+        let graph = ModuleDependencyGraph {
+            modules: BTreeMap::from([
+                ("0x42::Math".to_owned(), node("Math.move", &["0x1::vector"])),
+                ("0x42::Sum".to_owned(), node("Sum.move", &["0x42::Math"])),
+                (
+                    "0x42::Vault".to_owned(),
+                    node("Vault.move", &["0x1::vector"]),
+                ),
+                ("0x42::Log".to_owned(), node("Log.move", &[])),
+                ("0x1::vector".to_owned(), ModuleNode {
+                    package: "MoveStdlib".into(),
+                    is_root: false,
+                    ..node("vector.move", &[])
+                }),
+            ]),
+        };
+        let package_path = Path::new("/calc");
+
+        let mut previous = Report::new();
+        for (module, status) in [
+            ("Math", MutantStatus::Killed),
+            ("Vault", MutantStatus::Survived),
+            ("Log", MutantStatus::Skipped),
+        ] {
+            let mut entry = MutationReport::new(
+                Path::new("mutant.move"),
+                Path::new("sources/Sum.move"),
+                module,
+                "f",
+                "x - y\n",
+                "x + y\n",
+            );
+            entry.set_status(status);
+            previous.add_entry(entry);
+        }
+
+        assert_eq!(
+            affected_by_changes(
+                &graph,
+                package_path,
+                &[PathBuf::from("sources/Math.move")],
+                &previous
+            ),
+            BTreeSet::from(["Math".to_owned(), "Sum".to_owned(), "Log".to_owned()])
+        );
+        assert_eq!(
+            affected_by_changes(
+                &graph,
+                package_path,
+                &[PathBuf::from("sources/Sum.move")],
+                &Report::new()
+            ),
+            BTreeSet::from(["Math".to_owned(), "Sum".to_owned()])
+        );
+        assert!(affected_by_changes(&graph, package_path, &[], &Report::new()).is_empty());
+    }
+
+    #[test]
+    fn package_modules_are_found_by_source_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sources")).unwrap();
+        fs::write(
+            dir.path().join("Move.toml"),
+            "[package]\nname = \"Calc\"\nversion = \"0.0.1\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sources/Math.move"),
+            "module 0x42::Math { public fun add(x: u64, y: u64): u64 { x + y } }\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sources/Sum.move"),
+            "module 0x42::Sum { fun sum(x: u64): u64 { 0x42::Math::add(x, 1) } }\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sources/Log.move"),
+            "module 0x42::Log { fun id(x: u64): u64 { x } }\n",
+        )
+        .unwrap();
+
+        let graph = module_graph(&BuildConfig::default(), dir.path()).unwrap();
+        assert!(!dir.path().join("build").exists());
+        assert_eq!(
+            affected_by_changes(
+                &graph,
+                dir.path(),
+                &[PathBuf::from("sources/Math.move")],
+                &Report::new()
+            ),
+            BTreeSet::from(["Math".to_owned(), "Sum".to_owned()])
+        );
+    }
+
+    #[test]
+    fn file_digests_skip_build_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sources")).unwrap();
+        fs::create_dir_all(dir.path().join("build/Sum/sources")).unwrap();
+        fs::write(dir.path().join("Move.toml"), "[package]\nname = \"Sum\"\n").unwrap();
+        fs::write(dir.path().join("sources/Sum.move"), "x + y\n").unwrap();
+        fs::write(dir.path().join("build/Sum/sources/Sum.move"), "x + y\n").unwrap();
+
+        let files = file_digests(dir.path()).unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), vec![
+            Path::new("Move.toml"),
+            Path::new("sources/Sum.move")
+        ]);
+        assert!(!is_source(Path::new("Move.toml")));
+        assert!(is_source(Path::new("sources/Sum.move")));
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_mutator::report::{MutantStatus, MutationReport, Report};
+use std::{fmt::Write, path::Path};
+
+/// Creates a JUnit XML report from the results of the specification testing.
+///
+/// Each mutant is represented as a single test case - killed mutants are passing tests while
+/// surviving mutants are failures (with the mutant diff attached). Mutants with any other status
+/// are reported as skipped. Test cases are grouped into test suites by the original file.
+///
+/// # Arguments
+///
+/// * `report` - the mutator report with the status of each mutant.
+///
+/// # Returns
+///
+/// * `String` - the JUnit XML document.
+pub fn to_junit(report: &Report) -> String {
+    let all = report.query();
+    let tests = all.len();
+    let failures = all.clone().status(MutantStatus::Survived).len();
+    let skipped = tests - failures - all.clone().status(MutantStatus::Killed).len();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"move-spec-test\" tests=\"{tests}\" failures=\"{failures}\" skipped=\"{skipped}\">"
+    );
+
+    for (file, cases) in all.group_by_file() {
+        let failures = cases.clone().status(MutantStatus::Survived).len();
+        let skipped = cases.len() - failures - cases.clone().status(MutantStatus::Killed).len();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\">",
+            escape_xml(&file.to_string_lossy()),
+            cases.len()
+        );
+
+        for entry in cases {
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"{}\"",
+                escape_xml(&test_case_name(entry)),
+                escape_xml(&format!(
+                    "{}::{}",
+                    entry.get_module_name(),
+                    entry.get_function_name()
+                ))
+            );
+
+            match entry.status() {
+                MutantStatus::Killed => xml.push_str("/>\n"),
+                MutantStatus::Survived => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <failure message=\"Mutant survived\" type=\"survived\">{}</failure>\n    </testcase>",
+                        escape_xml(entry.get_diff())
+                    );
+                },
+                status => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <skipped message=\"Mutant status: {status}\"/>\n    </testcase>"
+                    );
+                },
+            }
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Saves the JUnit XML report to the given file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_to_junit_file(path: &Path, report: &Report) -> anyhow::Result<()> {
+    info!("Saving JUnit report to {}", path.display());
+    Ok(std::fs::write(path, to_junit(report))?)
+}
+
+/// Creates a descriptive test case name for the mutant.
+fn test_case_name(entry: &MutationReport) -> String {
+    let mutations = entry
+        .get_mutations()
+        .iter()
+        .map(|m| {
+            format!(
+                "{}: `{}` -> `{}`",
+                m.get_operator_name(),
+                m.get_old_value(),
+                m.get_new_value()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("{} ({mutations})", entry.mutant_id())
+}
+
+/// Escapes the characters which are not allowed in XML attributes and text.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::{Mutation, Range};
+
+    fn mutant(file: &str, old: &str, new: &str, status: MutantStatus) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new(file),
+            "Sum",
+            "sum",
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(2, 3),
+            "binary_operator_replacement".to_string(),
+            old.to_string(),
+            new.to_string(),
+        ));
+        entry.set_status(status);
+        entry
+    }
+
+    #[test]
+    fn escape_xml_escapes_special_characters() {
+        assert_eq!(
+            escape_xml("a < b && c > \"d\""),
+            "a &lt; b &amp;&amp; c &gt; &quot;d&quot;"
+        );
+    }
+
+    #[test]
+    fn junit_contains_passing_and_failing_test_cases() {
+        let mut report = Report::new();
+        report.add_entry(mutant("sources/Sum.move", "+", "-", MutantStatus::Killed));
+        report.add_entry(mutant(
+            "sources/Sum.move",
+            "<",
+            ">=",
+            MutantStatus::Survived,
+        ));
+        report.add_entry(mutant("sources/Sum.move", "+", "*", MutantStatus::Timeout));
+
+        let xml = to_junit(&report);
+        assert!(xml.contains(
+            "<testsuites name=\"move-spec-test\" tests=\"3\" failures=\"1\" skipped=\"1\">"
+        ));
+        assert!(xml.contains(
+            "<testsuite name=\"sources/Sum.move\" tests=\"3\" failures=\"1\" skipped=\"1\">"
+        ));
+        assert!(xml.contains("<skipped message=\"Mutant status: timeout\"/>"));
+        assert!(
+            xml.contains("binary_operator_replacement: `+` -&gt; `-`)\" classname=\"Sum::sum\"/>")
+        );
+        assert!(xml.contains("binary_operator_replacement: `&lt;` -&gt; `&gt;=`)"));
+        assert_eq!(xml.matches("<failure ").count(), 1);
+    }
+}
@@ -2,10 +2,38 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod badge;
 mod benchmark;
+mod cache;
 pub mod cli;
+mod csv_report;
+mod diff;
+mod dry_run;
+#[cfg(feature = "history")]
+mod history;
+mod incremental;
+mod junit;
+mod manifest;
+mod pipeline;
+mod progress;
 mod prover;
+mod prover_log;
+mod quarantine;
+mod render;
 mod report;
+mod review;
+mod spec_coverage;
+mod spec_reach;
+mod summary;
+mod survivors;
+mod timings;
+mod webhook;
+
+pub use cache::CacheStats;
+pub use summary::{
+    exit_code, SpecTestFailure, EXIT_BASELINE_FAILED, EXIT_INTERNAL_ERROR, EXIT_MUTANT_SURVIVED,
+    EXIT_NEW_SURVIVORS, EXIT_SCORE_BELOW_THRESHOLD,
+};
 
 extern crate pretty_env_logger;
 #[macro_use]
@@ -13,15 +41,261 @@ extern crate log;
 
 use crate::{
     benchmark::{Benchmark, Benchmarks},
-    prover::prove,
+    cache::ProverCache,
+    incremental::Incremental,
+    pipeline::{PreparedMutant, Preparer},
+    progress::Progress,
+    prover::{prove_mutant, prove_mutant_function_first, ProveTask, ProverOutcome},
+    quarantine::Quarantine,
+    spec_coverage::SpecCondition,
+    spec_reach::SpecReach,
+    summary::RunSummary,
+    timings::Timings,
 };
 use anyhow::anyhow;
+use move_mutator::{
+    cli::ModuleFilter,
+    report::{MutantStatus, MutationReport},
+    sandbox::{self, SandboxLimits, SandboxTask},
+};
 use move_package::{source_package::layout::SourcePackageLayout, BuildConfig};
 use std::{
+    collections::BTreeMap,
     fs,
+    io::Write,
+    mem,
     path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
 };
 
+/// This function compares two specification test JSON reports (e.g. from two CI runs) and prints
+/// newly surviving mutants, newly killed mutants and the change of the mutation score.
+///
+/// # Arguments
+///
+/// * `old_report` - path to the report from the previous run.
+/// * `new_report` - path to the report from the current run.
+///
+/// # Errors
+///
+/// Returns an error if any report cannot be loaded or if the new report is a regression (there are
+/// newly surviving mutants or the mutation score has dropped), so it can be used as a CI gate.
+pub fn run_report_diff(old_report: &Path, new_report: &Path) -> anyhow::Result<()> {
+    let old = report::Report::load_from_json_file(old_report)?;
+    let new = report::Report::load_from_json_file(new_report)?;
+
+    let diff = diff::ReportDiff::new(&old, &new);
+    println!("{diff}");
+
+    if diff.is_regression() {
+        return Err(anyhow!(
+            "Specification test regression: {} newly surviving mutants, mutation score change {:+.2}",
+            diff.newly_surviving.len(),
+            diff.score_delta()
+        ));
+    }
+
+    Ok(())
+}
+
+/// This function checks the specification test JSON report against the baseline report (e.g.
+/// committed to the repository) and prints only the mutants which survive in the report, but not in
+/// the baseline, so it can gate the pull requests.
+///
+/// # Arguments
+///
+/// * `report` - path to the report from the current run.
+/// * `baseline` - path to the baseline report.
+///
+/// # Errors
+///
+/// Returns an error if any report cannot be loaded or if there are new surviving mutants (exiting
+/// with `EXIT_NEW_SURVIVORS`).
+pub fn run_check(report: &Path, baseline: &Path) -> anyhow::Result<()> {
+    let baseline_report = report::Report::load_from_json_file(baseline)?;
+    let new = report::Report::load_from_json_file(report)?;
+
+    let diff = diff::ReportDiff::new(&baseline_report, &new);
+    if diff.newly_surviving.is_empty() {
+        println!(
+            "No new surviving mutants compared to {}",
+            baseline.display()
+        );
+        return Ok(());
+    }
+
+    print!("{}", diff.render_regressions());
+    Err(SpecTestFailure::NewSurvivors(format!(
+        "{} new surviving mutants compared to {}",
+        diff.newly_surviving.len(),
+        baseline.display()
+    ))
+    .into())
+}
+
+/// This function renders an SVG badge with the mutation score from the specification test JSON
+/// report, which can be embedded in READMEs and dashboards.
+///
+/// # Arguments
+///
+/// * `report` - path to the JSON report.
+/// * `output` - path to the SVG file to create.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be loaded or the badge cannot be saved.
+pub fn run_badge(report: &Path, output: &Path) -> anyhow::Result<()> {
+    let report = report::Report::load_from_json_file(report)?;
+    fs::write(output, badge::render_badge(report.mutation_score()))?;
+    println!(
+        "Badge with mutation score {:.2}% saved to {}",
+        report.mutation_score(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// This function prints the evolution of the mutation scores recorded in the history store
+/// (see the `--history` option).
+///
+/// # Arguments
+///
+/// * `history` - path to the SQLite history database.
+/// * `limit` - maximum number of the most recent runs to show.
+///
+/// # Errors
+///
+/// Returns an error if the history cannot be read or the tool was built without the `history` feature.
+pub fn run_trends(history: &Path, limit: usize) -> anyhow::Result<()> {
+    #[cfg(feature = "history")]
+    {
+        let runs = history::HistoryStore::open(history)?.runs(limit)?;
+        if runs.is_empty() {
+            println!("No runs recorded in {}", history.display());
+        } else {
+            println!("{}", history::render_trends(&runs));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "history"))]
+    {
+        let _ = (history, limit);
+        Err(anyhow!(cli::HISTORY_UNAVAILABLE))
+    }
+}
+
+/// This function merges the specification test JSON reports (e.g. from the shards of a single run)
+/// into a single report and prints the merged results.
+///
+/// # Arguments
+///
+/// * `reports` - paths to the reports to merge.
+/// * `output` - path to the merged report.
+///
+/// # Errors
+///
+/// Returns an error if any report cannot be loaded or the merged report cannot be saved.
+pub fn run_merge(reports: &[PathBuf], output: &Path) -> anyhow::Result<()> {
+    let mut merged = report::Report::new();
+    for path in reports {
+        merged.merge(report::Report::load_from_json_file(path)?);
+    }
+    merged.save_to_json_file(&output.to_path_buf())?;
+
+    println!("Merged {} reports into {}", reports.len(), output.display());
+    println!("\nTotal mutants tested: {}", merged.mutants_tested());
+    println!("Total mutants killed: {}\n", merged.mutants_killed());
+    merged.print_table();
+
+    Ok(())
+}
+
+/// This function renders the results of a completed run in the given format without re-running
+/// anything. The results are loaded from the mutator report with the status of each mutant (saved
+/// with `--mutator-report-output` or `--unified-output`) or from the JSON report with the mutation
+/// scores (saved with `--output`), which can be rendered as text, JSON or Markdown only.
+///
+/// # Arguments
+///
+/// * `report` - path to the report.
+/// * `format` - the format of the rendered report.
+/// * `output` - path to the rendered report, `None` to print it to stdout.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be loaded, rendered in the format or saved.
+pub fn run_report(
+    report: &Path,
+    format: cli::ReportFormat,
+    output: Option<&Path>,
+) -> anyhow::Result<()> {
+    let rendered = render::RunResults::load(report)?.render(format)?;
+    match output {
+        Some(output) => {
+            fs::write(output, rendered)?;
+            println!("Report saved to {}", output.display());
+        },
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+/// This function steps through the surviving mutants of the mutator report (saved with
+/// `--mutator-report-output`) one by one, showing their diffs and asking for the triage decision:
+/// the mutant needs a stronger specification, it's equivalent to the original code (so it's added to
+/// the quarantine file, if given) or it's ignored. The decisions are saved back into the report.
+///
+/// # Arguments
+///
+/// * `report` - path to the mutator report with the status of each mutant.
+/// * `quarantine` - path to the quarantine file where the equivalent mutants are added, if any.
+/// * `all` - review also the mutants with a decision from the previous reviews.
+///
+/// # Errors
+///
+/// Returns an error if the report cannot be loaded or saved or the terminal cannot be used.
+pub fn run_review(report: &Path, quarantine: Option<&Path>, all: bool) -> anyhow::Result<()> {
+    let mut mutator_report = move_mutator::report::Report::load_from_json_file(report)?;
+    let outcome = review::review(
+        &mut mutator_report,
+        quarantine,
+        all,
+        std::io::stdin().lock(),
+        std::io::stdout(),
+        |updated| Ok(updated.save_to_json_file(report)?),
+    )?;
+    println!(
+        "\nReviewed {} mutants, {} surviving mutants left without a decision",
+        outcome.decided, outcome.undecided
+    );
+    Ok(())
+}
+
+/// Check run on each mutated package before proving it (e.g. running the unit tests of the package).
+///
+/// The check gets the path to the mutated package and returns the reason along with the diagnostics
+/// if it kills the mutant, so the mutant doesn't need to be proved. Otherwise it returns `None`.
+pub type MutantCheck<'a> = dyn Fn(&Path) -> anyhow::Result<Option<(String, String)>> + Sync + 'a;
+
+/// This function runs the task the current process has been started to run in the sandbox (see
+/// `move_mutator::sandbox::requested_task`): proving a mutant or any task of the mutator.
+///
+/// # Arguments
+///
+/// * `name` - the name of the task.
+///
+/// # Errors
+///
+/// Returns an error if the task is unknown or cannot be served.
+pub fn run_sandbox_task(name: &str) -> anyhow::Result<()> {
+    match name {
+        ProveTask::NAME => sandbox::serve::<ProveTask>(),
+        _ => move_mutator::run_sandbox_task(name),
+    }
+}
+
 /// This function runs the specification testing, which is a combination of the
 /// mutator tool and the prover tool
 /// It takes the CLI options and constructs appropriate options for the
@@ -46,6 +320,33 @@ pub fn run_spec_test(
     options: &cli::CLIOptions,
     config: &BuildConfig,
     package_path: &Path,
+) -> anyhow::Result<()> {
+    run_spec_test_with_check(options, config, package_path, None)
+}
+
+/// This function runs the specification testing like `run_spec_test`, but each mutant is first
+/// passed to the given check (e.g. running the unit tests, which is much faster than proving).
+/// Only the mutants which pass the check are sent to the prover.
+///
+/// # Arguments
+///
+/// * `options` - A `cli::Options` representing the options for the spec test.
+/// * `config` - A `BuildConfig` representing the build configuration.
+/// * `package_path` - A `PathBuf` representing the path to the package.
+/// * `check` - An optional check run on each mutated package before proving it.
+///
+/// # Errors
+///
+/// Errors are returned as `anyhow::Result`.
+///
+/// # Returns
+///
+/// * `anyhow::Result<()>` - The result of the spec test.
+pub fn run_spec_test_with_check(
+    options: &cli::CLIOptions,
+    config: &BuildConfig,
+    package_path: &Path,
+    check: Option<&MutantCheck<'_>>,
 ) -> anyhow::Result<()> {
     // We need to initialize logger using try_init() as it might be already initialized in some other tool
     // (e.g. spec-test). If we use init() instead, we will get an abort.
@@ -54,6 +355,10 @@ pub fn run_spec_test(
     // Check if package is correctly structured.
     let package_path = SourcePackageLayout::try_find_root(&package_path.canonicalize()?)?;
 
+    // Per-package defaults from the manifest are overridden by the command line options.
+    let options = &manifest::merge_manifest_options(options, &package_path)?;
+    cli::check_options(options)?;
+
     info!("Running specification tester with the following options: {options:?} and package path: {package_path:?}");
 
     // Always create and use benchmarks.
@@ -66,12 +371,47 @@ pub fn run_spec_test(
 
     let mut error_writer = termcolor::StandardStream::stderr(termcolor::ColorChoice::Auto);
 
-    let result = prove(config, &package_path, &prover_conf, &mut error_writer);
+    let prover_limits = SandboxLimits {
+        memory_limit_mb: None,
+        group_memory_limit_mb: options.prover_memory_limit,
+        timeout: options.prover_timeout.map(Duration::from_secs),
+    };
 
-    if let Err(e) = result {
-        let msg = format!("Original code verification failed! Prover failed with error: {e}");
+    // Mutants of a package which doesn't verify would all be reported as killed, so check the
+    // original code before spending time on generating and proving the mutants.
+    let baseline_start = Instant::now();
+    let baseline = prove_mutant(
+        config,
+        &package_path,
+        &prover_conf,
+        None,
+        &mut error_writer,
+        &prover_limits,
+    )?;
+    let baseline_time = baseline_start.elapsed();
+    let failure = match baseline {
+        ProverOutcome::Verified => None,
+        ProverOutcome::Failed { killed_by, .. } => {
+            Some(format!("the prover failed with: {killed_by}"))
+        },
+        ProverOutcome::Timeout => Some("the prover exceeded the timeout".to_owned()),
+        ProverOutcome::OutOfMemory => Some("the prover exceeded the memory limit".to_owned()),
+    };
+    if let Some(failure) = failure {
+        let msg = format!(
+            "Original code verification failed, {failure}. Fix the specifications (or the code) so the \
+             original package verifies before running the specification test"
+        );
         error!("{msg}");
-        return Err(anyhow!(msg));
+        let summary = RunSummary::baseline_failed(benchmarks.spec_test.start_time.elapsed());
+        if let Some(outfile) = &options.summary_output {
+            summary.save(outfile)?;
+        }
+        if options.output_format.is_json() {
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+        webhook::notify(options, &summary, None);
+        return Err(SpecTestFailure::BaselineFailed(msg).into());
     }
 
     // Setup temporary directory structure.
@@ -80,95 +420,835 @@ pub fn run_spec_test(
 
     fs::create_dir_all(&outdir_original)?;
 
-    let outdir_mutant = if let Some(mutant_path) = &options.use_generated_mutants {
-        mutant_path.clone()
+    let incremental = options
+        .incremental
+        .as_deref()
+        .map(|dir| Incremental::load(dir, options, config, &package_path, &prover_conf))
+        .transpose()?;
+    // Only the modules affected by the changes since the previous run are mutated.
+    let affected = incremental.as_ref().and_then(Incremental::affected_modules);
+    let mutator_options = cli::CLIOptions {
+        include_modules: affected.map_or_else(
+            || options.include_modules.clone(),
+            |affected| {
+                ModuleFilter::Selected(
+                    affected
+                        .iter()
+                        .filter(|m| options.include_modules.matches(m))
+                        .cloned()
+                        .collect(),
+                )
+            },
+        ),
+        ..options.clone()
+    };
+
+    let nothing_affected = matches!(
+        &mutator_options.include_modules,
+        ModuleFilter::Selected(modules) if modules.is_empty()
+    );
+    let mut report = if nothing_affected {
+        info!("No modules affected by the changes since the previous run");
+        move_mutator::report::Report::new()
     } else {
-        benchmarks.mutator.start();
-        let outdir_mutant = run_mutator(options, config, &package_path, &outdir)?;
-        benchmarks.mutator.stop();
-        outdir_mutant
+        let outdir_mutant = if let Some(mutant_path) = &options.use_generated_mutants {
+            mutant_path.clone()
+        } else {
+            benchmarks.mutator.start();
+            let outdir_mutant = run_mutator(&mutator_options, config, &package_path, &outdir)?;
+            benchmarks.mutator.stop();
+            outdir_mutant
+        };
+        move_mutator::report::Report::load_from_json_file(&outdir_mutant.join("report.json"))?
+    };
+    if let Some(incremental) = &incremental {
+        // Previously generated mutants may cover the untouched modules as well.
+        report
+            .get_mutants_mut()
+            .retain(|m| incremental.is_affected(m.get_module_name()));
+        incremental.carry_forward(&mut report);
+    }
+    // Previously generated mutants may cover more than the selected modules and functions.
+    report.get_mutants_mut().retain(|m| {
+        options.include_modules.matches(m.get_module_name())
+            && options
+                .functions
+                .matches(m.get_module_name(), m.get_function_name())
+    });
+
+    if let Some(shard) = &options.shard {
+        report
+            .get_mutants_mut()
+            .retain(|m| shard.contains(m.mutant_id()));
+        info!(
+            "Proving {} mutants of shard {}/{}",
+            report.get_mutants().len(),
+            shard.index,
+            shard.count
+        );
+    }
+
+    if let Some(previous) = &options.rerun_survivors {
+        let previous = move_mutator::report::Report::load_from_json_file(previous)?;
+        let missing = survivors::retain_survivors(&mut report, &previous);
+        if missing > 0 {
+            warn!("{missing} surviving mutants from the previous report were not found (the code has changed)");
+        }
+        info!(
+            "Re-running {} surviving mutants from the previous report",
+            report.get_mutants().len()
+        );
+    }
+
+    let quarantine = options
+        .quarantine
+        .as_deref()
+        .map(Quarantine::load)
+        .transpose()?;
+    if let Some(quarantine) = &quarantine {
+        for id in quarantine.missing(&report) {
+            warn!("Quarantined mutant {id} was not found (the code has changed)");
+        }
+    }
+
+    let mut timings = options.timings.as_deref().map(Timings::load).transpose()?;
+    // With the seed, mutants are proved in the order they were generated, as the scheduling by the
+    // measured proving times would differ between the runs.
+    match (&timings, options.seed) {
+        (Some(_), Some(_)) => {
+            info!("Mutants are proved in the generation order, as the seed is set")
+        },
+        (Some(timings), None) => timings.schedule(&mut report),
+        (None, _) => {},
+    }
+    let is_carried = |mutant: &MutationReport| {
+        incremental
+            .as_ref()
+            .and_then(|i| i.carried_status(mutant))
+            .is_some()
     };
+    // Carried forward results take no time, so they are never skipped due to the time budget.
+    report.get_mutants_mut().sort_by_key(|m| !is_carried(m));
+    let time_budget = options.time_budget.map(|budget| budget.0);
 
-    let report =
-        move_mutator::report::Report::load_from_json_file(&outdir_mutant.join("report.json"))?;
+    if options.dry_run {
+        let mut planned = report.clone();
+        planned.get_mutants_mut().retain(|m| !is_carried(m));
+        if incremental.is_some() {
+            println!(
+                "Mutants carried forward from the previous run: {}",
+                report.get_mutants().len() - planned.get_mutants().len()
+            );
+        }
+        print!(
+            "{}",
+            dry_run::render_plan(
+                &planned,
+                |m| quarantine
+                    .as_ref()
+                    .is_some_and(|q| q.contains(m.mutant_id())),
+                baseline_time,
+                timings.as_ref(),
+                time_budget,
+            )
+        );
+        return Ok(());
+    }
 
     // Proving part.
     move_mutator::compiler::copy_dir_all(&package_path, &outdir_original)?;
 
-    let mut spec_report = report::Report::new();
+    if let Some(dir) = &options.prover_logs {
+        fs::create_dir_all(dir)?;
+    }
+    let cache = options
+        .prover_cache
+        .as_deref()
+        .map(|dir| {
+            ProverCache::open(
+                dir,
+                &incremental::dependencies_digest(config, &package_path)?,
+            )
+        })
+        .transpose()?;
+
+    // Mutants surviving outside any spec's reach are reported as `no-spec` instead of survived.
+    let spec_reach = SpecReach::collect(config, &package_path)?;
+
+    let preparer = Preparer {
+        package_path: &package_path,
+        outdir: &outdir,
+        prover_conf: &prover_conf,
+        cache: cache.as_ref(),
+        check,
+    };
+    let run = SpecTestRun {
+        options,
+        config,
+        package_path: &package_path,
+        prover_conf: &prover_conf,
+        prover_limits: &prover_limits,
+        cache: cache.as_ref(),
+        incremental: incremental.as_ref(),
+        quarantine: quarantine.as_ref(),
+        spec_reach: &spec_reach,
+        checked: check.is_some(),
+    };
 
     let mut proving_benchmarks = vec![Benchmark::new(); report.get_mutants().len()];
     benchmarks.prover.start();
-    for (index, (elem, benchmark)) in report
-        .get_mutants()
-        .iter()
-        .zip(proving_benchmarks.iter_mut())
-        .enumerate()
-    {
-        info!(
-            "Proving mutant {index} out of {}",
-            report.get_mutants().len()
-        );
+    let mut results = run.prove_mutants(
+        &report,
+        &preparer,
+        &mut proving_benchmarks,
+        &mut error_writer,
+    )?;
+
+    // Mutants not proved within the time budget are skipped.
+    let skipped = results.apply(&mut report);
+
+    if let (Some(path), Some(timings)) = (&options.timings, timings.as_mut()) {
+        timings.update(mem::take(&mut results.latest_timings));
+        timings.save(path)?;
+    }
+
+    if let Some(incremental) = &incremental {
+        incremental.save(&report)?;
+    }
+
+    // Conditions are matched with the prover diagnostics, so they must be counted before anonymizing.
+    let spec_coverage =
+        if options.spec_coverage || options.spec_coverage_output.is_some() || options.spec_scores {
+            let mut conditions = spec_coverage::collect_spec_conditions(config, &package_path)?;
+            spec_coverage::record_kills(&mut conditions, &report);
+            if options.anonymize {
+                conditions.iter_mut().for_each(|c| c.condition.clear());
+            }
+            Some(conditions)
+        } else {
+            None
+        };
+
+    if options.anonymize {
+        // The source code must not leak into any of the exported reports.
+        let detail = move_mutator::report::ReportDetail {
+            anonymize: true,
+            ..Default::default()
+        };
+        report.reduce_detail(&detail);
+        results.survivors.reduce_detail(&detail);
+        results.spec_report.anonymize();
+    }
+
+    benchmarks.prover.stop();
+    benchmarks.prover_results = proving_benchmarks;
+
+    run.save_reports(&report, &results, spec_coverage.as_deref())?;
+
+    // The tables are replaced with the JSON summary printed at the end of the run.
+    if !options.output_format.is_json() {
+        run.print_results(&report, &results, spec_coverage.as_deref(), skipped);
+    }
+
+    benchmarks.spec_test.stop();
+    benchmarks.display();
+
+    run.finish(&report, results, benchmarks.spec_test.elapsed)
+}
+
+/// State shared by the proving and the reporting of a single specification test run.
+struct SpecTestRun<'a> {
+    /// Options of the run (merged with the manifest defaults).
+    options: &'a cli::CLIOptions,
+    /// Build configuration of the package.
+    config: &'a BuildConfig,
+    /// Path to the original package.
+    package_path: &'a Path,
+    /// Options of the prover.
+    prover_conf: &'a move_prover::cli::Options,
+    /// Limits of the sandboxed prover.
+    prover_limits: &'a SandboxLimits,
+    /// Cache of the prover outcomes.
+    cache: Option<&'a ProverCache>,
+    /// Results of the previous run, if the run is incremental.
+    incremental: Option<&'a Incremental>,
+    /// Mutants acknowledged as equivalent.
+    quarantine: Option<&'a Quarantine>,
+    /// Functions reached by the specifications.
+    spec_reach: &'a SpecReach,
+    /// Whether the mutated packages are checked before proving them.
+    checked: bool,
+}
+
+impl SpecTestRun<'_> {
+    fn is_quarantined(&self, mutant: &MutationReport) -> bool {
+        self.quarantine
+            .is_some_and(|q| q.contains(mutant.mutant_id()))
+    }
+
+    fn carried_status(&self, mutant: &MutationReport) -> Option<MutantStatus> {
+        self.incremental.and_then(|i| i.carried_status(mutant))
+    }
+
+    /// Proves the mutants of the report in order, until all are processed or the run is stopped
+    /// early (due to `--fail-fast` or the time budget).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a mutant cannot be prepared or proved, or the results cannot be written.
+    fn prove_mutants(
+        &self,
+        report: &move_mutator::report::Report,
+        preparer: &Preparer,
+        benchmarks: &mut [Benchmark],
+        error_writer: &mut impl Write,
+    ) -> anyhow::Result<RunResults> {
+        let mutants = report.get_mutants();
+        let mut results = RunResults::new(mutants.len());
+        let report_detail = cli::report_detail(self.options);
+        let mut stream = self
+            .options
+            .stream_output
+            .as_deref()
+            .map(move_mutator::report::ReportStreamWriter::create)
+            .transpose()?;
+        let time_budget = self.options.time_budget.map(|budget| budget.0);
+        let start = Instant::now();
+        let mut progress = Progress::new(mutants.len());
+
+        thread::scope(|scope| -> anyhow::Result<()> {
+            // Mutated packages are prepared on a separate thread, ahead of the prover.
+            let prepared = pipeline::prepare_ahead(
+                scope,
+                preparer,
+                mutants
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, m)| !self.is_quarantined(m) && self.carried_status(m).is_none())
+                    .collect(),
+            );
+
+            for (index, (elem, benchmark)) in mutants.iter().zip(benchmarks.iter_mut()).enumerate()
+            {
+                if self.options.fail_fast && results.proved_survivor.is_some() {
+                    warn!("Mutant survived, the remaining mutants are skipped (--fail-fast)");
+                    results.stop_reason = Some("stopped on the first surviving mutant");
+                    break;
+                }
+
+                // Results of the mutants of the modules untouched since the previous run are carried forward.
+                if let Some(status) = self.carried_status(elem) {
+                    info!(
+                        "Mutant {} is carried forward from the previous run",
+                        elem.mutant_id()
+                    );
+                    results.record_carried(elem, status, self.package_path);
+                    progress.record(status);
+                    continue;
+                }
+
+                if time_budget.is_some_and(|budget| start.elapsed() >= budget) {
+                    warn!("Time budget exceeded, the remaining mutants are skipped");
+                    results.stop_reason = Some("time budget exceeded");
+                    break;
+                }
+
+                // Quarantined mutants are acknowledged as equivalent, so they are neither proved nor scored.
+                if self.is_quarantined(elem) {
+                    info!("Mutant {} is quarantined as equivalent", elem.mutant_id());
+                    results.statuses.push(MutantStatus::Equivalent);
+                    progress.record(MutantStatus::Equivalent);
+                    continue;
+                }
+
+                info!("Proving mutant {index} out of {}", mutants.len());
+
+                let mutant = prepared.recv().map_err(|_| {
+                    anyhow!("Mutant {} was not prepared for proving", elem.mutant_id())
+                })??;
+                let outcome =
+                    self.prove_prepared(elem, mutant, benchmark, &mut results, error_writer)?;
+                let status =
+                    results.record_outcome(elem, outcome, self.package_path, self.spec_reach);
+                progress.record(status);
+
+                if let Some(stream) = stream.as_mut() {
+                    results.stream_entry(stream, elem, status, &report_detail)?;
+                }
+            }
+            // Mutants prepared ahead are left over if the run was stopped early.
+            prepared.discard();
+
+            Ok(())
+        })?;
+        progress.finish();
+
+        Ok(results)
+    }
+
+    /// Proves the prepared mutant, unless its outcome is cached or the check has killed it. The
+    /// prover output is forwarded to the error writer (and kept in the prover log, if requested) and
+    /// the mutated package is removed afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the prover cannot be run or its output cannot be written.
+    fn prove_prepared(
+        &self,
+        elem: &MutationReport,
+        mutant: PreparedMutant,
+        benchmark: &mut Benchmark,
+        results: &mut RunResults,
+        error_writer: &mut impl Write,
+    ) -> anyhow::Result<ProverOutcome> {
+        let outdir_prove = &mutant.package;
+        let qname = format!("{}::{}", elem.get_module_name(), elem.get_function_name());
+
+        // The prover output is captured, so it can be kept in the log of the mutant.
+        let mut prover_output = termcolor::Buffer::no_color();
+        let mut elapsed = None;
+        let outcome = if let Some(outcome) = mutant.cached {
+            info!(
+                "Using cached prover outcome for mutant {}",
+                elem.mutant_id()
+            );
+            outcome
+        } else if let Some((killed_by, diagnostics)) = mutant.checked {
+            info!("Mutant {} killed before proving", elem.mutant_id());
+            results.killed_by_check += 1;
+            ProverOutcome::Failed {
+                killed_by,
+                diagnostics,
+            }
+        } else {
+            benchmark.start();
+            let outcome = if self.options.stop_on_first_failure {
+                prove_mutant_function_first(
+                    self.config,
+                    outdir_prove,
+                    &qname,
+                    self.prover_conf,
+                    &mut prover_output,
+                    self.prover_limits,
+                )?
+            } else {
+                prove_mutant(
+                    self.config,
+                    outdir_prove,
+                    self.prover_conf,
+                    None,
+                    &mut prover_output,
+                    self.prover_limits,
+                )?
+            };
+            benchmark.stop();
+            elapsed = Some(benchmark.elapsed);
+            results.latest_timings.record(qname, benchmark.elapsed);
+
+            if let (Some(cache), Some(key)) = (self.cache, &mutant.cache_key) {
+                cache.put(key, &outcome)?;
+            }
+            outcome
+        };
+        error_writer.write_all(prover_output.as_slice())?;
+
+        if let Some(dir) = &self.options.prover_logs {
+            let log = prover_log::save_prover_log(
+                dir,
+                elem,
+                &outcome,
+                &String::from_utf8_lossy(prover_output.as_slice()),
+                elapsed,
+                outdir_prove,
+            )?;
+            results.prover_logs.insert(elem.mutant_id().to_owned(), log);
+        }
+        let _ = fs::remove_dir_all(outdir_prove);
+
+        Ok(outcome)
+    }
+
+    /// Saves the reports requested by the options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any report cannot be saved.
+    fn save_reports(
+        &self,
+        report: &move_mutator::report::Report,
+        results: &RunResults,
+        spec_coverage: Option<&[SpecCondition]>,
+    ) -> anyhow::Result<()> {
+        let options = self.options;
+        let report_detail = cli::report_detail(options);
+
+        if let Some(outfile) = &options.output {
+            results.spec_report.save_to_json_file(outfile)?;
+        }
+
+        if let Some(outfile) = &options.sarif_output {
+            results.survivors.save_to_sarif_file(outfile)?;
+        }
 
-        let mutant_file = elem.mutant_path();
-        // Strip prefix to get the path relative to the package directory (or take that path if it's already relative).
-        let original_file = elem
-            .original_file_path()
-            .strip_prefix(&package_path)
-            .unwrap_or(elem.original_file_path());
-        let outdir_prove = outdir.join("prove");
+        if let Some(outfile) = &options.mutator_report_output {
+            let mut report = report.clone();
+            report.reduce_detail(&report_detail);
+            report.save_to_json_file(outfile)?;
+        }
+
+        if let Some(outfile) = &options.unified_output {
+            let mut report = report.clone();
+            report.reduce_detail(&report_detail);
+            report::save_to_unified_file(outfile, &results.spec_report, &report)?;
+        }
+
+        if let Some(outfile) = &options.junit_output {
+            junit::save_to_junit_file(outfile, report)?;
+        }
+
+        if let Some(outfile) = &options.csv_output {
+            csv_report::save_to_csv_file(outfile, report)?;
+        }
+
+        if let Some(outfile) = &options.stryker_output {
+            report.save_to_stryker_file(outfile)?;
+        }
+
+        if let Some(outfile) = &options.html_output {
+            report.save_to_html_file(outfile)?;
+        }
+
+        if let Some(outfile) = &options.markdown_output {
+            results.spec_report.save_to_markdown_file(outfile)?;
+        }
+
+        if let Some(outfile) = &options.survivors_output {
+            survivors::save_survivor_diffs(outfile, report)?;
+        }
+
+        if let (Some(outfile), Some(conditions)) = (&options.spec_coverage_output, spec_coverage) {
+            spec_coverage::save_spec_coverage(outfile, conditions)?;
+        }
 
-        let mut qname = elem.get_module_name().to_owned();
-        qname.push_str("::");
-        qname.push_str(elem.get_function_name());
+        if let Some(history) = &options.history {
+            record_history(history, &results.spec_report)?;
+        }
 
-        spec_report.increment_mutants_tested(original_file, qname.as_str());
+        Ok(())
+    }
 
-        let _ = fs::remove_dir_all(&outdir_prove);
-        move_mutator::compiler::copy_dir_all(&package_path, &outdir_prove)?;
+    /// Prints the results of the run (the survivors, the coverage and the totals) along with the
+    /// table of the mutation scores.
+    fn print_results(
+        &self,
+        report: &move_mutator::report::Report,
+        results: &RunResults,
+        spec_coverage: Option<&[SpecCondition]>,
+        skipped: usize,
+    ) {
+        let options = self.options;
+        let spec_report = &results.spec_report;
 
-        trace!(
-            "Copying mutant file {:?} to the package directory {:?}",
-            mutant_file,
-            outdir_prove.join(original_file)
+        if options.github_annotations {
+            for annotation in results.survivors.to_github_annotations() {
+                println!("{annotation}");
+            }
+        }
+
+        if options.show_survivors {
+            print!("\n{}", survivors::render_survivor_diffs(report));
+        }
+
+        if let Some(conditions) = spec_coverage.filter(|_| options.spec_coverage) {
+            print!("\n{}", spec_coverage::render_spec_coverage(conditions));
+        }
+
+        if let Some(conditions) = spec_coverage.filter(|_| options.spec_scores) {
+            let scores = spec_coverage::spec_scores(conditions, report);
+            println!("\n{}", spec_coverage::render_spec_scores(&scores));
+        }
+
+        if let Some(quarantine) = self.quarantine {
+            print!("\n{}", quarantine.render(report));
+        }
+
+        let no_spec = report.query().status(MutantStatus::NoSpec).len();
+        if no_spec > 0 {
+            print!("\n{}", self.spec_reach.render(report));
+        }
+
+        println!("\nTotal mutants tested: {}", spec_report.mutants_tested());
+        println!("Total mutants killed: {}", spec_report.mutants_killed());
+        if self.checked {
+            println!(
+                "Total mutants killed before proving: {}",
+                results.killed_by_check
+            );
+        }
+        if let Some(cache) = self.cache {
+            println!("{}", cache.stats());
+        }
+        if self.quarantine.is_some() {
+            println!(
+                "Total mutants quarantined as equivalent: {}",
+                report.query().status(MutantStatus::Equivalent).len()
+            );
+        }
+        if no_spec > 0 {
+            println!("Total mutants outside any spec's reach (no-spec): {no_spec}");
+        }
+        if skipped > 0 {
+            println!(
+                "Total mutants skipped ({}): {skipped}",
+                results.stop_reason.unwrap_or_default()
+            );
+        }
+        if options.prover_memory_limit.is_some() {
+            println!(
+                "Total mutants out of memory: {}",
+                report.query().status(MutantStatus::OutOfMemory).len()
+            );
+        }
+        println!(
+            "Total mutants timed out: {}\n",
+            report.query().status(MutantStatus::Timeout).len()
         );
+        spec_report.print_table();
+    }
+
+    /// Saves and prints the summary of the run and sends it to the webhook.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summary cannot be saved, a mutant survived with `--fail-fast` or the
+    /// mutation score threshold is not met.
+    fn finish(
+        &self,
+        report: &move_mutator::report::Report,
+        results: RunResults,
+        elapsed: Duration,
+    ) -> anyhow::Result<()> {
+        let options = self.options;
+        let spec_report = &results.spec_report;
 
-        if let Err(res) = fs::copy(mutant_file, outdir_prove.join(original_file)) {
-            return Err(anyhow!(
-                "Can't copy mutant file to the package directory: {res:?}"
-            ));
+        let violations = spec_report.threshold_violations(&options.min_score);
+        // With `--fail-fast`, the scores cover only the mutants proved before the first survivor.
+        let survivor = results.proved_survivor.filter(|_| options.fail_fast);
+        let mut summary = RunSummary::new(spec_report, report, violations.clone(), elapsed);
+        if survivor.is_some() {
+            summary = summary.mutant_survived();
+        }
+        if let Some(cache) = self.cache {
+            summary = summary.with_cache_stats(cache.stats());
+        }
+        if let Some(outfile) = &options.summary_output {
+            summary.save(outfile)?;
+        }
+        if options.output_format.is_json() {
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        }
+        webhook::notify(options, &summary, Some(spec_report));
+        if let Some(survivor) = survivor {
+            return Err(SpecTestFailure::MutantSurvived(format!(
+                "Mutant {survivor} survived, the run was stopped (--fail-fast)"
+            ))
+            .into());
+        }
+        if !violations.is_empty() {
+            return Err(SpecTestFailure::ScoreBelowThreshold(format!(
+                "Mutation score threshold not met:\n{}",
+                violations.join("\n")
+            ))
+            .into());
         }
 
-        move_mutator::compiler::rewrite_manifest_for_mutant(&package_path, &outdir_prove)?;
+        Ok(())
+    }
+}
 
-        benchmark.start();
-        let result = prove(config, &outdir_prove, &prover_conf, &mut error_writer);
-        benchmark.stop();
+/// Results collected while proving the mutants.
+struct RunResults {
+    /// Statuses of the processed mutants, in the order of the report.
+    statuses: Vec<MutantStatus>,
+    /// Mutation scores of the functions.
+    spec_report: report::Report,
+    /// Surviving mutants.
+    survivors: move_mutator::report::Report,
+    /// Reason and diagnostics of the killed mutants, by the mutant ID.
+    killers: BTreeMap<String, (String, String)>,
+    /// Paths to the prover logs, by the mutant ID.
+    prover_logs: BTreeMap<String, PathBuf>,
+    /// Number of the mutants killed by the check before proving.
+    killed_by_check: usize,
+    /// Reason why the remaining mutants were skipped, if the run was stopped early.
+    stop_reason: Option<&'static str>,
+    /// First mutant proved to survive in this run (carried forward survivors don't count).
+    proved_survivor: Option<String>,
+    /// Proving times of the functions measured in this run.
+    latest_timings: Timings,
+}
 
-        if let Err(e) = result {
-            trace!("Mutant killed! Prover failed with error: {e}");
-            spec_report.increment_mutants_killed(original_file, qname.as_str());
-        } else {
-            trace!("Mutant hasn't been killed!");
-            spec_report.add_mutants_alive_diff(original_file, qname.as_str(), elem.get_diff());
+impl RunResults {
+    fn new(capacity: usize) -> Self {
+        Self {
+            statuses: Vec::with_capacity(capacity),
+            spec_report: report::Report::new(),
+            survivors: move_mutator::report::Report::new(),
+            killers: BTreeMap::new(),
+            prover_logs: BTreeMap::new(),
+            killed_by_check: 0,
+            stop_reason: None,
+            proved_survivor: None,
+            latest_timings: Timings::default(),
         }
     }
 
-    benchmarks.prover.stop();
-    benchmarks.prover_results = proving_benchmarks;
+    /// Records the status of the mutant carried forward from the previous run.
+    fn record_carried(&mut self, elem: &MutationReport, status: MutantStatus, package_path: &Path) {
+        let (original_file, qname) = mutated_function(elem, package_path);
+        match status {
+            MutantStatus::Killed => {
+                self.spec_report
+                    .increment_mutants_tested(original_file, qname.as_str());
+                self.spec_report
+                    .increment_mutants_killed(original_file, qname.as_str());
+            },
+            MutantStatus::Survived => {
+                self.spec_report
+                    .increment_mutants_tested(original_file, qname.as_str());
+                self.spec_report.add_mutants_alive_diff(
+                    original_file,
+                    qname.as_str(),
+                    elem.get_diff(),
+                );
+                self.survivors.add_entry(elem.clone());
+            },
+            _ => {},
+        }
+        self.statuses.push(status);
+    }
 
-    if let Some(outfile) = &options.output {
-        spec_report.save_to_json_file(outfile)?;
+    /// Records the prover outcome of the mutant and returns its status.
+    fn record_outcome(
+        &mut self,
+        elem: &MutationReport,
+        outcome: ProverOutcome,
+        package_path: &Path,
+        spec_reach: &SpecReach,
+    ) -> MutantStatus {
+        let (original_file, qname) = mutated_function(elem, package_path);
+        let status = match outcome {
+            ProverOutcome::Failed {
+                killed_by,
+                diagnostics,
+            } => {
+                trace!("Mutant killed! Prover failed with: {killed_by}");
+                self.spec_report
+                    .increment_mutants_tested(original_file, qname.as_str());
+                self.spec_report
+                    .increment_mutants_killed(original_file, qname.as_str());
+                self.killers
+                    .insert(elem.mutant_id().to_owned(), (killed_by, diagnostics));
+                MutantStatus::Killed
+            },
+            ProverOutcome::Verified if !spec_reach.is_reached(&qname) => {
+                trace!("Mutant hasn't been killed, as {qname} is outside any spec's reach");
+                MutantStatus::NoSpec
+            },
+            ProverOutcome::Verified => {
+                trace!("Mutant hasn't been killed!");
+                self.spec_report
+                    .increment_mutants_tested(original_file, qname.as_str());
+                self.spec_report.add_mutants_alive_diff(
+                    original_file,
+                    qname.as_str(),
+                    elem.get_diff(),
+                );
+                self.survivors.add_entry(elem.clone());
+                self.proved_survivor
+                    .get_or_insert_with(|| elem.mutant_id().to_owned());
+                MutantStatus::Survived
+            },
+            ProverOutcome::Timeout => {
+                // Timed out mutants are neither killed nor survived, so they don't affect the score.
+                warn!("Proving mutant {} exceeded the timeout", elem.mutant_id());
+                MutantStatus::Timeout
+            },
+            ProverOutcome::OutOfMemory => {
+                // Like the timed out ones, such mutants don't affect the score.
+                warn!(
+                    "Proving mutant {} exceeded the memory limit",
+                    elem.mutant_id()
+                );
+                MutantStatus::OutOfMemory
+            },
+        };
+        self.statuses.push(status);
+        status
     }
 
-    println!("\nTotal mutants tested: {}", spec_report.mutants_tested());
-    println!("Total mutants killed: {}\n", spec_report.mutants_killed());
-    spec_report.print_table();
+    /// Writes the mutant with its status to the stream, if the entry matches the report detail.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written.
+    fn stream_entry(
+        &self,
+        stream: &mut move_mutator::report::ReportStreamWriter,
+        elem: &MutationReport,
+        status: MutantStatus,
+        report_detail: &move_mutator::report::ReportDetail,
+    ) -> anyhow::Result<()> {
+        let mut entry = elem.clone();
+        entry.set_status(status);
+        if let Some((killed_by, diagnostics)) = self.killers.get(elem.mutant_id()) {
+            entry.set_killed_by(killed_by.as_str());
+            entry.set_diagnostics(diagnostics.as_str());
+        }
+        if let Some(log) = self.prover_logs.get(elem.mutant_id()) {
+            entry.set_prover_log(log);
+        }
+        if report_detail.includes(&entry) {
+            entry.reduce_detail(report_detail);
+            stream.write_entry(&entry)?;
+        }
+        Ok(())
+    }
 
-    benchmarks.spec_test.stop();
-    benchmarks.display();
+    /// Sets the statuses of the mutants in the report, along with the killing reasons and the prover
+    /// logs. Mutants not processed (as the run was stopped early) are marked as skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of the skipped mutants.
+    fn apply(&mut self, report: &mut move_mutator::report::Report) -> usize {
+        let mutants = report.get_mutants_mut();
+        let skipped = mutants.len() - self.statuses.len();
+        self.statuses.resize(mutants.len(), MutantStatus::Skipped);
 
-    Ok(())
+        for (elem, status) in mutants.iter_mut().zip(&self.statuses) {
+            elem.set_status(*status);
+            if let Some((killed_by, diagnostics)) = self.killers.remove(elem.mutant_id()) {
+                elem.set_killed_by(killed_by);
+                elem.set_diagnostics(diagnostics);
+            }
+            if let Some(log) = self.prover_logs.remove(elem.mutant_id()) {
+                elem.set_prover_log(log);
+            }
+        }
+        skipped
+    }
+}
+
+/// Returns the path of the mutated file relative to the package directory (or the path itself if
+/// it's already relative) along with the qualified name of the mutated function.
+fn mutated_function<'a>(elem: &'a MutationReport, package_path: &Path) -> (&'a Path, String) {
+    let original_file = elem
+        .original_file_path()
+        .strip_prefix(package_path)
+        .unwrap_or(elem.original_file_path());
+    let qname = format!("{}::{}", elem.get_module_name(), elem.get_function_name());
+    (original_file, qname)
 }
 
 /// This function runs the Move Mutator tool.
@@ -193,3 +1273,16 @@ fn run_mutator(
 
     Ok(outdir_mutant)
 }
+
+/// Records the results of the run in the history store.
+#[cfg(feature = "history")]
+fn record_history(history: &Path, spec_report: &report::Report) -> anyhow::Result<()> {
+    history::HistoryStore::open(history)?.record(spec_report)?;
+    Ok(())
+}
+
+/// Records the results of the run in the history store.
+#[cfg(not(feature = "history"))]
+fn record_history(_history: &Path, _spec_report: &report::Report) -> anyhow::Result<()> {
+    Err(anyhow!(cli::HISTORY_UNAVAILABLE))
+}
@@ -6,8 +6,7 @@
 
 use clap::Parser;
 use move_package::BuildConfig;
-use move_spec_test::cli::CLIOptions;
-use move_spec_test::run_spec_test;
+use move_spec_test::{cli::CLIOptions, run_spec_test};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -25,9 +24,21 @@ pub struct Opts {
     pub build_config: BuildConfig,
 }
 
-fn main() -> anyhow::Result<()> {
+fn main() {
+    // The binary is started again with the hidden `sandbox-task` subcommand to run the sandboxed tasks.
+    if let Some(task) = move_mutator::sandbox::requested_task() {
+        if let Err(e) = move_spec_test::run_sandbox_task(&task) {
+            eprintln!("Error: {e:?}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let opts = Opts::parse();
     let package_path = opts.package_path.unwrap_or(PathBuf::from("."));
 
-    run_spec_test(&opts.cli_options, &opts.build_config, &package_path)
+    if let Err(e) = run_spec_test(&opts.cli_options, &opts.build_config, &package_path) {
+        eprintln!("Error: {e:?}");
+        std::process::exit(move_spec_test::exit_code(&e));
+    }
 }
@@ -0,0 +1,212 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli::{CLIOptions, FunctionTimeout, ScoreThreshold};
+use anyhow::{anyhow, Context};
+use move_mutator::cli::{FunctionFilter, ModuleFilter};
+use move_package::source_package::{
+    layout::SourcePackageLayout, manifest_parser::parse_move_manifest_string,
+};
+use serde::Deserialize;
+use std::{fs, path::Path, str::FromStr};
+
+/// Name of the manifest section with the specification test defaults.
+const SPEC_TEST_SECTION: &str = "spec-test";
+
+/// Per-package defaults of the specification test, read from the `[spec-test]` section of the
+/// package manifest (`Move.toml`), e.g.:
+///
+/// ```toml
+/// [spec-test]
+/// prover-timeout = 300
+/// function-timeout = [40, "Vault::withdraw=120"]
+/// prover-cores = 8
+/// min-score = [80, "Sum=90"]
+/// modules = ["Sum"]
+/// functions = ["Sum::sum"]
+/// ```
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct ManifestConfig {
+    /// Timeout (in seconds) of proving a single mutant.
+    prover_timeout: Option<u64>,
+    /// Solver timeouts (in seconds) of verifying the functions, given as the timeout of all
+    /// functions or `<MODULE>::<FUNCTION>=<SECS>`.
+    function_timeout: Vec<Timeout>,
+    /// Number of cores used by the prover backend.
+    prover_cores: Option<usize>,
+    /// Minimal mutation scores, given as the total score or `<MODULE>=<PCT>`.
+    min_score: Vec<Threshold>,
+    /// Modules to work on.
+    modules: Vec<String>,
+    /// Functions to work on, given as `<FUNCTION>` or `<MODULE>::<FUNCTION>`.
+    functions: Vec<String>,
+}
+
+/// Score threshold given either as a number (the total score) or a string.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Threshold {
+    Total(f64),
+    Text(String),
+}
+
+/// Function timeout given either as a number (the timeout of all functions) or a string.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Timeout {
+    All(usize),
+    Text(String),
+}
+
+/// Merges the `[spec-test]` section of the package manifest with the command line options. Options
+/// given on the command line take precedence over the ones from the manifest.
+///
+/// # Arguments
+///
+/// * `options` - the command line options.
+/// * `package_path` - the path to the package root.
+///
+/// # Errors
+///
+/// Returns an error if the manifest cannot be read or the section is invalid.
+///
+/// # Returns
+///
+/// * `anyhow::Result<CLIOptions>` - the merged options.
+pub fn merge_manifest_options(
+    options: &CLIOptions,
+    package_path: &Path,
+) -> anyhow::Result<CLIOptions> {
+    let manifest = fs::read_to_string(package_path.join(SourcePackageLayout::Manifest.path()))?;
+    let Some(section) = parse_move_manifest_string(manifest)?
+        .get(SPEC_TEST_SECTION)
+        .cloned()
+    else {
+        return Ok(options.clone());
+    };
+
+    let config: ManifestConfig = section
+        .try_into()
+        .with_context(|| format!("Invalid [{SPEC_TEST_SECTION}] section of the manifest"))?;
+    info!("Using the [{SPEC_TEST_SECTION}] section of the manifest: {config:?}");
+    merge(options, config)
+}
+
+fn merge(options: &CLIOptions, config: ManifestConfig) -> anyhow::Result<CLIOptions> {
+    let mut options = options.clone();
+
+    options.prover_timeout = options.prover_timeout.or(config.prover_timeout);
+    options.prover_cores = options.prover_cores.or(config.prover_cores);
+
+    if options.min_score.is_empty() {
+        options.min_score = config
+            .min_score
+            .into_iter()
+            .map(|threshold| match threshold {
+                Threshold::Total(score) => ScoreThreshold::from_str(&score.to_string()),
+                Threshold::Text(text) => ScoreThreshold::from_str(&text),
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|e| anyhow!("Invalid min-score in the [{SPEC_TEST_SECTION}] section: {e}"))?;
+    }
+
+    if options.function_timeout.is_empty() {
+        options.function_timeout = config
+            .function_timeout
+            .into_iter()
+            .map(|timeout| match timeout {
+                Timeout::All(secs) => FunctionTimeout::from_str(&secs.to_string()),
+                Timeout::Text(text) => FunctionTimeout::from_str(&text),
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|e| {
+                anyhow!("Invalid function-timeout in the [{SPEC_TEST_SECTION}] section: {e}")
+            })?;
+    }
+
+    if options.include_modules == ModuleFilter::All && !config.modules.is_empty() {
+        options.include_modules = ModuleFilter::Selected(config.modules);
+    }
+
+    if options.functions == FunctionFilter::All && !config.functions.is_empty() {
+        options.functions = FunctionFilter::Selected(config.functions);
+    }
+
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_options_are_merged_with_cli() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Move.toml"),
+            r#"
+[package]
+name = "Sum"
+version = "0.0.0"
+
+[spec-test]
+prover-timeout = 300
+function-timeout = [40, "Sum::sum=120"]
+prover-cores = 8
+min-score = [80, "Sum=90"]
+modules = ["Sum"]
+"#,
+        )
+        .unwrap();
+
+        let cli = CLIOptions {
+            prover_timeout: Some(60),
+            ..Default::default()
+        };
+        let options = merge_manifest_options(&cli, dir.path()).unwrap();
+        assert_eq!(options.prover_timeout, Some(60));
+        assert_eq!(options.prover_cores, Some(8));
+        assert_eq!(options.min_score, vec![
+            ScoreThreshold::Total(80.0),
+            ScoreThreshold::Module("Sum".to_owned(), 90.0)
+        ]);
+        assert_eq!(
+            options.include_modules,
+            ModuleFilter::Selected(vec!["Sum".to_owned()])
+        );
+        assert_eq!(options.functions, FunctionFilter::All);
+        assert_eq!(options.function_timeout, vec![
+            FunctionTimeout {
+                function: None,
+                secs: 40
+            },
+            FunctionTimeout {
+                function: Some("Sum::sum".to_owned()),
+                secs: 120
+            }
+        ]);
+    }
+
+    #[test]
+    fn manifest_without_section_keeps_options() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Move.toml"),
+            "[package]\nname = \"Sum\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+
+        let options = merge_manifest_options(&CLIOptions::default(), dir.path()).unwrap();
+        assert_eq!(options.prover_timeout, None);
+        assert!(options.min_score.is_empty());
+
+        fs::write(
+            dir.path().join("Move.toml"),
+            "[package]\nname = \"Sum\"\n[spec-test]\nno-such-option = 1\n",
+        )
+        .unwrap();
+        assert!(merge_manifest_options(&CLIOptions::default(), dir.path()).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{cache::ProverCache, prover::ProverOutcome, MutantCheck};
+use anyhow::anyhow;
+use move_mutator::report::MutationReport;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvError},
+        Arc,
+    },
+    thread::Scope,
+};
+
+/// Number of the mutants prepared ahead of the one being proved.
+const PIPELINE_DEPTH: usize = 2;
+
+/// Mutated package prepared for proving.
+#[derive(Debug)]
+pub(crate) struct PreparedMutant {
+    /// Path to the mutated package (removed once the mutant is proved).
+    pub(crate) package: PathBuf,
+    /// Key of the prover outcome in the cache, if the cache is used.
+    pub(crate) cache_key: Option<String>,
+    /// Prover outcome found in the cache.
+    pub(crate) cached: Option<ProverOutcome>,
+    /// Reason and diagnostics if the check killed the mutant.
+    pub(crate) checked: Option<(String, String)>,
+}
+
+/// Prepares the mutated packages for proving.
+pub(crate) struct Preparer<'a> {
+    /// Path to the original package.
+    pub(crate) package_path: &'a Path,
+    /// Directory where the mutated packages are created.
+    pub(crate) outdir: &'a Path,
+    /// Options of the prover (part of the cache key).
+    pub(crate) prover_conf: &'a move_prover::cli::Options,
+    /// Cache of the prover outcomes.
+    pub(crate) cache: Option<&'a ProverCache>,
+    /// Check run on each mutated package before proving it.
+    pub(crate) check: Option<&'a MutantCheck<'a>>,
+}
+
+impl Preparer<'_> {
+    /// Prepares the mutant for proving: copies the package along with the mutant file to a separate
+    /// directory, looks up the cached outcome and runs the check (unless the outcome is cached).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the package cannot be copied or the check fails to run.
+    fn prepare(&self, index: usize, mutant: &MutationReport) -> anyhow::Result<PreparedMutant> {
+        // Strip prefix to get the path relative to the package directory (or take that path if it's already relative).
+        let original_file = mutant
+            .original_file_path()
+            .strip_prefix(self.package_path)
+            .unwrap_or(mutant.original_file_path());
+        let package = self.outdir.join(format!("prove-{index}"));
+
+        let _ = fs::remove_dir_all(&package);
+        move_mutator::compiler::copy_dir_all(self.package_path, &package)?;
+
+        trace!(
+            "Copying mutant file {:?} to the package directory {:?}",
+            mutant.mutant_path(),
+            package.join(original_file)
+        );
+
+        if let Err(res) = fs::copy(mutant.mutant_path(), package.join(original_file)) {
+            return Err(anyhow!(
+                "Can't copy mutant file to the package directory: {res:?}"
+            ));
+        }
+
+        move_mutator::compiler::rewrite_manifest_for_mutant(self.package_path, &package)?;
+
+        let cache_key = match self.cache {
+            Some(cache) => Some(cache.key(&package, self.prover_conf)?),
+            None => None,
+        };
+        let cached = self
+            .cache
+            .zip(cache_key.as_deref())
+            .and_then(|(cache, key)| cache.get(key));
+
+        // The check is skipped for cached mutants, as it's not needed to get their outcome.
+        let checked = match self.check {
+            Some(check) if cached.is_none() => check(&package)?,
+            _ => None,
+        };
+
+        Ok(PreparedMutant {
+            package,
+            cache_key,
+            cached,
+            checked,
+        })
+    }
+}
+
+/// Prepares the mutants on a separate thread, ahead of the prover. Copying the packages and running
+/// the check (e.g. compiling and running the unit tests) overlaps with proving the previous mutants,
+/// instead of alternating with it.
+///
+/// Prepared mutants are received in the given order. The queue is bounded, so only a few mutants
+/// are prepared ahead. Preparing stops at the first error or once the queue is discarded (e.g. when
+/// the time budget is exceeded).
+///
+/// The preparing thread compiles the mutants while the prover runs, so the sandboxed prover has to be
+/// started as a new process (see `move_mutator::sandbox::run_sandboxed`). A process forked while the
+/// preparing thread holds a lock (e.g. of the symbol pool) would hang on it.
+///
+/// # Arguments
+///
+/// * `scope` - the scope of the preparing thread.
+/// * `preparer` - the preparer of the mutants.
+/// * `mutants` - the mutants to prepare along with their indices in the report.
+///
+/// # Returns
+///
+/// * `PreparedQueue` - the queue of the prepared mutants.
+pub(crate) fn prepare_ahead<'scope>(
+    scope: &'scope Scope<'scope, '_>,
+    preparer: &'scope Preparer<'scope>,
+    mutants: Vec<(usize, &'scope MutationReport)>,
+) -> PreparedQueue {
+    let (sender, receiver) = mpsc::sync_channel(PIPELINE_DEPTH);
+    let stop = Arc::new(AtomicBool::new(false));
+    let stopped = stop.clone();
+    scope.spawn(move || {
+        for (index, mutant) in mutants {
+            if stopped.load(Ordering::Relaxed) {
+                break;
+            }
+            let prepared = preparer.prepare(index, mutant);
+            let failed = prepared.is_err();
+            if let Err(mpsc::SendError(prepared)) = sender.send(prepared) {
+                if let Ok(prepared) = prepared {
+                    let _ = fs::remove_dir_all(prepared.package);
+                }
+                break;
+            }
+            if failed {
+                break;
+            }
+        }
+    });
+    PreparedQueue { receiver, stop }
+}
+
+/// Queue of the mutants prepared ahead of the prover.
+pub(crate) struct PreparedQueue {
+    receiver: Receiver<anyhow::Result<PreparedMutant>>,
+    /// Set to stop preparing the remaining mutants.
+    stop: Arc<AtomicBool>,
+}
+
+impl PreparedQueue {
+    /// Waits for the next prepared mutant.
+    pub(crate) fn recv(&self) -> Result<anyhow::Result<PreparedMutant>, RecvError> {
+        self.receiver.recv()
+    }
+
+    /// Stops preparing the mutants and removes the packages prepared but not proved (e.g. when the
+    /// run was stopped early).
+    pub(crate) fn discard(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // The preparing thread finishes the mutant it is working on, so wait until it exits.
+        for prepared in self.receiver.into_iter().flatten() {
+            let _ = fs::remove_dir_all(prepared.package);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::{build_model, prove_mutant};
+    use move_mutator::sandbox::SandboxLimits;
+    use move_package::BuildConfig;
+    use std::{iter, thread, time::Duration};
+    use termcolor::Buffer;
+
+    /// Creates the package along with its mutants.
+    fn create_package(dir: &Path, source: &str, mutated: &[&str]) -> Vec<MutationReport> {
+        let package = dir.join("package");
+        fs::create_dir_all(package.join("sources")).unwrap();
+        fs::write(
+            package.join("Move.toml"),
+            "[package]\nname = \"Sum\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+        fs::write(package.join("sources/Sum.move"), source).unwrap();
+
+        mutated
+            .iter()
+            .enumerate()
+            .map(|(index, mutated)| {
+                let path = dir.join(format!("mutant{index}.move"));
+                fs::write(&path, mutated).unwrap();
+                MutationReport::new(
+                    &path,
+                    &package.join("sources/Sum.move"),
+                    "Sum",
+                    "sum",
+                    mutated,
+                    source,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn mutants_are_prepared_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("package");
+        let outdir = dir.path().join("out");
+        let mutants = create_package(dir.path(), "x + y\n", &["x - y\n", "x * y\n", "x / y\n"]);
+
+        let check = |package: &Path| -> anyhow::Result<Option<(String, String)>> {
+            let source = fs::read_to_string(package.join("sources/Sum.move"))?;
+            Ok(source
+                .contains('/')
+                .then(|| ("division".to_owned(), source)))
+        };
+        let prover_conf = move_prover::cli::Options::default();
+        let preparer = Preparer {
+            package_path: &package,
+            outdir: &outdir,
+            prover_conf: &prover_conf,
+            cache: None,
+            check: Some(&check),
+        };
+
+        let prepared = thread::scope(|scope| {
+            let queue = prepare_ahead(scope, &preparer, mutants.iter().enumerate().collect());
+            iter::from_fn(|| queue.recv().ok())
+                .map(Result::unwrap)
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(prepared.len(), 3);
+        for (index, mutant) in prepared.iter().enumerate() {
+            assert_eq!(mutant.package, outdir.join(format!("prove-{index}")));
+            assert!(mutant.cache_key.is_none() && mutant.cached.is_none());
+        }
+        assert_eq!(
+            fs::read_to_string(prepared[1].package.join("sources/Sum.move")).unwrap(),
+            "x * y\n"
+        );
+        assert!(prepared[1].checked.is_none());
+        assert_eq!(prepared[2].checked.as_ref().unwrap().0, "division");
+    }
+
+    #[test]
+    fn discarded_mutants_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("package");
+        let outdir = dir.path().join("out");
+        let mutated = vec!["x - y\n"; 8];
+        let mutants = create_package(dir.path(), "x + y\n", &mutated);
+
+        let prover_conf = move_prover::cli::Options::default();
+        let preparer = Preparer {
+            package_path: &package,
+            outdir: &outdir,
+            prover_conf: &prover_conf,
+            cache: None,
+            check: None,
+        };
+
+        thread::scope(|scope| {
+            let queue = prepare_ahead(scope, &preparer, mutants.iter().enumerate().collect());
+            let first = queue.recv().unwrap().unwrap();
+            fs::remove_dir_all(first.package).unwrap();
+            queue.discard();
+        });
+
+        assert_eq!(fs::read_dir(&outdir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn mutants_are_prepared_while_proved_in_sandbox() {
+        move_mutator::sandbox::set_task_args(vec![
+            "prover::tests::sandbox_task".to_owned(),
+            "--exact".to_owned(),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("package");
+        let outdir = dir.path().join("out");
+        // The mutants don't compile, so the prover fails without running Boogie.
+        let source = "module 0x42::Sum { fun sum(x: u64): u64 { x + true } }\n";
+        let sub = source.replace('+', "-");
+        let mul = source.replace('+', "*");
+        let mutants = create_package(dir.path(), source, &[&sub, &mul, &sub, &mul]);
+
+        let config = BuildConfig::default();
+        // The check compiles the mutant on the preparing thread, while the previous one is proved.
+        let check = |package: &Path| -> anyhow::Result<Option<(String, String)>> {
+            let _ = build_model(&config, package);
+            Ok(None)
+        };
+        let prover_conf = move_prover::cli::Options::default();
+        let preparer = Preparer {
+            package_path: &package,
+            outdir: &outdir,
+            prover_conf: &prover_conf,
+            cache: None,
+            check: Some(&check),
+        };
+        let limits = SandboxLimits {
+            timeout: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+
+        let outcomes = thread::scope(|scope| {
+            let queue = prepare_ahead(scope, &preparer, mutants.iter().enumerate().collect());
+            iter::from_fn(|| queue.recv().ok())
+                .map(|prepared| {
+                    let package = prepared.unwrap().package;
+                    let mut diagnostics = Buffer::no_color();
+                    prove_mutant(
+                        &config,
+                        &package,
+                        &prover_conf,
+                        None,
+                        &mut diagnostics,
+                        &limits,
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(outcomes.len(), 4);
+        assert!(outcomes
+            .iter()
+            .all(|outcome| matches!(outcome, ProverOutcome::Failed { .. })));
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use indicatif::{ProgressBar, ProgressStyle};
+use move_mutator::report::MutantStatus;
+use std::time::Duration;
+
+/// Template of the progress bar.
+const PROGRESS_TEMPLATE: &str =
+    "{elapsed_precise} [{bar:40}] {pos}/{len} mutants proved ({msg}), ETA {eta}";
+
+/// Interval of redrawing the progress bar.
+const STEADY_TICK: Duration = Duration::from_secs(1);
+
+/// Live progress of proving the mutants, shown on the terminal.
+///
+/// The bar is hidden if stderr is not a terminal (e.g. in CI logs).
+pub(crate) struct Progress {
+    bar: ProgressBar,
+    killed: usize,
+    survived: usize,
+    timed_out: usize,
+}
+
+impl Progress {
+    /// Creates the progress of proving the given number of mutants.
+    pub(crate) fn new(total: usize) -> Self {
+        let bar = ProgressBar::new(total as u64);
+        bar.set_style(
+            ProgressStyle::default_bar()
+                .template(PROGRESS_TEMPLATE)
+                .progress_chars("=> "),
+        );
+        // Keep the elapsed time and ETA moving while a single mutant takes long to prove.
+        bar.enable_steady_tick(STEADY_TICK.as_millis() as u64);
+
+        let progress = Self {
+            bar,
+            killed: 0,
+            survived: 0,
+            timed_out: 0,
+        };
+        progress.update_message();
+        progress
+    }
+
+    /// Records the status of the proved mutant.
+    pub(crate) fn record(&mut self, status: MutantStatus) {
+        match status {
+            MutantStatus::Killed => self.killed += 1,
+            MutantStatus::Survived => self.survived += 1,
+            MutantStatus::Timeout => self.timed_out += 1,
+            _ => {},
+        }
+        self.bar.inc(1);
+        self.update_message();
+    }
+
+    /// Removes the progress bar from the terminal.
+    pub(crate) fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+
+    fn update_message(&self) {
+        self.bar.set_message(&self.message());
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "killed: {}, survived: {}, timed out: {}",
+            self.killed, self.survived, self.timed_out
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_are_counted() {
+        let mut progress = Progress::new(4);
+        progress.record(MutantStatus::Killed);
+        progress.record(MutantStatus::Killed);
+        progress.record(MutantStatus::Survived);
+        progress.record(MutantStatus::Timeout);
+        assert_eq!(progress.bar.position(), 4);
+        assert_eq!(progress.message(), "killed: 2, survived: 1, timed out: 1");
+        progress.finish();
+    }
+}
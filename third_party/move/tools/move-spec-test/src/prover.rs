@@ -2,10 +2,41 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use move_model::metadata::{CompilerVersion, LanguageVersion};
+use move_model::{
+    ast::{PropertyValue, Value},
+    metadata::{CompilerVersion, LanguageVersion},
+    model::{GlobalEnv, VerificationScope},
+    pragmas::VERIFY_PRAGMA,
+};
+use move_mutator::sandbox::{
+    run_sandboxed, SandboxFailure, SandboxLimits, SandboxResult, SandboxTask,
+};
 use move_package::{BuildConfig, ModelConfig};
-use std::{path::Path, time::Instant};
-use termcolor::WriteColor;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use termcolor::{Buffer, WriteColor};
+
+/// Result of proving a mutant.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ProverOutcome {
+    /// The prover verified the mutant, so it survived.
+    Verified,
+    /// The prover failed, so the mutant was killed.
+    Failed {
+        /// The specification condition which killed the mutant.
+        killed_by: String,
+        /// The diagnostics emitted by the prover.
+        diagnostics: String,
+    },
+    /// The prover run exceeded the timeout.
+    Timeout,
+    /// The prover run exceeded the memory limit.
+    OutOfMemory,
+}
 
 /// The `prove` function is responsible for proving the package.
 ///
@@ -14,6 +45,8 @@ use termcolor::WriteColor;
 /// * `config` - A `BuildConfig` representing the build configuration.
 /// * `package_path` - A `Path` to the package.
 /// * `prover_conf` - `move_prover::cli::Options` the options for the prover.
+/// * `skipped_function` - the function (`<MODULE>::<FUNCTION>`) not to verify, e.g. as it has been
+///   verified already.
 /// * `error_writer` - `&mut dyn std::io::Write` the error writer.
 ///
 /// # Returns
@@ -23,11 +56,36 @@ pub(crate) fn prove<W: WriteColor>(
     config: &BuildConfig,
     package_path: &Path,
     prover_conf: &move_prover::cli::Options,
+    skipped_function: Option<&str>,
     mut error_writer: &mut W,
 ) -> anyhow::Result<()> {
-    let mut model = config.clone().move_model_for_package(
-        package_path,
-        ModelConfig {
+    let mut model = build_model(config, package_path)?;
+    if let Some(function) = skipped_function {
+        skip_verification(&model, function);
+    }
+
+    let mut prover_conf = prover_conf.clone();
+    prover_conf.output_path = package_path
+        .to_path_buf()
+        .join("output.bpl")
+        .to_str()
+        .unwrap_or("")
+        .to_string();
+
+    let now = Instant::now();
+
+    move_prover::run_move_prover_with_model(&mut model, &mut error_writer, prover_conf, Some(now))
+}
+
+/// Builds the Move model of the package with all the files as targets.
+///
+/// # Errors
+///
+/// Returns an error if the package cannot be compiled.
+pub(crate) fn build_model(config: &BuildConfig, package_path: &Path) -> anyhow::Result<GlobalEnv> {
+    config
+        .clone()
+        .move_model_for_package(package_path, ModelConfig {
             all_files_as_targets: true,
             target_filter: None,
             compiler_version: config
@@ -38,18 +96,410 @@ pub(crate) fn prove<W: WriteColor>(
                 .compiler_config
                 .language_version
                 .unwrap_or(LanguageVersion::V1),
+        })
+}
+
+/// Excludes the function from the verification, as if it was marked with `pragma verify = false`.
+/// Its specification is still used when verifying its callers.
+fn skip_verification(model: &GlobalEnv, function: &str) {
+    let pragma = model.symbol_pool().make(VERIFY_PRAGMA);
+    for module in model.get_modules() {
+        for fun in module
+            .get_functions()
+            .filter(|fun| fun.matches_name(function))
+        {
+            fun.get_mut_spec()
+                .properties
+                .insert(pragma, PropertyValue::Value(Value::Bool(false)));
+        }
+    }
+}
+
+/// Proves the mutated package, capturing the diagnostics to find out what killed the mutant.
+///
+/// If any limit is set, the prover is run in a sandbox process which is killed (along with the
+/// Boogie and SMT solver processes) once the timeout or the memory limit is exceeded. The
+/// diagnostics are passed back from the sandbox process along with the prover result.
+///
+/// # Arguments
+///
+/// * `config` - A `BuildConfig` representing the build configuration.
+/// * `package_path` - A `Path` to the mutated package.
+/// * `prover_conf` - `move_prover::cli::Options` the options for the prover.
+/// * `skipped_function` - the function (`<MODULE>::<FUNCTION>`) not to verify.
+/// * `error_writer` - the writer to which the prover diagnostics are forwarded.
+/// * `limits` - the limits of the prover run.
+///
+/// # Errors
+///
+/// Returns an error if the diagnostics cannot be written or the sandboxed prover crashes.
+///
+/// # Returns
+///
+/// * `anyhow::Result<ProverOutcome>` - the outcome of the prover run.
+pub(crate) fn prove_mutant<W: WriteColor>(
+    config: &BuildConfig,
+    package_path: &Path,
+    prover_conf: &move_prover::cli::Options,
+    skipped_function: Option<&str>,
+    error_writer: &mut W,
+    limits: &SandboxLimits,
+) -> anyhow::Result<ProverOutcome> {
+    if !limits.is_enabled() {
+        let mut diagnostics = Buffer::no_color();
+        let result = prove(
+            config,
+            package_path,
+            prover_conf,
+            skipped_function,
+            &mut diagnostics,
+        );
+        error_writer.write_all(diagnostics.as_slice())?;
+        return Ok(outcome(result, diagnostics.as_slice()));
+    }
+
+    let task = ProveTask {
+        config: config.clone(),
+        package_path: package_path.to_owned(),
+        prover_conf: prover_conf.clone(),
+        skipped_function: skipped_function.map(str::to_owned),
+    };
+    match run_sandboxed(limits, &task) {
+        SandboxResult::Finished(Ok(report)) => {
+            error_writer.write_all(report.diagnostics.as_bytes())?;
+            let result = report.error.map_or(Ok(()), |e| Err(anyhow::anyhow!(e)));
+            Ok(outcome(result, report.diagnostics.as_bytes()))
         },
-    )?;
+        SandboxResult::Finished(Err(e)) => Err(e.context("Prover run failed")),
+        SandboxResult::Failed(SandboxFailure::Timeout) => Ok(ProverOutcome::Timeout),
+        SandboxResult::Failed(SandboxFailure::MemoryLimit) => Ok(ProverOutcome::OutOfMemory),
+        SandboxResult::Failed(failure) => Err(anyhow::anyhow!("Prover run failed: {failure}")),
+    }
+}
 
+/// Proving of the mutated package run in the sandbox process.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ProveTask {
+    /// The build configuration.
+    config: BuildConfig,
+    /// Path to the mutated package.
+    package_path: PathBuf,
+    /// The options for the prover.
+    prover_conf: move_prover::cli::Options,
+    /// The function not to verify.
+    skipped_function: Option<String>,
+}
+
+/// Result of the prover run passed back from the sandbox process.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ProveReport {
+    /// The error returned by the prover, if any.
+    error: Option<String>,
+    /// The diagnostics emitted by the prover.
+    diagnostics: String,
+}
+
+impl SandboxTask for ProveTask {
+    type Output = ProveReport;
+
+    const NAME: &'static str = "prove";
+
+    fn run(self) -> anyhow::Result<ProveReport> {
+        let mut diagnostics = Buffer::no_color();
+        let result = prove(
+            &self.config,
+            &self.package_path,
+            &self.prover_conf,
+            self.skipped_function.as_deref(),
+            &mut diagnostics,
+        );
+        Ok(ProveReport {
+            error: result.err().map(|e| format!("{e:#}")),
+            diagnostics: String::from_utf8_lossy(diagnostics.as_slice()).into_owned(),
+        })
+    }
+}
+
+/// Proves the mutated package, checking the mutated function first.
+///
+/// Most mutants are killed by a single condition of the mutated function, so the function is verified
+/// alone first. Only if it verifies, the rest of the package is proved (as the mutant can still break
+/// the specifications of other functions), skipping the already verified function. Boogie stops
+/// verifying each function at its first error in both runs.
+///
+/// # Arguments
+///
+/// * `config` - A `BuildConfig` representing the build configuration.
+/// * `package_path` - A `Path` to the mutated package.
+/// * `function` - the qualified name (`<MODULE>::<FUNCTION>`) of the mutated function.
+/// * `prover_conf` - `move_prover::cli::Options` the options for the prover.
+/// * `error_writer` - the writer to which the prover diagnostics are forwarded.
+/// * `limits` - the limits of each prover run.
+///
+/// # Errors
+///
+/// Returns an error if the diagnostics cannot be written or the sandboxed prover crashes.
+///
+/// # Returns
+///
+/// * `anyhow::Result<ProverOutcome>` - the outcome of the prover runs.
+pub(crate) fn prove_mutant_function_first<W: WriteColor>(
+    config: &BuildConfig,
+    package_path: &Path,
+    function: &str,
+    prover_conf: &move_prover::cli::Options,
+    error_writer: &mut W,
+    limits: &SandboxLimits,
+) -> anyhow::Result<ProverOutcome> {
+    let first_error_conf = first_error_options(prover_conf);
+    let mut function_conf = first_error_conf.clone();
+    function_conf.prover.verify_scope = VerificationScope::Only(function.to_owned());
+    match prove_mutant(
+        config,
+        package_path,
+        &function_conf,
+        None,
+        error_writer,
+        limits,
+    )? {
+        ProverOutcome::Verified => prove_mutant(
+            config,
+            package_path,
+            &first_error_conf,
+            Some(function),
+            error_writer,
+            limits,
+        ),
+        outcome => Ok(outcome),
+    }
+}
+
+/// Creates the prover options stopping the verification of each function at its first error.
+fn first_error_options(prover_conf: &move_prover::cli::Options) -> move_prover::cli::Options {
     let mut prover_conf = prover_conf.clone();
-    prover_conf.output_path = package_path
-        .to_path_buf()
-        .join("output.bpl")
-        .to_str()
-        .unwrap_or("")
-        .to_string();
+    prover_conf
+        .backend
+        .boogie_flags
+        .push("-errorLimit:1".to_owned());
+    prover_conf
+}
 
-    let now = Instant::now();
+/// Converts the prover result into the outcome.
+fn outcome(result: anyhow::Result<()>, diagnostics: &[u8]) -> ProverOutcome {
+    match result {
+        Ok(()) => ProverOutcome::Verified,
+        Err(e) => {
+            let diagnostics = String::from_utf8_lossy(diagnostics).trim_end().to_owned();
+            // A function exceeding its solver timeout doesn't kill the mutant on its own.
+            if only_timeouts(&diagnostics) {
+                return ProverOutcome::Timeout;
+            }
+            ProverOutcome::Failed {
+                killed_by: killing_condition(&diagnostics)
+                    .unwrap_or_else(|| e.to_string().lines().next().unwrap_or_default().to_owned()),
+                diagnostics,
+            }
+        },
+    }
+}
 
-    move_prover::run_move_prover_with_model(&mut model, &mut error_writer, prover_conf, Some(now))
+/// Checks if all the errors reported by the prover are solver timeouts of the verified functions.
+fn only_timeouts(diagnostics: &str) -> bool {
+    let mut errors = diagnostics
+        .lines()
+        .filter(|l| l.starts_with("error"))
+        .peekable();
+    errors.peek().is_some() && errors.all(is_timeout)
+}
+
+/// Checks if the error line reports a solver timeout.
+fn is_timeout(error: &str) -> bool {
+    error.contains("verification out of resources/timeout")
+}
+
+/// Finds the specification condition which failed in the prover diagnostics.
+///
+/// The message of the first reported error (other than a solver timeout) is used along with the first line of its source snippet
+/// (which points at the failing condition), e.g. `post-condition does not hold (ensures result == x + y;)`.
+///
+/// # Arguments
+///
+/// * `diagnostics` - the diagnostics emitted by the prover (without colors).
+///
+/// # Returns
+///
+/// * `Option<String>` - the failed condition or `None` if no error has been reported.
+fn killing_condition(diagnostics: &str) -> Option<String> {
+    let mut lines = diagnostics
+        .lines()
+        .skip_while(|l| !l.starts_with("error") || is_timeout(l));
+    let header = lines.next()?;
+    let message = header
+        .split_once(": ")
+        .map_or(header, |(_, message)| message)
+        .trim();
+
+    // Snippet lines look like ` 8 │         ensures result == x + y;`.
+    let snippet = lines.take_while(|l| !l.starts_with("error")).find_map(|l| {
+        let (number, code) = l.split_once(['│', '|'])?;
+        let number = number.trim();
+        (!number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+            .then(|| code.trim())
+            .filter(|code| !code.is_empty())
+    });
+
+    Some(match snippet {
+        Some(code) => format!("{message} ({code})"),
+        None => message.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, time::Duration};
+
+    // Serves the sandboxed tasks of the other tests, as the sandbox starts the test binary again.
+    #[test]
+    fn sandbox_task() {
+        if let Some(task) = move_mutator::sandbox::requested_task() {
+            crate::run_sandbox_task(&task).unwrap();
+        }
+    }
+
+    #[test]
+    fn sandboxed_prover_result_is_passed_back() {
+        move_mutator::sandbox::set_task_args(vec![
+            "prover::tests::sandbox_task".to_owned(),
+            "--exact".to_owned(),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sources")).unwrap();
+        fs::write(
+            dir.path().join("Move.toml"),
+            "[package]\nname = \"Sum\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sources/Sum.move"),
+            "module 0x42::Sum { fun sum(x: u64): u64 { x + true } }\n",
+        )
+        .unwrap();
+
+        let prove = |limits: &SandboxLimits| {
+            let mut diagnostics = Buffer::no_color();
+            let outcome = prove_mutant(
+                &BuildConfig::default(),
+                dir.path(),
+                &move_prover::cli::Options::default(),
+                None,
+                &mut diagnostics,
+                limits,
+            )
+            .unwrap();
+            (outcome, diagnostics.into_inner())
+        };
+
+        let (outcome, diagnostics) = prove(&SandboxLimits {
+            timeout: Some(Duration::from_secs(60)),
+            ..Default::default()
+        });
+        assert!(matches!(outcome, ProverOutcome::Failed { .. }));
+        assert_eq!((outcome, diagnostics), prove(&SandboxLimits::default()));
+    }
+
+    #[test]
+    fn first_error_options_limit_boogie_errors() {
+        let options = move_prover::cli::Options::default();
+        let first_error_options = first_error_options(&options);
+        assert!(first_error_options
+            .backend
+            .boogie_flags
+            .contains(&"-errorLimit:1".to_owned()));
+        assert!(!options
+            .backend
+            .boogie_flags
+            .contains(&"-errorLimit:1".to_owned()));
+    }
+
+    #[test]
+    fn skipped_function_is_not_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("sources")).unwrap();
+        fs::write(
+            dir.path().join("Move.toml"),
+            "[package]\nname = \"Sum\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("sources/Sum.move"),
+            "module 0x42::Sum { fun sum(x: u64): u64 { x } fun double(x: u64): u64 { x + x } }\n",
+        )
+        .unwrap();
+
+        let model = build_model(&BuildConfig::default(), dir.path()).unwrap();
+        skip_verification(&model, "Sum::sum");
+        let verified = model
+            .get_modules()
+            .flat_map(|module| {
+                module
+                    .get_functions()
+                    .filter(|fun| fun.should_verify(&VerificationScope::All))
+                    .map(|fun| fun.get_simple_name_string().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(verified, vec!["double".to_owned()]);
+    }
+
+    #[test]
+    fn killing_condition_is_found_in_diagnostics() {
+        let diagnostics = "\
+error: post-condition does not hold
+   ┌─ /tmp/prove/sources/Sum.move:8:9
+   │
+ 8 │         ensures result == x + y;
+   │         ^^^^^^^^^^^^^^^^^^^^^^^^
+   │
+   =     at /tmp/prove/sources/Sum.move:2: sum
+
+error: abort not covered by any of the `aborts_if` clauses
+   ┌─ /tmp/prove/sources/Sum.move:3:5
+   │
+ 3 │     fun sub(x: u64, y: u64): u64 {
+";
+        assert_eq!(
+            killing_condition(diagnostics).as_deref(),
+            Some("post-condition does not hold (ensures result == x + y;)")
+        );
+        assert_eq!(
+            killing_condition("error[E04007]: incompatible types\n").as_deref(),
+            Some("incompatible types")
+        );
+        assert_eq!(killing_condition("warning: unused variable\n"), None);
+    }
+
+    #[test]
+    fn function_timeouts_are_not_kills() {
+        let timeout = "\
+error: verification out of resources/timeout (global timeout set to 40s)
+   ┌─ /tmp/prove/sources/Vault.move:5:5
+   │
+ 5 │     fun withdraw(amount: u64) {
+";
+        assert!(matches!(
+            outcome(
+                Err(anyhow::anyhow!("exiting with verification errors")),
+                timeout.as_bytes()
+            ),
+            ProverOutcome::Timeout
+        ));
+
+        let killed = format!("{timeout}\nerror: post-condition does not hold\n");
+        assert!(matches!(
+            outcome(Err(anyhow::anyhow!("exiting with verification errors")), killed.as_bytes()),
+            ProverOutcome::Failed { killed_by, .. } if killed_by == "post-condition does not hold"
+        ));
+        assert!(!only_timeouts(""));
+    }
 }
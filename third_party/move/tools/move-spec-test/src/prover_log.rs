@@ -0,0 +1,152 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::prover::ProverOutcome;
+use move_mutator::report::MutationReport;
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Name of the Boogie file generated by the prover in the package directory.
+const BOOGIE_FILE: &str = "output.bpl";
+
+/// Saves the log of proving the mutant to the given directory, so it's possible to find out why the
+/// mutant survived (or what killed it) without re-running the prover manually.
+///
+/// The log (`<MUTANT_ID>.log`) contains the outcome, the diff of the mutant and the prover output.
+/// The Boogie file generated for the mutant is kept along with it (`<MUTANT_ID>.bpl`), if any.
+///
+/// # Arguments
+///
+/// * `dir` - the directory where the logs are stored.
+/// * `entry` - the proved mutant.
+/// * `outcome` - the outcome of proving the mutant.
+/// * `output` - the diagnostics emitted by the prover (empty for cached outcomes).
+/// * `elapsed` - the proving time (`None` if the mutant wasn't proved in this run).
+/// * `package_path` - the path to the mutated package which was proved.
+///
+/// # Errors
+///
+/// Returns an error if the log cannot be written.
+///
+/// # Returns
+///
+/// * `anyhow::Result<PathBuf>` - the path to the saved log.
+pub(crate) fn save_prover_log(
+    dir: &Path,
+    entry: &MutationReport,
+    outcome: &ProverOutcome,
+    output: &str,
+    elapsed: Option<Duration>,
+    package_path: &Path,
+) -> anyhow::Result<PathBuf> {
+    let log_path = dir.join(format!("{}.log", entry.mutant_id()));
+    fs::write(&log_path, render_log(entry, outcome, output, elapsed))?;
+
+    let boogie_file = package_path.join(BOOGIE_FILE);
+    if boogie_file.exists() {
+        fs::copy(&boogie_file, dir.join(format!("{}.bpl", entry.mutant_id())))?;
+    }
+
+    Ok(log_path)
+}
+
+/// Renders the log of proving the mutant.
+fn render_log(
+    entry: &MutationReport,
+    outcome: &ProverOutcome,
+    output: &str,
+    elapsed: Option<Duration>,
+) -> String {
+    let mut log = String::new();
+    let _ = writeln!(log, "mutant: {}", entry.mutant_id());
+    let _ = writeln!(log, "file: {}", entry.original_file_path().display());
+    let _ = writeln!(
+        log,
+        "function: {}::{}",
+        entry.get_module_name(),
+        entry.get_function_name()
+    );
+
+    let (status, output) = match outcome {
+        ProverOutcome::Verified => ("survived".to_owned(), output),
+        // Cached outcomes and mutants killed by a check have no prover output, so the stored
+        // diagnostics are used instead.
+        ProverOutcome::Failed {
+            killed_by,
+            diagnostics,
+        } => (
+            format!("killed by {killed_by}"),
+            if output.is_empty() {
+                diagnostics.as_str()
+            } else {
+                output
+            },
+        ),
+        ProverOutcome::Timeout => ("timeout".to_owned(), output),
+        ProverOutcome::OutOfMemory => ("out of memory".to_owned(), output),
+    };
+    let _ = writeln!(log, "outcome: {status}");
+    match elapsed {
+        Some(elapsed) => {
+            let _ = writeln!(log, "elapsed: {:.3}s", elapsed.as_secs_f64());
+        },
+        None => log.push_str("elapsed: not proved in this run\n"),
+    }
+
+    let _ = writeln!(log, "\n--- diff ---\n{}", entry.get_diff().trim_end());
+    let _ = writeln!(log, "\n--- prover output ---\n{}", output.trim_end());
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prover_log_is_saved_with_boogie_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("package");
+        let logs = dir.path().join("logs");
+        fs::create_dir_all(&package).unwrap();
+        fs::create_dir_all(&logs).unwrap();
+        fs::write(package.join(BOOGIE_FILE), "procedure sum();").unwrap();
+
+        let entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            "Sum",
+            "sum",
+            "x - y\n",
+            "x + y\n",
+        );
+        let outcome = ProverOutcome::Failed {
+            killed_by: "post-condition does not hold".to_owned(),
+            diagnostics: "error: post-condition does not hold".to_owned(),
+        };
+
+        let path = save_prover_log(&logs, &entry, &outcome, "", None, &package).unwrap();
+        assert_eq!(path, logs.join(format!("{}.log", entry.mutant_id())));
+
+        let log = fs::read_to_string(&path).unwrap();
+        assert!(log.contains("function: Sum::sum\n"));
+        assert!(log.contains("outcome: killed by post-condition does not hold\n"));
+        assert!(log.contains("elapsed: not proved in this run\n"));
+        assert!(log.contains("-x + y\n+x - y\n"));
+        assert!(log.ends_with("--- prover output ---\nerror: post-condition does not hold\n"));
+        assert!(logs.join(format!("{}.bpl", entry.mutant_id())).exists());
+
+        let verified = render_log(
+            &entry,
+            &ProverOutcome::Verified,
+            "warning: unused variable\n",
+            Some(Duration::from_millis(1500)),
+        );
+        assert!(verified.contains("outcome: survived\nelapsed: 1.500s\n"));
+        assert!(verified.ends_with("warning: unused variable\n"));
+    }
+}
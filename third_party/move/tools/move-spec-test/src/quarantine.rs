@@ -0,0 +1,170 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_mutator::report::{MutantStatus, Report};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs::{self, OpenOptions},
+    io::Write as _,
+    path::Path,
+};
+
+/// List of the mutants acknowledged as equivalent to the original code.
+///
+/// The file is meant to be checked in along with the package. Each line contains a stable mutant ID
+/// optionally followed by a comment with the reason, e.g. `3f2a9c1b7d5e8f01 # x * 1 == x`. Empty
+/// lines and lines starting with `#` are ignored.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct Quarantine {
+    /// Reasons keyed by the mutant IDs (empty if no reason is given).
+    mutants: BTreeMap<String, String>,
+}
+
+impl Quarantine {
+    /// Loads the quarantine list from the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        Ok(Self::parse(&fs::read_to_string(path)?))
+    }
+
+    fn parse(content: &str) -> Self {
+        let mutants = content
+            .lines()
+            .filter_map(|line| {
+                let (id, reason) = line.split_once('#').unwrap_or((line, ""));
+                let id = id.trim();
+                (!id.is_empty()).then(|| (id.to_owned(), reason.trim().to_owned()))
+            })
+            .collect();
+        Self { mutants }
+    }
+
+    /// Appends the mutant with the given ID (and the reason, if any) to the quarantine file, creating
+    /// the file if needed. Mutants already in the file are not added again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or written.
+    pub(crate) fn append(path: &Path, mutant_id: &str, reason: &str) -> anyhow::Result<()> {
+        let content = if path.exists() {
+            fs::read_to_string(path)?
+        } else {
+            String::new()
+        };
+        if Self::parse(&content).contains(mutant_id) {
+            return Ok(());
+        }
+
+        let mut line = mutant_id.to_owned();
+        if !reason.is_empty() {
+            let _ = write!(line, " # {reason}");
+        }
+        let separator = if content.is_empty() || content.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{separator}{line}")?;
+        Ok(())
+    }
+
+    /// Checks if the mutant with the given ID is quarantined.
+    pub(crate) fn contains(&self, mutant_id: &str) -> bool {
+        self.mutants.contains_key(mutant_id)
+    }
+
+    /// Returns the quarantined IDs which are not found in the report (e.g. because the code has
+    /// changed), so the stale entries can be removed from the list.
+    pub(crate) fn missing(&self, report: &Report) -> Vec<&str> {
+        self.mutants
+            .keys()
+            .filter(|id| !report.get_mutants().iter().any(|m| m.mutant_id() == *id))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Renders the quarantined mutants of the report along with their reasons.
+    pub(crate) fn render(&self, report: &Report) -> String {
+        let mut output = String::new();
+        for entry in report.query().status(MutantStatus::Equivalent).iter() {
+            let _ = write!(
+                output,
+                "Mutant {} quarantined in {} ({}::{})",
+                entry.mutant_id(),
+                entry.original_file_path().display(),
+                entry.get_module_name(),
+                entry.get_function_name()
+            );
+            match self.mutants.get(entry.mutant_id()) {
+                Some(reason) if !reason.is_empty() => {
+                    let _ = writeln!(output, ": {reason}");
+                },
+                _ => output.push('\n'),
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::{Mutation, MutationReport, Range};
+
+    #[test]
+    fn quarantine_is_parsed_and_rendered() {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            "Sum",
+            "sum",
+            "x * 1\n",
+            "x\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(1, 5),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "*".to_string(),
+        ));
+        let id = entry.mutant_id().to_owned();
+
+        let quarantine = Quarantine::parse(&format!(
+            "# Equivalent mutants of the Sum module\n\n{id} # x * 1 == x\n0123456789abcdef\n"
+        ));
+        assert!(quarantine.contains(&id));
+        assert!(quarantine.contains("0123456789abcdef"));
+        assert!(!quarantine.contains("# Equivalent mutants of the Sum module"));
+
+        entry.set_status(MutantStatus::Equivalent);
+        let mut report = Report::new();
+        report.add_entry(entry);
+
+        assert_eq!(quarantine.missing(&report), vec!["0123456789abcdef"]);
+        assert_eq!(
+            quarantine.render(&report),
+            format!("Mutant {id} quarantined in sources/Sum.move (Sum::sum): x * 1 == x\n")
+        );
+    }
+
+    #[test]
+    fn mutants_are_appended_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("quarantine.txt");
+        fs::write(&path, "# Equivalent mutants").unwrap();
+
+        Quarantine::append(&path, "0123456789abcdef", "x * 1 == x").unwrap();
+        Quarantine::append(&path, "fedcba9876543210", "").unwrap();
+        Quarantine::append(&path, "0123456789abcdef", "again").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# Equivalent mutants\n0123456789abcdef # x * 1 == x\nfedcba9876543210\n"
+        );
+    }
+}
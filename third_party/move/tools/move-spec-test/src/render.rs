@@ -0,0 +1,189 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{cli::ReportFormat, csv_report, junit, report, report::Report};
+use anyhow::anyhow;
+use move_mutator::report::MutantStatus;
+use std::{fmt::Write, path::Path};
+
+/// Results of a completed run loaded from a report.
+pub(crate) enum RunResults {
+    /// The mutator report with the status of each mutant (e.g. saved with `--mutator-report-output`).
+    Mutants(move_mutator::report::Report),
+    /// The report with the mutation scores only (saved with `--output`).
+    Scores(Report),
+}
+
+impl RunResults {
+    /// Loads the results from the mutator report or, if it's not one, from the report with the
+    /// mutation scores.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or it's neither of the reports.
+    pub(crate) fn load(path: &Path) -> anyhow::Result<Self> {
+        match move_mutator::report::Report::load_from_json_file(path) {
+            Ok(report) => Ok(RunResults::Mutants(report)),
+            Err(mutator_error) => Report::load_from_json_file(path)
+                .map(RunResults::Scores)
+                .map_err(|_| {
+                    anyhow!(
+                        "{} is neither a mutator report nor a specification test report: \
+                         {mutator_error}",
+                        path.display()
+                    )
+                }),
+        }
+    }
+
+    /// Renders the results in the given format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format needs the status of each mutant, but only the mutation scores
+    /// were loaded.
+    pub(crate) fn render(&self, format: ReportFormat) -> anyhow::Result<String> {
+        let mutants = match self {
+            RunResults::Scores(_) if format.needs_mutants() => {
+                return Err(anyhow!(
+                    "The {format:?} report needs the status of each mutant, use the mutator report \
+                     (e.g. saved with `--mutator-report-output`) instead"
+                ));
+            },
+            RunResults::Scores(scores) => return render_scores(scores, format, None),
+            RunResults::Mutants(mutants) => mutants,
+        };
+
+        Ok(match format {
+            ReportFormat::Text | ReportFormat::Json | ReportFormat::Markdown => {
+                render_scores(&Report::from_mutator_report(mutants), format, Some(mutants))?
+            },
+            ReportFormat::Html => mutants.to_html(),
+            ReportFormat::Sarif => {
+                let mut survivors = mutants.clone();
+                survivors
+                    .get_mutants_mut()
+                    .retain(|m| m.status() == MutantStatus::Survived);
+                serde_json::to_string_pretty(&survivors.to_sarif())?
+            },
+            ReportFormat::Junit => junit::to_junit(mutants),
+            ReportFormat::Csv => {
+                let mut csv = Vec::new();
+                csv_report::write_csv(&mut csv, mutants)?;
+                String::from_utf8(csv)?
+            },
+            ReportFormat::Stryker => serde_json::to_string_pretty(&mutants.to_stryker())?,
+        })
+    }
+}
+
+/// Renders the formats which need the mutation scores only.
+fn render_scores(
+    scores: &Report,
+    format: ReportFormat,
+    mutants: Option<&move_mutator::report::Report>,
+) -> anyhow::Result<String> {
+    match format {
+        ReportFormat::Json => scores.to_json(),
+        ReportFormat::Markdown => Ok(scores.to_markdown(report::MAX_MARKDOWN_SURVIVORS)),
+        _ => Ok(render_text(scores, mutants)),
+    }
+}
+
+/// Renders the totals and the table with the scores, as printed at the end of the run. The mutants
+/// which don't affect the score (e.g. timed out) are counted only if their statuses are known.
+fn render_text(scores: &Report, mutants: Option<&move_mutator::report::Report>) -> String {
+    let mut text = format!(
+        "Total mutants tested: {}\nTotal mutants killed: {}\n",
+        scores.mutants_tested(),
+        scores.mutants_killed()
+    );
+    if let Some(mutants) = mutants {
+        for (status, description) in [
+            (MutantStatus::Equivalent, "quarantined as equivalent"),
+            (MutantStatus::NoSpec, "outside any spec's reach (no-spec)"),
+            (MutantStatus::Skipped, "skipped"),
+            (MutantStatus::OutOfMemory, "out of memory"),
+            (MutantStatus::Timeout, "timed out"),
+        ] {
+            let count = mutants.query().status(status).len();
+            if count > 0 {
+                let _ = writeln!(text, "Total mutants {description}: {count}");
+            }
+        }
+    }
+    let _ = writeln!(text, "\n{}", scores.to_table());
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::{Mutation, MutationReport, Range};
+
+    fn mutants() -> move_mutator::report::Report {
+        let mut report = move_mutator::report::Report::new();
+        report.set_package_root(Path::new("/package"));
+        for (function, mutated, status) in [
+            ("sum", "x - y\n", MutantStatus::Killed),
+            ("sum", "x * y\n", MutantStatus::Survived),
+            ("sub", "x / y\n", MutantStatus::Timeout),
+        ] {
+            let mut entry = MutationReport::new(
+                Path::new("mutant.move"),
+                Path::new("/package/sources/Sum.move"),
+                "Sum",
+                function,
+                mutated,
+                "x + y\n",
+            );
+            entry.add_modification(Mutation::new(
+                Range::new(2, 3),
+                "binary_operator_replacement".to_owned(),
+                "+".to_owned(),
+                mutated[2..3].to_owned(),
+            ));
+            entry.set_status(status);
+            report.add_entry(entry);
+        }
+        report
+    }
+
+    #[test]
+    fn results_are_rendered_from_mutants() {
+        let results = RunResults::Mutants(mutants());
+
+        let text = results.render(ReportFormat::Text).unwrap();
+        assert!(text.starts_with(
+            "Total mutants tested: 2\nTotal mutants killed: 1\nTotal mutants timed out: 1\n"
+        ));
+        assert!(text.contains("sources/Sum.move::Sum::sum"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&results.render(ReportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["summary"]["total"]["score"], 50.0);
+        assert!(results
+            .render(ReportFormat::Markdown)
+            .unwrap()
+            .contains("**Mutation score: 50.00%** (1 of 2 mutants killed)"));
+
+        let sarif: serde_json::Value =
+            serde_json::from_str(&results.render(ReportFormat::Sarif).unwrap()).unwrap();
+        assert_eq!(sarif["runs"][0]["results"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            results.render(ReportFormat::Csv).unwrap().lines().count(),
+            4
+        );
+    }
+
+    #[test]
+    fn scores_render_only_summaries() {
+        let results = RunResults::Scores(Report::from_mutator_report(&mutants()));
+        assert!(results
+            .render(ReportFormat::Text)
+            .unwrap()
+            .starts_with("Total mutants tested: 2\nTotal mutants killed: 1\n\n"));
+        assert!(results.render(ReportFormat::Html).is_err());
+    }
+}
@@ -2,9 +2,12 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use serde::Serialize;
+use crate::cli::ScoreThreshold;
+use move_mutator::report::MutantStatus;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
+    fmt::Write,
     path::{Path, PathBuf},
 };
 use tabled::{builder::Builder, settings::Style};
@@ -12,7 +15,7 @@ use tabled::{builder::Builder, settings::Style};
 /// This struct represents a report of the specification testing.
 /// It contains the list of entries, where each entry is a file and the number of mutants tested
 /// and killed in that file (in form of a `ReportEntry` structure).
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Report {
     /// The list of entries in the report.
     files: BTreeMap<PathBuf, Vec<MutantStats>>,
@@ -49,6 +52,79 @@ impl Report {
         self.total_count(|v| v.killed)
     }
 
+    /// Returns the percentage of killed mutants (0 if no mutants were tested).
+    pub fn mutation_score(&self) -> f64 {
+        Score::new(self.mutants_tested(), self.mutants_killed()).score
+    }
+
+    /// Returns mutation scores per module (mutants from all functions of the module are counted).
+    pub fn module_scores(&self) -> BTreeMap<String, Score> {
+        self.aggregate(|_, stat| {
+            stat.module_func
+                .split("::")
+                .next()
+                .unwrap_or_default()
+                .to_owned()
+        })
+    }
+
+    /// Returns mutation scores per function (keyed by `module::function`).
+    pub fn function_scores(&self) -> BTreeMap<String, Score> {
+        self.aggregate(|_, stat| stat.module_func.clone())
+    }
+
+    /// Returns the summary of the mutation scores - total, per module and per function.
+    pub fn summary(&self) -> Summary {
+        Summary {
+            total: Score::new(self.mutants_tested(), self.mutants_killed()),
+            modules: self.module_scores(),
+            functions: self.function_scores(),
+        }
+    }
+
+    /// Checks the mutation scores against the given thresholds.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - descriptions of the thresholds which are not met (empty if all are met).
+    pub fn threshold_violations(&self, thresholds: &[ScoreThreshold]) -> Vec<String> {
+        let modules = self.module_scores();
+        let mut violations = vec![];
+
+        for threshold in thresholds {
+            match threshold {
+                ScoreThreshold::Total(min) => {
+                    let score = self.mutation_score();
+                    if score < *min {
+                        violations.push(format!(
+                            "Total mutation score {score:.2}% is below the threshold {min:.2}%"
+                        ));
+                    }
+                },
+                ScoreThreshold::Module(module, min) => match modules.get(module) {
+                    Some(score) if score.score < *min => violations.push(format!(
+                        "Mutation score {:.2}% of module {module} is below the threshold {min:.2}%",
+                        score.score
+                    )),
+                    Some(_) => {},
+                    None => warn!("No mutants tested in module {module}, the threshold is ignored"),
+                },
+            }
+        }
+
+        violations
+    }
+
+    /// Strips the diffs of the surviving mutants (the number of survivors is kept), so the report
+    /// doesn't contain any source code.
+    pub fn anonymize(&mut self) {
+        for stat in self.files.values_mut().flatten() {
+            for diff in &mut stat.mutants_alive_diffs {
+                diff.clear();
+            }
+        }
+    }
+
     /// Add a diff for a not killed mutant.
     pub fn add_mutants_alive_diff(&mut self, path: &Path, module_func: &str, diff: &str) {
         let entry = self
@@ -65,15 +141,172 @@ impl Report {
         }
     }
 
+    /// Merges another report (e.g. from a different shard of the same run) into this one, summing up
+    /// the counts of the mutants and keeping the diffs of the surviving mutants of both reports.
+    pub fn merge(&mut self, other: Report) {
+        for (path, stats) in other.files {
+            for stat in stats {
+                self.increment_stat(&path, &stat.module_func, |existing| {
+                    existing.tested += stat.tested;
+                    existing.killed += stat.killed;
+                    existing
+                        .mutants_alive_diffs
+                        .extend(stat.mutants_alive_diffs.iter().cloned());
+                });
+            }
+        }
+    }
+
+    /// Creates the report from the mutator report with the status of each mutant (e.g. saved with
+    /// `--mutator-report-output`), so a completed run can be re-rendered without proving anything.
+    /// Only the killed and surviving mutants are counted as tested, like during the run.
+    pub fn from_mutator_report(mutator_report: &move_mutator::report::Report) -> Self {
+        let mut report = Self::new();
+        for mutant in mutator_report.get_mutants() {
+            let path = mutator_report
+                .package_root()
+                .and_then(|root| mutant.original_file_path().strip_prefix(root).ok())
+                .unwrap_or(mutant.original_file_path());
+            let qname = format!(
+                "{}::{}",
+                mutant.get_module_name(),
+                mutant.get_function_name()
+            );
+            match mutant.status() {
+                MutantStatus::Killed => {
+                    report.increment_mutants_tested(path, &qname);
+                    report.increment_mutants_killed(path, &qname);
+                },
+                MutantStatus::Survived => {
+                    report.increment_mutants_tested(path, &qname);
+                    report.add_mutants_alive_diff(path, &qname, mutant.get_diff());
+                },
+                _ => {},
+            }
+        }
+        report
+    }
+
     /// Save the report to a JSON file.
     /// The file is created if it does not exist, otherwise it is overwritten.
+    /// The report is stored along with the score summary (see `Report::summary`).
     pub fn save_to_json_file(&self, path: &PathBuf) -> anyhow::Result<()> {
-        let file = std::fs::File::create(path)?;
-        Ok(serde_json::to_writer_pretty(file, self)?)
+        Ok(std::fs::write(path, self.to_json()?)?)
+    }
+
+    /// Renders the report as JSON, along with the score summary (see `Report::summary`).
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        let report = SerializedReport {
+            report: self,
+            summary: self.summary(),
+        };
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    /// Load the report from a JSON file.
+    pub fn load_from_json_file(path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Save the report to a Markdown file.
+    /// The file is created if it does not exist, otherwise it is overwritten.
+    pub fn save_to_markdown_file(&self, path: &PathBuf) -> anyhow::Result<()> {
+        Ok(std::fs::write(
+            path,
+            self.to_markdown(MAX_MARKDOWN_SURVIVORS),
+        )?)
+    }
+
+    /// Renders the report as a Markdown document suitable for PR descriptions or CI bot comments.
+    ///
+    /// The document contains the overall mutation score, a table with per-module results and the
+    /// diffs of at most `max_survivors` surviving mutants, starting with the functions with the most
+    /// surviving mutants.
+    pub fn to_markdown(&self, max_survivors: usize) -> String {
+        let mut md = String::from("## Specification test report\n\n");
+        let _ = writeln!(
+            md,
+            "**Mutation score: {}** ({} of {} mutants killed)\n",
+            format_score(self.mutants_killed(), self.mutants_tested()),
+            self.mutants_killed(),
+            self.mutants_tested()
+        );
+
+        let modules = self.aggregate(|path, stat| {
+            let module = stat.module_func.split("::").next().unwrap_or_default();
+            format!("{}::{module}", path.to_string_lossy())
+        });
+
+        md.push_str("| Module | Mutants tested | Mutants killed | Score |\n");
+        md.push_str("| --- | ---: | ---: | ---: |\n");
+        for (module, score) in &modules {
+            let _ = writeln!(
+                md,
+                "| `{module}` | {} | {} | {} |",
+                score.tested,
+                score.killed,
+                format_score(score.killed, score.tested)
+            );
+        }
+
+        let mut survivors = self
+            .files
+            .iter()
+            .flat_map(|(path, stats)| stats.iter().map(move |stat| (path, stat)))
+            .filter(|(_, stat)| !stat.mutants_alive_diffs.is_empty())
+            .collect::<Vec<_>>();
+        // Stable sort keeps the path order for functions with the same number of survivors.
+        survivors.sort_by(|(_, a), (_, b)| {
+            b.mutants_alive_diffs
+                .len()
+                .cmp(&a.mutants_alive_diffs.len())
+        });
+
+        let total_survivors = survivors
+            .iter()
+            .map(|(_, stat)| stat.mutants_alive_diffs.len())
+            .sum::<usize>();
+        if total_survivors == 0 {
+            return md;
+        }
+
+        md.push_str("\n### Surviving mutants\n");
+        let diffs = survivors.iter().flat_map(|(path, stat)| {
+            stat.mutants_alive_diffs
+                .iter()
+                .map(move |diff| (path, &stat.module_func, diff))
+        });
+        for (path, module_func, diff) in diffs.take(max_survivors) {
+            let _ = write!(
+                md,
+                "\n<details>\n<summary><code>{}::{module_func}</code></summary>\n\n```diff\n{diff}",
+                path.to_string_lossy()
+            );
+            if !diff.ends_with('\n') {
+                md.push('\n');
+            }
+            md.push_str("```\n\n</details>\n");
+        }
+
+        if total_survivors > max_survivors {
+            let _ = writeln!(
+                md,
+                "\n_{} more surviving mutants are not shown._",
+                total_survivors - max_survivors
+            );
+        }
+
+        md
     }
 
     /// Prints the report to stdout in a table format.
     pub fn print_table(&self) {
+        println!("{}\n\n", self.to_table());
+    }
+
+    /// Renders the report in a table format.
+    pub fn to_table(&self) -> String {
         let mut builder = Builder::new();
         builder.push_record(["Module", "Mutants tested", "Mutants killed", "Percentage"]);
 
@@ -94,9 +327,7 @@ impl Report {
             }
         }
 
-        let table = builder.build().with(Style::modern_rounded()).to_string();
-
-        println!("{table}\n\n");
+        builder.build().with(Style::modern_rounded()).to_string()
     }
 
     // Internal function to increment the chosen stat.
@@ -117,6 +348,21 @@ impl Report {
         }
     }
 
+    // Internal function to aggregate the stats by the chosen key.
+    fn aggregate<F>(&self, mut key: F) -> BTreeMap<String, Score>
+    where
+        F: FnMut(&Path, &MutantStats) -> String,
+    {
+        let mut scores: BTreeMap<String, Score> = BTreeMap::new();
+        for (path, stats) in &self.files {
+            for stat in stats {
+                let score = scores.entry(key(path, stat)).or_default();
+                *score = Score::new(score.tested + stat.tested, score.killed + stat.killed);
+            }
+        }
+        scores
+    }
+
     // Internal function to count the chosen stat.
     fn total_count<F>(&self, mut count: F) -> u32
     where
@@ -129,15 +375,103 @@ impl Report {
     }
 
     /// Returns the list of entries in the report.
-    #[cfg(test)]
     pub fn entries(&self) -> &BTreeMap<PathBuf, Vec<MutantStats>> {
         &self.files
     }
 }
 
+/// Maximum number of surviving mutant diffs included in the Markdown report.
+pub(crate) const MAX_MARKDOWN_SURVIVORS: usize = 10;
+
+/// Formats the percentage of killed mutants.
+fn format_score(killed: u32, tested: u32) -> String {
+    if tested == 0 {
+        return "n/a".to_owned();
+    }
+
+    format!("{:.2}%", f64::from(killed) / f64::from(tested) * 100.0)
+}
+
+/// The report as stored in the JSON file - entries along with the score summary.
+#[derive(Serialize)]
+struct SerializedReport<'a> {
+    #[serde(flatten)]
+    report: &'a Report,
+    summary: Summary,
+}
+
+/// Saves the unified report - the mutator report with the status and prover diagnostics of each
+/// mutant, extended with the summary of the mutation scores.
+///
+/// The file is a valid mutator report, so it can be used with the mutator tooling (e.g. `move mutate apply`).
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_to_unified_file(
+    path: &Path,
+    spec_report: &Report,
+    mutator_report: &move_mutator::report::Report,
+) -> anyhow::Result<()> {
+    info!("Saving unified report to {}", path.display());
+    let file = std::fs::File::create(path)?;
+    let report = UnifiedReport {
+        report: &mutator_report.portable(),
+        summary: spec_report.summary(),
+    };
+    Ok(serde_json::to_writer_pretty(file, &report)?)
+}
+
+/// Mutator report extended with the summary of the mutation scores.
+#[derive(Serialize)]
+struct UnifiedReport<'a> {
+    #[serde(flatten)]
+    report: &'a move_mutator::report::Report,
+    summary: Summary,
+}
+
+/// Summary of the mutation scores of the report.
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    /// The score of all the mutants in the report.
+    pub total: Score,
+    /// Scores per module.
+    pub modules: BTreeMap<String, Score>,
+    /// Scores per function (keyed by `module::function`).
+    pub functions: BTreeMap<String, Score>,
+}
+
+/// Mutation score of a group of mutants (e.g. all mutants in a module).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Score {
+    /// The number of mutants tested.
+    pub tested: u32,
+    /// The number of mutants killed.
+    pub killed: u32,
+    /// The percentage of killed mutants (0 if no mutants were tested).
+    pub score: f64,
+}
+
+impl Score {
+    /// Creates a new score from the number of mutants tested and killed.
+    pub fn new(tested: u32, killed: u32) -> Self {
+        let score = if tested == 0 {
+            0.0
+        } else {
+            f64::from(killed) / f64::from(tested) * 100.0
+        };
+
+        Self {
+            tested,
+            killed,
+            score,
+        }
+    }
+}
+
 /// This struct represents an entry in the report.
 /// It contains the number of mutants tested and killed.
-#[derive(Default, Debug, Serialize)]
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct MutantStats {
     /// Module::function where mutant resides.
     pub module_func: String,
@@ -146,6 +480,7 @@ pub struct MutantStats {
     /// The number of mutants killed.
     pub killed: u32,
     /// The list of not killed mutants.
+    #[serde(default)]
     pub mutants_alive_diffs: Vec<String>,
 }
 
@@ -166,6 +501,82 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn threshold_violations_are_reported() {
+        let mut report = Report::new();
+        let path = PathBuf::from("sources/Sum.move");
+        report.increment_mutants_tested(&path, "Sum::sum");
+        report.increment_mutants_tested(&path, "Sum::sum");
+        report.increment_mutants_killed(&path, "Sum::sum");
+        report.increment_mutants_tested(&path, "Sub::sub");
+        report.increment_mutants_killed(&path, "Sub::sub");
+
+        assert!(report
+            .threshold_violations(&[
+                ScoreThreshold::Total(60.0),
+                ScoreThreshold::Module("Sub".to_owned(), 100.0),
+                ScoreThreshold::Module("Unknown".to_owned(), 100.0),
+            ])
+            .is_empty());
+
+        let violations = report.threshold_violations(&[
+            ScoreThreshold::Total(70.0),
+            ScoreThreshold::Module("Sum".to_owned(), 60.0),
+        ]);
+        assert_eq!(violations, vec![
+            "Total mutation score 66.67% is below the threshold 70.00%",
+            "Mutation score 50.00% of module Sum is below the threshold 60.00%",
+        ]);
+    }
+
+    #[test]
+    fn anonymize_strips_diffs_but_keeps_scores() {
+        let mut report = Report::new();
+        let path = PathBuf::from("sources/Sum.move");
+        report.increment_mutants_tested(&path, "Sum::sum");
+        report.add_mutants_alive_diff(&path, "Sum::sum", "-x + y\n+x - y\n");
+
+        report.anonymize();
+        let stat = &report.entries()[&path][0];
+        assert_eq!(stat.mutants_alive_diffs, vec![String::new()]);
+        assert_eq!(report.mutants_tested(), 1);
+    }
+
+    #[test]
+    fn unified_report_is_a_mutator_report_with_summary() {
+        let mut spec_report = Report::new();
+        let path = PathBuf::from("sources/Sum.move");
+        spec_report.increment_mutants_tested(&path, "Sum::sum");
+        spec_report.increment_mutants_killed(&path, "Sum::sum");
+
+        let mut entry = move_mutator::report::MutationReport::new(
+            Path::new("mutant.move"),
+            &path,
+            "Sum",
+            "sum",
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.set_status(move_mutator::report::MutantStatus::Killed);
+        entry.set_diagnostics("error: post-condition does not hold");
+        let mut mutator_report = move_mutator::report::Report::new();
+        mutator_report.add_entry(entry);
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("unified.json");
+        save_to_unified_file(&file, &spec_report, &mutator_report).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(json["summary"]["total"]["score"], 100.0);
+
+        let loaded = move_mutator::report::Report::load_from_json_file(&file).unwrap();
+        assert_eq!(
+            loaded.get_mutants()[0].diagnostics(),
+            Some("error: post-condition does not hold")
+        );
+    }
+
     #[test]
     fn report_starts_empty() {
         let report = Report::new();
@@ -256,6 +667,104 @@ mod tests {
                 && s.mutants_alive_diffs.contains(&diff.to_owned())));
     }
 
+    #[test]
+    fn report_is_loaded_from_json_file() {
+        let mut report = Report::new();
+        let path = PathBuf::from("path/to/file");
+        report.increment_mutants_tested(&path, "module::func");
+        report.increment_mutants_tested(&path, "module::func");
+        report.increment_mutants_killed(&path, "module::func");
+        report.add_mutants_alive_diff(&path, "module::func", "diff");
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("report.json");
+        report.save_to_json_file(&file).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(json["summary"]["functions"]["module::func"]["score"], 50.0);
+
+        let loaded = Report::load_from_json_file(&file).unwrap();
+        assert_eq!(loaded.mutants_tested(), 2);
+        assert_eq!(loaded.mutants_killed(), 1);
+        assert_eq!(loaded.mutation_score(), 50.0);
+        assert_eq!(loaded.entries()[&path][0].mutants_alive_diffs, vec!["diff"]);
+    }
+
+    #[test]
+    fn reports_are_merged() {
+        let sum = PathBuf::from("sources/Sum.move");
+        let mul = PathBuf::from("sources/Mul.move");
+
+        let mut report = Report::new();
+        report.increment_mutants_tested(&sum, "Sum::sum");
+        report.increment_mutants_killed(&sum, "Sum::sum");
+
+        let mut other = Report::new();
+        other.increment_mutants_tested(&sum, "Sum::sum");
+        other.add_mutants_alive_diff(&sum, "Sum::sum", "diff");
+        other.increment_mutants_tested(&mul, "Mul::mul");
+        other.increment_mutants_killed(&mul, "Mul::mul");
+
+        report.merge(other);
+        assert_eq!(report.mutants_tested(), 3);
+        assert_eq!(report.mutants_killed(), 2);
+        assert_eq!(report.entries()[&sum].len(), 1);
+        assert_eq!(report.entries()[&sum][0].tested, 2);
+        assert_eq!(report.entries()[&sum][0].mutants_alive_diffs, vec!["diff"]);
+        assert_eq!(report.entries()[&mul][0].killed, 1);
+    }
+
+    #[test]
+    fn scores_are_aggregated_per_module_and_function() {
+        let mut report = Report::new();
+        let path1 = PathBuf::from("path/to/file1");
+        let path2 = PathBuf::from("path/to/file2");
+        report.increment_mutants_tested(&path1, "Sum::sum");
+        report.increment_mutants_tested(&path1, "Sum::sum");
+        report.increment_mutants_killed(&path1, "Sum::sum");
+        report.increment_mutants_tested(&path1, "Sum::sub");
+        report.increment_mutants_killed(&path1, "Sum::sub");
+        report.increment_mutants_tested(&path2, "Mul::mul");
+
+        let modules = report.module_scores();
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules["Sum"].tested, 3);
+        assert_eq!(modules["Sum"].killed, 2);
+        assert_eq!(modules["Mul"], Score::new(1, 0));
+
+        let functions = report.function_scores();
+        assert_eq!(functions.len(), 3);
+        assert_eq!(functions["Sum::sum"].score, 50.0);
+        assert_eq!(functions["Sum::sub"].score, 100.0);
+
+        let summary = report.summary();
+        assert_eq!(summary.total, Score::new(4, 2));
+    }
+
+    #[test]
+    fn markdown_contains_score_module_table_and_survivors() {
+        let mut report = Report::new();
+        let path = PathBuf::from("sources/Sum.move");
+        for _ in 0..4 {
+            report.increment_mutants_tested(&path, "Sum::sum");
+        }
+        for _ in 0..3 {
+            report.increment_mutants_killed(&path, "Sum::sum");
+        }
+        report.increment_mutants_tested(&path, "Sum::sub");
+        report.add_mutants_alive_diff(&path, "Sum::sub", "-x - y\n+x + y\n");
+        report.add_mutants_alive_diff(&path, "Sum::sub", "-x - y\n+x / y\n");
+        report.add_mutants_alive_diff(&path, "Sum::sum", "-x + y\n+x * y\n");
+
+        let md = report.to_markdown(1);
+        assert!(md.contains("**Mutation score: 60.00%** (3 of 5 mutants killed)"));
+        assert!(md.contains("| `sources/Sum.move::Sum` | 5 | 3 | 60.00% |"));
+        assert!(md.contains("```diff\n-x - y\n+x + y\n```"));
+        assert!(!md.contains("x * y"));
+        assert!(md.contains("_2 more surviving mutants are not shown._"));
+    }
+
     #[test]
     fn add_mutants_alive_diff_adds_diff_to_existing_module() {
         let mut report = Report::new();
@@ -0,0 +1,243 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quarantine::Quarantine;
+use move_mutator::report::{MutantStatus, MutationReport, Report, Triage};
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+/// Choices offered for each reviewed mutant.
+const CHOICES: &str = "[n] needs spec, [e] equivalent, [i] ignore, [s] skip, [q] quit";
+
+/// Outcome of the review session.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ReviewOutcome {
+    /// Number of the mutants with a decision made in the session.
+    pub(crate) decided: usize,
+    /// Number of the surviving mutants still left without a decision.
+    pub(crate) undecided: usize,
+}
+
+/// Steps through the surviving mutants of the report one by one, showing their diffs and asking for
+/// the triage decision: the mutant needs a stronger specification, it's equivalent to the original
+/// code (so it's added to the quarantine file, if given) or it's ignored. Mutants can be skipped
+/// and the review can be quit at any time (also with the end of the input).
+///
+/// # Arguments
+///
+/// * `report` - the mutator report with the status of each mutant.
+/// * `quarantine` - the quarantine file where the equivalent mutants are added, if any.
+/// * `all` - review also the mutants with a decision from the previous sessions.
+/// * `input` - the input with the answers.
+/// * `output` - the output where the mutants and the questions are shown.
+/// * `on_decision` - called with the updated report after each decision, so it can be saved.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read, the output cannot be written or the callback fails.
+///
+/// # Returns
+///
+/// * `anyhow::Result<ReviewOutcome>` - the number of the decided and undecided mutants.
+pub(crate) fn review(
+    report: &mut Report,
+    quarantine: Option<&Path>,
+    all: bool,
+    mut input: impl BufRead,
+    mut output: impl Write,
+    mut on_decision: impl FnMut(&Report) -> anyhow::Result<()>,
+) -> anyhow::Result<ReviewOutcome> {
+    let pending = report
+        .get_mutants()
+        .iter()
+        .enumerate()
+        .filter(|(_, m)| m.status() == MutantStatus::Survived && (all || m.triage().is_none()))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+
+    let mut outcome = ReviewOutcome::default();
+    'mutants: for (position, &index) in pending.iter().enumerate() {
+        let mutant = &report.get_mutants()[index];
+        let mutant_id = mutant.mutant_id().to_owned();
+        writeln!(
+            output,
+            "\n[{}/{}] {}",
+            position + 1,
+            pending.len(),
+            describe(mutant)
+        )?;
+        let diff = mutant.get_diff();
+        write!(output, "{diff}")?;
+        if !diff.ends_with('\n') {
+            writeln!(output)?;
+        }
+
+        let triage = loop {
+            write!(output, "{CHOICES}: ")?;
+            output.flush()?;
+            let Some(answer) = read_answer(&mut input)? else {
+                break 'mutants;
+            };
+            match answer.as_str() {
+                "n" => break Triage::NeedsSpec,
+                "e" => break Triage::Equivalent,
+                "i" => break Triage::Ignore,
+                "s" | "" => continue 'mutants,
+                "q" => break 'mutants,
+                _ => writeln!(output, "Unknown choice '{answer}'")?,
+            }
+        };
+
+        if let (Triage::Equivalent, Some(path)) = (triage, quarantine) {
+            write!(output, "Reason (optional): ")?;
+            output.flush()?;
+            let reason = read_answer(&mut input)?.unwrap_or_default();
+            Quarantine::append(path, &mutant_id, &reason)?;
+            writeln!(output, "Mutant {mutant_id} added to {}", path.display())?;
+        }
+        report.get_mutants_mut()[index].set_triage(triage);
+        on_decision(report)?;
+        outcome.decided += 1;
+    }
+
+    outcome.undecided = report
+        .get_mutants()
+        .iter()
+        .filter(|m| m.status() == MutantStatus::Survived && m.triage().is_none())
+        .count();
+    Ok(outcome)
+}
+
+/// Describes the mutant with its ID, location and the previous decision, if any.
+fn describe(mutant: &MutationReport) -> String {
+    let mut description = format!(
+        "Mutant {} in {} ({}::{})",
+        mutant.mutant_id(),
+        mutant.original_file_path().display(),
+        mutant.get_module_name(),
+        mutant.get_function_name()
+    );
+    if let Some(triage) = mutant.triage() {
+        description.push_str(&format!(", previously marked as {triage}"));
+    }
+    description
+}
+
+/// Reads the trimmed answer, `None` at the end of the input.
+fn read_answer(input: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    Ok((input.read_line(&mut line)? > 0).then(|| line.trim().to_lowercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::{Mutation, Range};
+    use std::fs;
+
+    fn survivor(function: &str) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            "Sum",
+            function,
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(2, 3),
+            "binary_operator_replacement".to_owned(),
+            "+".to_owned(),
+            "-".to_owned(),
+        ));
+        entry.set_status(MutantStatus::Survived);
+        entry
+    }
+
+    #[test]
+    fn decisions_are_recorded() {
+        let dir = tempfile::tempdir().unwrap();
+        let quarantine = dir.path().join("quarantine.txt");
+        let mut report = Report::new();
+        for function in ["sum", "sub", "mul", "div"] {
+            report.add_entry(survivor(function));
+        }
+        let mut killed = survivor("add");
+        killed.set_status(MutantStatus::Killed);
+        report.add_entry(killed);
+        let equivalent_id = report.get_mutants()[1].mutant_id().to_owned();
+
+        let mut output = Vec::new();
+        let mut saves = 0;
+        let outcome = review(
+            &mut report,
+            Some(&quarantine),
+            false,
+            "n\nx\ne\nx - y == x + y\ns\n".as_bytes(),
+            &mut output,
+            |_| {
+                saves += 1;
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(outcome, ReviewOutcome {
+            decided: 2,
+            undecided: 2
+        });
+        assert_eq!(saves, 2);
+        let triages = report
+            .get_mutants()
+            .iter()
+            .map(MutationReport::triage)
+            .collect::<Vec<_>>();
+        assert_eq!(triages, vec![
+            Some(Triage::NeedsSpec),
+            Some(Triage::Equivalent),
+            None,
+            None,
+            None
+        ]);
+        assert_eq!(
+            fs::read_to_string(&quarantine).unwrap(),
+            format!("{equivalent_id} # x - y == x + y\n")
+        );
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("[1/4] Mutant "));
+        assert!(output.contains("-x + y\n+x - y\n"));
+        assert!(output.contains("Unknown choice 'x'"));
+
+        // Only the undecided mutants are reviewed again, unless all of them are requested.
+        let mut output = Vec::new();
+        let outcome = review(
+            &mut report,
+            None,
+            false,
+            "i\n".as_bytes(),
+            &mut output,
+            |_| Ok(()),
+        )
+        .unwrap();
+        assert_eq!(outcome.undecided, 1);
+        assert_eq!(report.get_mutants()[2].triage(), Some(Triage::Ignore));
+        assert!(String::from_utf8(output).unwrap().contains("[1/2] Mutant "));
+
+        let mut output = Vec::new();
+        review(
+            &mut report,
+            None,
+            true,
+            "q\n".as_bytes(),
+            &mut output,
+            |_| Ok(()),
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("[1/4] Mutant "));
+        assert!(output.contains("previously marked as needs-spec"));
+    }
+}
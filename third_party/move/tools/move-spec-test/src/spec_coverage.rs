@@ -0,0 +1,440 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::prover::build_model;
+use move_model::ast::ConditionKind;
+use move_mutator::report::{MutantStatus, Report};
+use move_package::BuildConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+use tabled::{builder::Builder, settings::Style};
+
+/// Single specification condition (e.g. `ensures` or `aborts_if` clause) of a function along with
+/// the number of mutants it killed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecCondition {
+    /// Qualified name (`<MODULE>::<FUNCTION>`) of the function the condition belongs to.
+    pub function: String,
+    /// Kind of the condition (e.g. `ensures`).
+    pub kind: String,
+    /// Source code of the condition (first line only).
+    pub condition: String,
+    /// Path of the file with the condition, relative to the package root.
+    pub file: PathBuf,
+    /// First line of the condition (1-based).
+    pub start_line: usize,
+    /// Last line of the condition (1-based).
+    pub end_line: usize,
+    /// Number of mutants killed by the condition.
+    pub kills: usize,
+}
+
+impl SpecCondition {
+    /// Checks if the location reported by the prover points at the condition.
+    fn contains(&self, file: &Path, line: usize) -> bool {
+        file.ends_with(&self.file) && (self.start_line..=self.end_line).contains(&line)
+    }
+}
+
+/// Collects the conditions which can kill a mutant (`ensures`, `aborts_if` and `aborts_with`) from
+/// the specifications of all the functions of the package (dependencies are skipped).
+///
+/// # Arguments
+///
+/// * `config` - A `BuildConfig` representing the build configuration.
+/// * `package_path` - A `Path` to the package root.
+///
+/// # Errors
+///
+/// Returns an error if the package cannot be compiled.
+///
+/// # Returns
+///
+/// * `anyhow::Result<Vec<SpecCondition>>` - the conditions (with no kills recorded yet).
+pub fn collect_spec_conditions(
+    config: &BuildConfig,
+    package_path: &Path,
+) -> anyhow::Result<Vec<SpecCondition>> {
+    let env = build_model(config, package_path)?;
+    let mut conditions = Vec::new();
+
+    for module in env.get_modules() {
+        for function in module.get_functions() {
+            let spec = function.get_spec();
+            for cond in spec.conditions.iter().filter(|c| {
+                matches!(
+                    c.kind,
+                    ConditionKind::Ensures | ConditionKind::AbortsIf | ConditionKind::AbortsWith
+                )
+            }) {
+                let Some((file, start)) = env.get_file_and_location(&cond.loc) else {
+                    continue;
+                };
+                let Ok(file) = Path::new(&file).strip_prefix(package_path) else {
+                    continue;
+                };
+                if file.starts_with("build") {
+                    continue;
+                }
+                let end = env
+                    .get_location(&cond.loc.at_end())
+                    .map_or(start.line, |end| end.line);
+                let condition = env
+                    .get_source(&cond.loc)
+                    .ok()
+                    .and_then(|source| source.lines().next())
+                    .unwrap_or_default()
+                    .trim()
+                    .to_owned();
+
+                conditions.push(SpecCondition {
+                    function: function.get_full_name_str(),
+                    kind: cond.kind.to_string(),
+                    condition,
+                    file: file.to_path_buf(),
+                    start_line: start.line.to_usize() + 1,
+                    end_line: end.to_usize() + 1,
+                    kills: 0,
+                });
+            }
+        }
+    }
+
+    Ok(conditions)
+}
+
+/// Counts the mutants killed by each condition, using the prover diagnostics stored for the killed
+/// mutants in the report. A mutant counts for every condition reported as failed by the prover.
+pub fn record_kills(conditions: &mut [SpecCondition], report: &Report) {
+    for entry in report.query().status(MutantStatus::Killed).iter() {
+        let Some(diagnostics) = entry.diagnostics() else {
+            continue;
+        };
+        let locations = error_locations(diagnostics);
+        let killers = conditions
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| locations.iter().any(|(file, line)| c.contains(file, *line)))
+            .map(|(index, _)| index)
+            .collect::<BTreeSet<_>>();
+        for index in killers {
+            conditions[index].kills += 1;
+        }
+    }
+}
+
+/// Finds the primary locations (file and 1-based line) of all the errors in the prover diagnostics.
+///
+/// The location follows the error header, e.g. `┌─ /tmp/prove/sources/Sum.move:8:9`.
+fn error_locations(diagnostics: &str) -> Vec<(PathBuf, usize)> {
+    let mut locations = Vec::new();
+    let mut in_error = false;
+
+    for line in diagnostics.lines() {
+        if line.starts_with("error") {
+            in_error = true;
+            continue;
+        }
+        if !in_error {
+            continue;
+        }
+        let line = line.trim_start();
+        let Some(location) = line.strip_prefix("┌─").or_else(|| line.strip_prefix("-->"))
+        else {
+            continue;
+        };
+        in_error = false;
+
+        let mut parts = location.trim().rsplitn(3, ':');
+        let (Some(_column), Some(number), Some(file)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        if let Ok(number) = number.parse() {
+            locations.push((PathBuf::from(file), number));
+        }
+    }
+
+    locations
+}
+
+/// Renders the conditions which never killed any mutant, so the specifications not contributing to
+/// the mutation score are visible.
+///
+/// # Returns
+///
+/// * `String` - the rendered conditions.
+pub fn render_spec_coverage(conditions: &[SpecCondition]) -> String {
+    let unused = conditions
+        .iter()
+        .filter(|c| c.kills == 0)
+        .collect::<Vec<_>>();
+    let mut output = format!(
+        "Spec conditions which never killed any mutant: {} out of {}\n",
+        unused.len(),
+        conditions.len()
+    );
+
+    for cond in unused {
+        let _ = writeln!(
+            output,
+            "  {} ({}:{}): {}",
+            cond.function,
+            cond.file.display(),
+            cond.start_line,
+            cond.condition
+        );
+    }
+
+    output
+}
+
+/// Saves the conditions with the number of mutants killed by each of them to the JSON file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_spec_coverage(path: &Path, conditions: &[SpecCondition]) -> anyhow::Result<()> {
+    info!("Saving spec coverage to {}", path.display());
+    Ok(std::fs::write(
+        path,
+        serde_json::to_vec_pretty(conditions)?,
+    )?)
+}
+
+/// Mutation score of the specification of a single function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecScore {
+    /// Qualified name of the function covered by the specification, or `None` for the functions
+    /// without any specification.
+    pub function: Option<String>,
+    /// Number of the conditions in the specification.
+    pub conditions: usize,
+    /// Number of the tested mutants of the function.
+    pub tested: usize,
+    /// Number of the killed mutants of the function.
+    pub killed: usize,
+}
+
+impl SpecScore {
+    fn new(function: Option<String>) -> Self {
+        Self {
+            function,
+            conditions: 0,
+            tested: 0,
+            killed: 0,
+        }
+    }
+
+    /// Returns the mutation score in percent.
+    pub fn percentage(&self) -> f64 {
+        if self.tested == 0 {
+            0.0
+        } else {
+            self.killed as f64 / self.tested as f64 * 100.0
+        }
+    }
+}
+
+/// Aggregates the results by the specification covering each mutated function, so it's visible
+/// which specifications are strong and which are only decorative. Mutants of the functions without
+/// any specification are aggregated together (they can still be killed by the specifications of
+/// their callers).
+///
+/// # Arguments
+///
+/// * `conditions` - the specification conditions of the package.
+/// * `report` - the mutator report with the status of each mutant.
+///
+/// # Returns
+///
+/// * `Vec<SpecScore>` - the scores ordered by the function name, with the functions without any
+///   specification last.
+pub fn spec_scores(conditions: &[SpecCondition], report: &Report) -> Vec<SpecScore> {
+    let mut scores = BTreeMap::new();
+    for cond in conditions {
+        scores
+            .entry(Some(cond.function.clone()))
+            .or_insert_with(|| SpecScore::new(Some(cond.function.clone())))
+            .conditions += 1;
+    }
+
+    for entry in report.get_mutants() {
+        let killed = match entry.status() {
+            MutantStatus::Killed => true,
+            MutantStatus::Survived => false,
+            _ => continue,
+        };
+        let qname = format!("{}::{}", entry.get_module_name(), entry.get_function_name());
+        let key = Some(qname).filter(|qname| scores.contains_key(&Some(qname.clone())));
+        let score = scores
+            .entry(key.clone())
+            .or_insert_with(|| SpecScore::new(key));
+        score.tested += 1;
+        score.killed += usize::from(killed);
+    }
+
+    let mut scores = scores.into_values().collect::<Vec<_>>();
+    // Functions without any specification (`None`) are ordered first by the map.
+    if scores.first().is_some_and(|s| s.function.is_none()) {
+        scores.rotate_left(1);
+    }
+    scores
+}
+
+/// Renders the scores of the specifications as a table.
+///
+/// # Returns
+///
+/// * `String` - the rendered table.
+pub fn render_spec_scores(scores: &[SpecScore]) -> String {
+    let mut builder = Builder::new();
+    builder.push_record([
+        "Spec",
+        "Conditions",
+        "Mutants tested",
+        "Mutants killed",
+        "Percentage",
+    ]);
+
+    for score in scores {
+        builder.push_record([
+            score
+                .function
+                .clone()
+                .unwrap_or_else(|| "(functions without spec)".to_owned()),
+            score.conditions.to_string(),
+            score.tested.to_string(),
+            score.killed.to_string(),
+            format!("{:.2}%", score.percentage()),
+        ]);
+    }
+
+    builder.build().with(Style::modern_rounded()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::MutationReport;
+
+    fn condition(kind: &str, condition: &str, line: usize) -> SpecCondition {
+        SpecCondition {
+            function: "Sum::sum".to_owned(),
+            kind: kind.to_owned(),
+            condition: condition.to_owned(),
+            file: PathBuf::from("sources/Sum.move"),
+            start_line: line,
+            end_line: line,
+            kills: 0,
+        }
+    }
+
+    fn mutant(function: &str, status: MutantStatus) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            "Sum",
+            function,
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.set_status(status);
+        entry
+    }
+
+    fn killed(diagnostics: &str) -> MutationReport {
+        let mut entry = mutant("sum", MutantStatus::Killed);
+        entry.set_diagnostics(diagnostics);
+        entry
+    }
+
+    #[test]
+    fn kills_are_attributed_to_failed_conditions() {
+        let mut conditions = vec![
+            condition("ensures", "ensures result == x + y;", 8),
+            condition("aborts_if", "aborts_if x + y > MAX_U64;", 9),
+        ];
+
+        let mut report = Report::new();
+        report.add_entry(killed(
+            "\
+error: post-condition does not hold
+   ┌─ /tmp/old/prove/sources/Sum.move:8:9
+   │
+ 8 │         ensures result == x + y;
+   │
+   =     at /tmp/old/prove/sources/Sum.move:2: sum
+",
+        ));
+        report.add_entry(killed(
+            "\
+error: post-condition does not hold
+   ┌─ /tmp/prove/sources/Sum.move:8:9
+
+error: post-condition does not hold
+   ┌─ /tmp/prove/sources/Sum.move:8:9
+",
+        ));
+        report.add_entry(killed("error: unit tests failed\n"));
+
+        record_kills(&mut conditions, &report);
+        assert_eq!(conditions[0].kills, 2);
+        assert_eq!(conditions[1].kills, 0);
+
+        let output = render_spec_coverage(&conditions);
+        assert!(output.starts_with("Spec conditions which never killed any mutant: 1 out of 2\n"));
+        assert!(output.contains("Sum::sum (sources/Sum.move:9): aborts_if x + y > MAX_U64;"));
+        assert!(!output.contains("ensures"));
+    }
+
+    #[test]
+    fn scores_are_aggregated_by_spec() {
+        let mut conditions = vec![
+            condition("ensures", "ensures result == x + y;", 8),
+            condition("aborts_if", "aborts_if x + y > MAX_U64;", 9),
+        ];
+        conditions.push(SpecCondition {
+            function: "Sum::mul".to_owned(),
+            ..condition("ensures", "ensures result == x * y;", 14)
+        });
+
+        let mut report = Report::new();
+        report.add_entry(mutant("sum", MutantStatus::Killed));
+        report.add_entry(mutant("sum", MutantStatus::Survived));
+        report.add_entry(mutant("sum", MutantStatus::Timeout));
+        report.add_entry(mutant("helper", MutantStatus::Killed));
+
+        let scores = spec_scores(&conditions, &report);
+        assert_eq!(scores, vec![
+            SpecScore {
+                function: Some("Sum::mul".to_owned()),
+                conditions: 1,
+                tested: 0,
+                killed: 0,
+            },
+            SpecScore {
+                function: Some("Sum::sum".to_owned()),
+                conditions: 2,
+                tested: 2,
+                killed: 1,
+            },
+            SpecScore {
+                function: None,
+                conditions: 0,
+                tested: 1,
+                killed: 1,
+            },
+        ]);
+        assert_eq!(scores[1].percentage(), 50.0);
+
+        let table = render_spec_scores(&scores);
+        assert!(table.contains("(functions without spec)"));
+        assert!(table.contains("50.00%"));
+    }
+}
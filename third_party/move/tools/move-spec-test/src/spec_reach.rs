@@ -0,0 +1,197 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::prover::build_model;
+use move_model::{
+    ast::{ExpData, Spec},
+    model::{FunId, FunctionEnv, QualifiedId},
+};
+use move_mutator::report::{MutantStatus, Report};
+use move_package::BuildConfig;
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Write,
+    path::Path,
+};
+
+/// Reachability of the functions of the package by the specifications.
+///
+/// A function is reached if it has a specification with any conditions (including the inline spec
+/// blocks), is declared in a module with invariants or is called (directly or transitively) by a
+/// reached function. Mutants of the functions outside any spec's reach survive because the
+/// specification is missing, not weak.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct SpecReach {
+    /// Qualified names (`<MODULE>::<FUNCTION>`) of the functions of the package, along with the
+    /// information whether they are reached by any specification.
+    functions: BTreeMap<String, bool>,
+}
+
+impl SpecReach {
+    /// Collects the reachability of all the functions of the package (dependencies are skipped).
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A `BuildConfig` representing the build configuration.
+    /// * `package_path` - A `Path` to the package root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the package cannot be compiled.
+    pub(crate) fn collect(config: &BuildConfig, package_path: &Path) -> anyhow::Result<Self> {
+        let env = build_model(config, package_path)?;
+        let modules = env
+            .get_modules()
+            .filter(|module| {
+                Path::new(module.get_source_path())
+                    .strip_prefix(package_path)
+                    .is_ok_and(|file| !file.starts_with("build"))
+            })
+            .collect::<Vec<_>>();
+
+        let mut reached: BTreeSet<QualifiedId<FunId>> = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        for module in &modules {
+            let invariants = !module.get_spec().conditions.is_empty()
+                || module
+                    .get_structs()
+                    .any(|s| !s.get_spec().conditions.is_empty());
+            for function in module.get_functions() {
+                if (invariants || has_spec(&function))
+                    && reached.insert(function.get_qualified_id())
+                {
+                    queue.push_back(function.get_qualified_id());
+                }
+            }
+        }
+
+        // Functions called by the reached ones are verified along with them.
+        while let Some(id) = queue.pop_front() {
+            for callee in env
+                .get_function(id)
+                .get_called_functions()
+                .into_iter()
+                .flatten()
+            {
+                if reached.insert(*callee) {
+                    queue.push_back(*callee);
+                }
+            }
+        }
+
+        let functions = modules
+            .iter()
+            .flat_map(|module| module.get_functions())
+            .map(|f| {
+                (
+                    f.get_full_name_str(),
+                    reached.contains(&f.get_qualified_id()),
+                )
+            })
+            .collect();
+        Ok(Self { functions })
+    }
+
+    /// Checks if the function is reached by any specification. Functions unknown to the package
+    /// (e.g. scripts) are considered reached, so their mutants are never misclassified.
+    pub(crate) fn is_reached(&self, function: &str) -> bool {
+        self.functions.get(function).copied().unwrap_or(true)
+    }
+
+    /// Renders the summary of the code outside any spec's reach: the functions along with their
+    /// `no-spec` mutants.
+    pub(crate) fn render(&self, report: &Report) -> String {
+        let mut mutants = BTreeMap::<String, usize>::new();
+        for entry in report.query().status(MutantStatus::NoSpec).iter() {
+            *mutants
+                .entry(format!(
+                    "{}::{}",
+                    entry.get_module_name(),
+                    entry.get_function_name()
+                ))
+                .or_default() += 1;
+        }
+
+        let unreached = self
+            .functions
+            .iter()
+            .filter(|(_, reached)| !**reached)
+            .map(|(function, _)| function)
+            .collect::<Vec<_>>();
+        let mut output = format!(
+            "Functions outside any spec's reach: {} out of {}\n",
+            unreached.len(),
+            self.functions.len()
+        );
+        for function in unreached {
+            let _ = writeln!(
+                output,
+                "  {function} ({} no-spec mutants)",
+                mutants.get(function).copied().unwrap_or_default()
+            );
+        }
+        let _ = writeln!(
+            output,
+            "Mutants outside any spec's reach: {} out of {}",
+            mutants.values().sum::<usize>(),
+            report.get_mutants().len()
+        );
+        output
+    }
+}
+
+/// Checks if the function has any specification conditions, either in its spec block or inlined in
+/// its body.
+fn has_spec(function: &FunctionEnv<'_>) -> bool {
+    let spec = function.get_spec();
+    spec.has_conditions()
+        || spec.on_impl.values().any(Spec::has_conditions)
+        || function.get_def().is_some_and(|def| {
+            def.any(&mut |e| matches!(e, ExpData::SpecBlock(_, spec) if spec.has_conditions()))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::MutationReport;
+
+    #[test]
+    fn unreached_functions_are_summarized() {
+        let reach = SpecReach {
+            functions: BTreeMap::from([
+                ("Sum::sum".to_owned(), true),
+                ("Sum::helper".to_owned(), false),
+                ("Sum::unused".to_owned(), false),
+            ]),
+        };
+        assert!(reach.is_reached("Sum::sum"));
+        assert!(!reach.is_reached("Sum::helper"));
+        assert!(reach.is_reached("script::main"));
+
+        let mut report = Report::new();
+        for (function, status) in [
+            ("sum", MutantStatus::Survived),
+            ("helper", MutantStatus::NoSpec),
+            ("helper", MutantStatus::NoSpec),
+        ] {
+            let mut entry = MutationReport::new(
+                Path::new("mutant.move"),
+                Path::new("sources/Sum.move"),
+                "Sum",
+                function,
+                "x - y\n",
+                "x + y\n",
+            );
+            entry.set_status(status);
+            report.add_entry(entry);
+        }
+
+        assert_eq!(
+            reach.render(&report),
+            "Functions outside any spec's reach: 2 out of 3\n  Sum::helper (2 no-spec mutants)\n  \
+             Sum::unused (0 no-spec mutants)\nMutants outside any spec's reach: 2 out of 3\n"
+        );
+    }
+}
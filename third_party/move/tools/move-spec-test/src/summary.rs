@@ -0,0 +1,300 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    cache::CacheStats,
+    report::{self, Score},
+};
+use move_mutator::report::MutantStatus;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt, path::Path, time::Duration};
+
+/// Exit code of a run which failed because of an internal error (e.g. invalid options or a crash).
+pub const EXIT_INTERNAL_ERROR: i32 = 1;
+/// Exit code of a run where the mutation score is below the threshold (see `--min-score`).
+pub const EXIT_SCORE_BELOW_THRESHOLD: i32 = 2;
+/// Exit code of a run where the original package doesn't verify.
+pub const EXIT_BASELINE_FAILED: i32 = 3;
+/// Exit code of a run stopped on the first surviving mutant (see `--fail-fast`).
+pub const EXIT_MUTANT_SURVIVED: i32 = 4;
+/// Exit code of a check finding new surviving mutants compared to the baseline report.
+pub const EXIT_NEW_SURVIVORS: i32 = 5;
+
+/// Expected failure of the specification test, distinguished from internal errors by its exit code.
+#[derive(Debug)]
+pub enum SpecTestFailure {
+    /// The mutation score is below the threshold.
+    ScoreBelowThreshold(String),
+    /// The original package doesn't verify.
+    BaselineFailed(String),
+    /// A mutant survived and the run was stopped (see `--fail-fast`).
+    MutantSurvived(String),
+    /// New mutants survive compared to the baseline report (see `spec-test check`).
+    NewSurvivors(String),
+}
+
+impl SpecTestFailure {
+    /// Returns the exit code of the failure.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            SpecTestFailure::ScoreBelowThreshold(_) => EXIT_SCORE_BELOW_THRESHOLD,
+            SpecTestFailure::BaselineFailed(_) => EXIT_BASELINE_FAILED,
+            SpecTestFailure::MutantSurvived(_) => EXIT_MUTANT_SURVIVED,
+            SpecTestFailure::NewSurvivors(_) => EXIT_NEW_SURVIVORS,
+        }
+    }
+}
+
+impl fmt::Display for SpecTestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecTestFailure::ScoreBelowThreshold(msg)
+            | SpecTestFailure::BaselineFailed(msg)
+            | SpecTestFailure::MutantSurvived(msg)
+            | SpecTestFailure::NewSurvivors(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl std::error::Error for SpecTestFailure {}
+
+/// Returns the exit code for the error returned by the specification test.
+///
+/// # Returns
+///
+/// * `i32` - the exit code of the `SpecTestFailure` or `EXIT_INTERNAL_ERROR` for any other error.
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    error
+        .downcast_ref::<SpecTestFailure>()
+        .map_or(EXIT_INTERNAL_ERROR, SpecTestFailure::exit_code)
+}
+
+/// Outcome of the specification test run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    /// All the mutants were tested and the thresholds are met.
+    Passed,
+    /// The mutation score is below the threshold.
+    ScoreBelowThreshold,
+    /// The original package doesn't verify, so no mutants were tested.
+    BaselineFailed,
+    /// A mutant survived and the run was stopped (see `--fail-fast`).
+    MutantSurvived,
+}
+
+impl RunOutcome {
+    /// Returns the exit code of the run.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RunOutcome::Passed => 0,
+            RunOutcome::ScoreBelowThreshold => EXIT_SCORE_BELOW_THRESHOLD,
+            RunOutcome::BaselineFailed => EXIT_BASELINE_FAILED,
+            RunOutcome::MutantSurvived => EXIT_MUTANT_SURVIVED,
+        }
+    }
+}
+
+/// Compact machine-readable summary of the run, meant for automation instead of the logs.
+#[derive(Debug, Serialize)]
+pub struct RunSummary {
+    /// Outcome of the run.
+    pub outcome: RunOutcome,
+    /// Exit code of the run.
+    pub exit_code: i32,
+    /// Duration of the whole run in seconds.
+    pub duration_secs: f64,
+    /// Number of the mutants selected for the run.
+    pub mutants: usize,
+    /// Number of the tested mutants (killed or survived).
+    pub tested: u32,
+    /// Number of the killed mutants.
+    pub killed: u32,
+    /// Number of the mutants which exceeded the prover timeout.
+    pub timed_out: usize,
+    /// Number of the mutants which exceeded the prover memory limit.
+    pub out_of_memory: usize,
+    /// Number of the mutants skipped because the time budget was exceeded.
+    pub skipped: usize,
+    /// Number of the mutants quarantined as equivalent.
+    pub equivalent: usize,
+    /// Number of the mutants which survived outside any spec's reach.
+    pub no_spec: usize,
+    /// The total mutation score in percent.
+    pub score: f64,
+    /// Scores per module.
+    pub modules: BTreeMap<String, Score>,
+    /// Descriptions of the thresholds which are not met.
+    pub violations: Vec<String>,
+    /// Use of the prover cache, if the cache is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prover_cache: Option<CacheStats>,
+}
+
+impl RunSummary {
+    /// Creates the summary of the run which tested the mutants.
+    pub fn new(
+        spec_report: &report::Report,
+        mutator_report: &move_mutator::report::Report,
+        violations: Vec<String>,
+        duration: Duration,
+    ) -> Self {
+        let outcome = if violations.is_empty() {
+            RunOutcome::Passed
+        } else {
+            RunOutcome::ScoreBelowThreshold
+        };
+
+        Self {
+            outcome,
+            exit_code: outcome.exit_code(),
+            duration_secs: duration.as_secs_f64(),
+            mutants: mutator_report.get_mutants().len(),
+            tested: spec_report.mutants_tested(),
+            killed: spec_report.mutants_killed(),
+            timed_out: mutator_report.query().status(MutantStatus::Timeout).len(),
+            out_of_memory: mutator_report
+                .query()
+                .status(MutantStatus::OutOfMemory)
+                .len(),
+            skipped: mutator_report.query().status(MutantStatus::Skipped).len(),
+            equivalent: mutator_report
+                .query()
+                .status(MutantStatus::Equivalent)
+                .len(),
+            no_spec: mutator_report.query().status(MutantStatus::NoSpec).len(),
+            score: spec_report.mutation_score(),
+            modules: spec_report.module_scores(),
+            violations,
+            prover_cache: None,
+        }
+    }
+
+    /// Records the use of the prover cache during the run.
+    pub fn with_cache_stats(mut self, stats: CacheStats) -> Self {
+        self.prover_cache = Some(stats);
+        self
+    }
+
+    /// Marks the run as stopped on the first surviving mutant (see `--fail-fast`).
+    pub fn mutant_survived(mut self) -> Self {
+        self.outcome = RunOutcome::MutantSurvived;
+        self.exit_code = RunOutcome::MutantSurvived.exit_code();
+        self
+    }
+
+    /// Creates the summary of the run stopped because the original package doesn't verify.
+    pub fn baseline_failed(duration: Duration) -> Self {
+        Self {
+            outcome: RunOutcome::BaselineFailed,
+            exit_code: RunOutcome::BaselineFailed.exit_code(),
+            duration_secs: duration.as_secs_f64(),
+            mutants: 0,
+            tested: 0,
+            killed: 0,
+            timed_out: 0,
+            out_of_memory: 0,
+            skipped: 0,
+            equivalent: 0,
+            no_spec: 0,
+            score: 0.0,
+            modules: BTreeMap::new(),
+            violations: Vec::new(),
+            prover_cache: None,
+        }
+    }
+
+    /// Saves the summary to the JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        info!("Saving run summary to {}", path.display());
+        Ok(std::fs::write(path, serde_json::to_vec(self)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use move_mutator::report::MutationReport;
+    use std::path::PathBuf;
+
+    #[test]
+    fn failures_have_distinct_exit_codes() {
+        let threshold: anyhow::Error =
+            SpecTestFailure::ScoreBelowThreshold("score too low".to_owned()).into();
+        let baseline: anyhow::Error =
+            SpecTestFailure::BaselineFailed("verification failed".to_owned()).into();
+        let survived: anyhow::Error =
+            SpecTestFailure::MutantSurvived("mutant survived".to_owned()).into();
+        assert_eq!(exit_code(&threshold), EXIT_SCORE_BELOW_THRESHOLD);
+        assert_eq!(exit_code(&baseline), EXIT_BASELINE_FAILED);
+        assert_eq!(exit_code(&survived), EXIT_MUTANT_SURVIVED);
+        assert_eq!(exit_code(&anyhow!("crash")), EXIT_INTERNAL_ERROR);
+        assert_eq!(threshold.to_string(), "score too low");
+    }
+
+    #[test]
+    fn summary_contains_totals_and_modules() {
+        let path = PathBuf::from("sources/Sum.move");
+        let mut spec_report = report::Report::new();
+        spec_report.increment_mutants_tested(&path, "Sum::sum");
+        spec_report.increment_mutants_tested(&path, "Sum::sum");
+        spec_report.increment_mutants_killed(&path, "Sum::sum");
+
+        let mut mutator_report = move_mutator::report::Report::new();
+        for status in [
+            MutantStatus::Killed,
+            MutantStatus::Survived,
+            MutantStatus::Skipped,
+            MutantStatus::Equivalent,
+            MutantStatus::NoSpec,
+            MutantStatus::OutOfMemory,
+        ] {
+            let mut entry = MutationReport::new(
+                Path::new("mutant.move"),
+                &path,
+                "Sum",
+                "sum",
+                "x - y\n",
+                "x + y\n",
+            );
+            entry.set_status(status);
+            mutator_report.add_entry(entry);
+        }
+
+        let violations =
+            vec!["Total mutation score 50.00% is below the threshold 80.00%".to_owned()];
+        let summary = RunSummary::new(
+            &spec_report,
+            &mutator_report,
+            violations,
+            Duration::from_millis(1500),
+        );
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["outcome"], "score_below_threshold");
+        assert_eq!(json["exit_code"], EXIT_SCORE_BELOW_THRESHOLD);
+        assert_eq!(json["duration_secs"], 1.5);
+        assert_eq!(json["mutants"], 6);
+        assert_eq!(json["tested"], 2);
+        assert_eq!(json["killed"], 1);
+        assert_eq!(json["skipped"], 1);
+        assert_eq!(json["equivalent"], 1);
+        assert_eq!(json["no_spec"], 1);
+        assert_eq!(json["out_of_memory"], 1);
+        assert_eq!(json["score"], 50.0);
+        assert_eq!(json["modules"]["Sum"]["tested"], 2);
+
+        let survived = serde_json::to_value(summary.mutant_survived()).unwrap();
+        assert_eq!(survived["outcome"], "mutant_survived");
+        assert_eq!(survived["exit_code"], EXIT_MUTANT_SURVIVED);
+
+        let baseline = serde_json::to_value(RunSummary::baseline_failed(Duration::ZERO)).unwrap();
+        assert_eq!(baseline["outcome"], "baseline_failed");
+        assert_eq!(baseline["exit_code"], EXIT_BASELINE_FAILED);
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_mutator::report::{MutantStatus, Report};
+use std::{collections::BTreeSet, fmt::Write, path::Path};
+
+/// Renders the diffs of all the surviving mutants grouped by module.
+///
+/// Each module section lists its surviving mutants with their location, mutations and diff, so it's
+/// immediately visible which code changes the specifications failed to notice.
+///
+/// # Arguments
+///
+/// * `report` - the mutator report with the status of each mutant.
+///
+/// # Returns
+///
+/// * `String` - the rendered diffs (empty if there are no surviving mutants).
+pub fn render_survivor_diffs(report: &Report) -> String {
+    let mut output = String::new();
+
+    for (module, mutants) in report
+        .query()
+        .status(MutantStatus::Survived)
+        .group_by_module()
+    {
+        let _ = writeln!(
+            output,
+            "=== Module {module}: {} surviving mutant(s) ===\n",
+            mutants.len()
+        );
+
+        for entry in mutants.iter() {
+            let mutations = entry
+                .get_mutations()
+                .iter()
+                .map(|m| {
+                    format!(
+                        "{}: `{}` -> `{}`",
+                        m.get_operator_name(),
+                        m.get_old_value(),
+                        m.get_new_value()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                output,
+                "Mutant {} in {} ({module}::{}): {mutations}",
+                entry.mutant_id(),
+                entry.original_file_path().display(),
+                entry.get_function_name()
+            );
+
+            let diff = entry.get_diff();
+            if diff.is_empty() {
+                output.push_str("(diff not available)\n\n");
+            } else {
+                let _ = writeln!(output, "{}", diff.trim_end_matches('\n'));
+                output.push('\n');
+            }
+        }
+    }
+
+    output
+}
+
+/// Saves the diffs of all the surviving mutants grouped by module to the given file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn save_survivor_diffs(path: &Path, report: &Report) -> anyhow::Result<()> {
+    info!("Saving surviving mutant diffs to {}", path.display());
+    Ok(std::fs::write(path, render_survivor_diffs(report))?)
+}
+
+/// Keeps only the mutants which survived in the previous report, matching them by their stable IDs.
+///
+/// # Arguments
+///
+/// * `report` - the report with the newly generated mutants.
+/// * `previous` - the report with the mutant statuses from the previous run.
+///
+/// # Returns
+///
+/// * `usize` - the number of surviving mutants from the previous report which are missing in the
+///   report (e.g. because the code has changed).
+pub fn retain_survivors(report: &mut Report, previous: &Report) -> usize {
+    let survivors = previous
+        .query()
+        .status(MutantStatus::Survived)
+        .iter()
+        .map(|m| m.mutant_id())
+        .collect::<BTreeSet<_>>();
+
+    report
+        .get_mutants_mut()
+        .retain(|m| survivors.contains(m.mutant_id()));
+
+    survivors.len().saturating_sub(report.get_mutants().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_mutator::report::{Mutation, MutationReport, Range};
+
+    fn mutant(module: &str, status: MutantStatus) -> MutationReport {
+        let mut entry = MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            module,
+            "sum",
+            "x - y\n",
+            "x + y\n",
+        );
+        entry.add_modification(Mutation::new(
+            Range::new(2, 3),
+            "binary_operator_replacement".to_string(),
+            "+".to_string(),
+            "-".to_string(),
+        ));
+        entry.set_status(status);
+        entry
+    }
+
+    #[test]
+    fn survivor_diffs_are_grouped_by_module() {
+        let mut report = Report::new();
+        report.add_entry(mutant("Sum", MutantStatus::Survived));
+        report.add_entry(mutant("Sum", MutantStatus::Killed));
+        report.add_entry(mutant("Add", MutantStatus::Survived));
+        report.add_entry(mutant("Add", MutantStatus::Survived));
+
+        let output = render_survivor_diffs(&report);
+        let add = output
+            .find("=== Module Add: 2 surviving mutant(s) ===")
+            .unwrap();
+        let sum = output
+            .find("=== Module Sum: 1 surviving mutant(s) ===")
+            .unwrap();
+        assert!(add < sum);
+        assert_eq!(output.matches("-x + y\n+x - y\n").count(), 3);
+        assert!(output.contains("(Sum::sum): binary_operator_replacement: `+` -> `-`"));
+    }
+
+    #[test]
+    fn only_previous_survivors_are_retained() {
+        let killed = mutant("Sum", MutantStatus::Killed);
+        let mut survived = mutant("Sum", MutantStatus::Survived);
+        survived.add_modification(Mutation::new(
+            Range::new(0, 1),
+            "literal_replacement".to_string(),
+            "x".to_string(),
+            "y".to_string(),
+        ));
+        assert_ne!(killed.mutant_id(), survived.mutant_id());
+
+        let mut previous = Report::new();
+        previous.add_entry(killed.clone());
+        previous.add_entry(survived.clone());
+
+        let mut report = Report::new();
+        report.add_entry(killed);
+        report.add_entry(survived.clone());
+        assert_eq!(retain_survivors(&mut report, &previous), 0);
+        assert_eq!(report.get_mutants().len(), 1);
+        assert_eq!(report.get_mutants()[0].mutant_id(), survived.mutant_id());
+
+        let mut changed = Report::new();
+        assert_eq!(retain_survivors(&mut changed, &previous), 1);
+    }
+
+    #[test]
+    fn no_output_without_survivors() {
+        let mut report = Report::new();
+        report.add_entry(mutant("Sum", MutantStatus::Killed));
+        assert!(render_survivor_diffs(&report).is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_mutator::report::{MutationReport, Report};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path, time::Duration};
+
+/// Proving timings of the mutants of a single function.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct FunctionTiming {
+    /// Total proving time of the mutants in milliseconds.
+    total_ms: u64,
+    /// Number of the proved mutants.
+    mutants: u64,
+}
+
+impl FunctionTiming {
+    /// Returns the average proving time of a mutant in milliseconds.
+    fn average_ms(&self) -> u64 {
+        self.total_ms / self.mutants.max(1)
+    }
+}
+
+/// Proving timings of the functions collected from the previous runs, used to schedule the mutants
+/// by their expected cost.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    /// Timings keyed by the qualified function name (`<MODULE>::<FUNCTION>`).
+    functions: BTreeMap<String, FunctionTiming>,
+}
+
+impl Timings {
+    /// Loads the timings from the JSON file. Returns empty timings if the file doesn't exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or has an invalid format.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Saves the timings to the JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        info!("Saving prover timings to {}", path.display());
+        Ok(fs::write(path, serde_json::to_vec_pretty(self)?)?)
+    }
+
+    /// Records the proving time of a single mutant of the function.
+    pub fn record(&mut self, function: String, elapsed: Duration) {
+        let timing = self.functions.entry(function).or_default();
+        timing.total_ms += u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        timing.mutants += 1;
+    }
+
+    /// Updates the timings with the ones measured in the latest run. Functions proved in the latest
+    /// run replace the older timings, as they reflect the current code and specifications.
+    pub fn update(&mut self, latest: Timings) {
+        self.functions.extend(latest.functions);
+    }
+
+    /// Orders the mutants of the report by the expected proving time of their functions, so the
+    /// historically fast functions are proved first. Functions without timing data (e.g. new code)
+    /// are proved before all the others. The order of mutants within a function is kept.
+    pub fn schedule(&self, report: &mut Report) {
+        report
+            .get_mutants_mut()
+            .sort_by_key(|m| self.expected_cost(m).map_or((0, 0), |cost| (1, cost)));
+    }
+
+    /// Returns the expected proving time (in milliseconds) of the mutant, if known.
+    pub(crate) fn expected_cost(&self, mutant: &MutationReport) -> Option<u64> {
+        self.functions
+            .get(&qualified_name(mutant))
+            .map(FunctionTiming::average_ms)
+    }
+}
+
+/// Returns the qualified name of the function of the mutant.
+fn qualified_name(mutant: &MutationReport) -> String {
+    format!(
+        "{}::{}",
+        mutant.get_module_name(),
+        mutant.get_function_name()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mutant(function: &str, mutated: &str) -> MutationReport {
+        MutationReport::new(
+            Path::new("mutant.move"),
+            Path::new("sources/Sum.move"),
+            "Sum",
+            function,
+            mutated,
+            "x + y\n",
+        )
+    }
+
+    #[test]
+    fn mutants_are_scheduled_by_expected_cost() {
+        let mut timings = Timings::default();
+        timings.record("Sum::slow".to_owned(), Duration::from_secs(10));
+        timings.record("Sum::fast".to_owned(), Duration::from_millis(100));
+        timings.record("Sum::fast".to_owned(), Duration::from_millis(300));
+        assert_eq!(timings.expected_cost(&mutant("fast", "x - y\n")), Some(200));
+
+        let mut report = Report::new();
+        report.add_entry(mutant("slow", "x - y\n"));
+        report.add_entry(mutant("fast", "x - y\n"));
+        report.add_entry(mutant("new", "x - y\n"));
+        report.add_entry(mutant("fast", "x * y\n"));
+
+        timings.schedule(&mut report);
+        let order = report
+            .get_mutants()
+            .iter()
+            .map(|m| (m.get_function_name(), m.get_diff().contains("x * y")))
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec![
+            ("new", false),
+            ("fast", false),
+            ("fast", true),
+            ("slow", false)
+        ]);
+    }
+
+    #[test]
+    fn timings_are_updated_and_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("timings.json");
+
+        let mut timings = Timings::load(&path).unwrap();
+        timings.record("Sum::sum".to_owned(), Duration::from_millis(500));
+        timings.record("Sum::mul".to_owned(), Duration::from_millis(700));
+
+        let mut latest = Timings::default();
+        latest.record("Sum::sum".to_owned(), Duration::from_millis(100));
+        timings.update(latest);
+        timings.save(&path).unwrap();
+
+        let loaded = Timings::load(&path).unwrap();
+        assert_eq!(loaded, timings);
+        assert_eq!(loaded.functions["Sum::sum"].average_ms(), 100);
+        assert_eq!(loaded.functions["Sum::mul"].average_ms(), 700);
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright © Eiger
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    cli::{CLIOptions, WebhookFormat},
+    diff::ReportDiff,
+    report::Report,
+    summary::{RunOutcome, RunSummary},
+};
+use anyhow::anyhow;
+use serde_json::json;
+use std::time::Duration;
+
+/// Timeout of delivering the notification.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Notifies the webhook given in the options (if any) about the completed run.
+///
+/// If the baseline report is given with `--webhook-on-regression`, the webhook is notified only when
+/// the run is a regression against it (or when the original package doesn't verify). Failing to
+/// deliver the notification doesn't fail the run, as the results are already saved.
+///
+/// # Arguments
+///
+/// * `options` - the command line options.
+/// * `summary` - the summary of the run.
+/// * `spec_report` - the report of the run, `None` if no mutants were tested.
+pub(crate) fn notify(options: &CLIOptions, summary: &RunSummary, spec_report: Option<&Report>) {
+    let Some(url) = &options.webhook else {
+        return;
+    };
+
+    let score_delta = match (&options.webhook_on_regression, spec_report) {
+        (Some(baseline_path), Some(report)) => match Report::load_from_json_file(baseline_path) {
+            Ok(baseline) => {
+                let diff = ReportDiff::new(&baseline, report);
+                if !diff.is_regression() {
+                    info!(
+                        "No regression against {}, webhook not notified",
+                        baseline_path.display()
+                    );
+                    return;
+                }
+                Some(diff.score_delta())
+            },
+            Err(e) => {
+                warn!("Can't load the baseline report, webhook not notified: {e}");
+                return;
+            },
+        },
+        _ => None,
+    };
+
+    let payload = payload(summary, options.webhook_format, score_delta);
+    match post(url, &payload) {
+        Ok(()) => info!("Webhook notified about the run"),
+        Err(e) => warn!("Failed to notify the webhook: {e}"),
+    }
+}
+
+/// Creates the payload of the notification in the given format. The generic format is the JSON
+/// summary of the run, while Slack and Teams get a message with its overview.
+fn payload(
+    summary: &RunSummary,
+    format: WebhookFormat,
+    score_delta: Option<f64>,
+) -> serde_json::Value {
+    let text = message(summary, score_delta);
+    match format {
+        WebhookFormat::Generic => json!(summary),
+        WebhookFormat::Slack => json!({ "text": text }),
+        WebhookFormat::Teams => json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": "Specification test",
+            "text": text,
+        }),
+    }
+}
+
+/// Renders the overview of the run.
+fn message(summary: &RunSummary, score_delta: Option<f64>) -> String {
+    let status = match summary.outcome {
+        RunOutcome::Passed => "passed",
+        RunOutcome::ScoreBelowThreshold => "failed (score below the threshold)",
+        RunOutcome::BaselineFailed => "failed (the original package doesn't verify)",
+        RunOutcome::MutantSurvived => "failed (stopped on the first surviving mutant)",
+    };
+    let mut text = format!(
+        "Specification test {status}: mutation score {:.2}% ({} out of {} mutants killed) in {:.0}s",
+        summary.score, summary.killed, summary.tested, summary.duration_secs
+    );
+    if let Some(delta) = score_delta {
+        text.push_str(&format!(", regression: score change {delta:+.2}"));
+    }
+    for violation in &summary.violations {
+        text.push_str(&format!("\n{violation}"));
+    }
+    text
+}
+
+/// Posts the payload to the URL.
+fn post(url: &str, payload: &serde_json::Value) -> anyhow::Result<()> {
+    let response = reqwest::blocking::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()?
+        .post(url)
+        .json(payload)
+        .send()?;
+
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        status => Err(anyhow!("webhook responded with HTTP {}", status.as_u16())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        collections::BTreeMap,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    fn summary() -> RunSummary {
+        RunSummary {
+            outcome: RunOutcome::ScoreBelowThreshold,
+            exit_code: 2,
+            duration_secs: 61.4,
+            mutants: 10,
+            tested: 8,
+            killed: 6,
+            timed_out: 2,
+            out_of_memory: 0,
+            skipped: 0,
+            equivalent: 0,
+            no_spec: 0,
+            score: 75.0,
+            modules: BTreeMap::new(),
+            violations: vec!["Total mutation score 75.00% is below the threshold 80.00%".to_owned()],
+            prover_cache: None,
+        }
+    }
+
+    #[test]
+    fn payloads_match_the_formats() {
+        let summary = summary();
+        let text = "Specification test failed (score below the threshold): mutation score 75.00% (6 \
+                    out of 8 mutants killed) in 61s, regression: score change -5.00\nTotal mutation \
+                    score 75.00% is below the threshold 80.00%";
+
+        let generic = payload(&summary, WebhookFormat::Generic, Some(-5.0));
+        assert_eq!(generic["outcome"], "score_below_threshold");
+        assert_eq!(generic["killed"], 6);
+        assert_eq!(
+            payload(&summary, WebhookFormat::Slack, Some(-5.0)),
+            json!({
+                "text": text
+            })
+        );
+        let teams = payload(&summary, WebhookFormat::Teams, Some(-5.0));
+        assert_eq!(teams["@type"], "MessageCard");
+        assert_eq!(teams["text"], text);
+    }
+
+    #[test]
+    fn payload_is_posted_as_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = Vec::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push(line);
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok")
+                .unwrap();
+            (head, body)
+        });
+
+        post(&url, &json!({ "text": "done" })).unwrap();
+        let (head, body) = server.join().unwrap();
+        assert!(head[0].starts_with("POST /hook "));
+        assert!(head
+            .iter()
+            .any(|line| line.eq_ignore_ascii_case("content-type: application/json\r\n")));
+        assert_eq!(body, br#"{"text":"done"}"#);
+    }
+}